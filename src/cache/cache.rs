@@ -3,14 +3,86 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
+use core::grid::ExtentInt;
 use std::io::Read;
 use std::io;
 
 
 pub trait Cache {
-    fn read<F>(&self, path: &str, read: F) -> bool where F: FnMut(&mut Read);
+    /// Call `read` with cached tile data. Returns `Ok(false)` if the tile is not cached.
+    fn read<F>(&self, path: &str, read: F) -> Result<bool, io::Error> where F: FnMut(&mut Read);
+    /// Like `read`, but includes tiles which are expired.
+    fn read_stale<F>(&self, path: &str, read: F) -> Result<bool, io::Error>
+        where F: FnMut(&mut Read)
+    {
+        self.read(path, read)
+    }
+    /// Cached tile data. Returns `Ok(None)` if the tile is not cached.
+    fn read_tile(&self, path: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        let mut tile = None;
+        try!(self.read(path, |f| {
+            let mut data = Vec::new();
+            tile = Some(f.read_to_end(&mut data).map(|_| data));
+        }));
+        tile.map_or(Ok(None), |res| res.map(Some))
+    }
+    /// Like `read_tile`, but includes tiles which are expired.
+    fn read_tile_stale(&self, path: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        let mut tile = None;
+        try!(self.read_stale(path, |f| {
+            let mut data = Vec::new();
+            tile = Some(f.read_to_end(&mut data).map(|_| data));
+        }));
+        tile.map_or(Ok(None), |res| res.map(Some))
+    }
     fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error>;
     fn exists(&self, path: &str) -> bool;
+    /// Remove cached tiles of `tileset` between `minzoom` and `maxzoom`, optionally
+    /// restricted to tile limits for each zoom level (XYZ scheme).
+    /// Returns the number of removed tiles.
+    fn purge(&self,
+             tileset: &str,
+             minzoom: u8,
+             maxzoom: u8,
+             limits: Option<&[ExtentInt]>)
+             -> Result<u64, io::Error>;
+}
+
+
+/// Tileset name and tile coordinates of a cache path `{tileset}/{z}/{x}/{y}.pbf`
+pub fn tile_path_coords(path: &str) -> Option<(&str, u8, u32, u32)> {
+    let parts: Vec<&str> = path.split('/').collect();
+    if parts.len() != 4 {
+        return None;
+    }
+    let y = parts[3].split('.').next().unwrap_or("");
+    match (parts[1].parse::<u8>(), parts[2].parse::<u32>(), y.parse::<u32>()) {
+        (Ok(z), Ok(x), Ok(y)) => Some((parts[0], z, x, y)),
+        _ => None,
+    }
+}
+
+/// Check whether a cache path `{tileset}/{z}/{x}/{y}.pbf` matches purge criteria
+pub fn path_matches(path: &str,
+                    tileset: &str,
+                    minzoom: u8,
+                    maxzoom: u8,
+                    limits: Option<&[ExtentInt]>)
+                    -> bool {
+    match tile_path_coords(path) {
+        Some((name, z, x, y)) => {
+            if name != tileset || z < minzoom || z > maxzoom {
+                return false;
+            }
+            match limits.and_then(|l| l.get(z as usize)) {
+                Some(limit) => {
+                    x >= limit.minx && x < limit.maxx && y >= limit.miny && y < limit.maxy
+                }
+                None => true,
+            }
+        }
+        None => false,
+    }
 }
 
 
@@ -18,10 +90,10 @@ pub struct Nocache;
 
 impl Cache for Nocache {
     #[allow(unused_variables)]
-    fn read<F>(&self, path: &str, read: F) -> bool
+    fn read<F>(&self, path: &str, read: F) -> Result<bool, io::Error>
         where F: FnMut(&mut Read)
     {
-        false
+        Ok(false)
     }
     #[allow(unused_variables)]
     fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error> {
@@ -31,4 +103,14 @@ impl Cache for Nocache {
     fn exists(&self, _path: &str) -> bool {
         false
     }
+
+    #[allow(unused_variables)]
+    fn purge(&self,
+             tileset: &str,
+             minzoom: u8,
+             maxzoom: u8,
+             limits: Option<&[ExtentInt]>)
+             -> Result<u64, io::Error> {
+        Ok(0)
+    }
 }
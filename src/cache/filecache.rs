@@ -3,41 +3,164 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
-use cache::cache::Cache;
+use cache::cache::{Cache, path_matches};
+use core::grid::ExtentInt;
 use std::fs::{self, File};
 use std::io::{self, Read, Write};
 use std::path::Path;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime};
 
 
+/// Unique suffix for temporary files of concurrent writers
+static TMPFILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
 pub struct Filecache {
     pub basepath: String,
+    /// Maximal age of cached tiles. Older tiles are treated as cache miss.
+    pub max_age_seconds: Option<u64>,
 }
 
-impl Cache for Filecache {
-    fn read<F>(&self, path: &str, mut read: F) -> bool
+impl Filecache {
+    fn read_file<F>(&self, fullpath: &str, mut read: F) -> Result<bool, io::Error>
         where F: FnMut(&mut Read)
     {
-        let fullpath = format!("{}/{}", self.basepath, path);
-        debug!("Filecache.read {}", fullpath);
-        match File::open(&fullpath) {
+        match File::open(fullpath) {
             Ok(mut f) => {
                 read(&mut f);
-                true
+                Ok(true)
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+    /// Read file into a buffer allocated with the file size
+    fn read_file_data(&self, fullpath: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        match File::open(fullpath) {
+            Ok(mut f) => {
+                let size = f.metadata().map(|meta| meta.len()).unwrap_or(0);
+                // One extra byte avoids a reallocation for detecting the end of file
+                let mut data = Vec::with_capacity(size as usize + 1);
+                try!(f.read_to_end(&mut data));
+                Ok(Some(data))
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+    /// Check whether tile file is older than `max_age_seconds`
+    fn expired(&self, fullpath: &str) -> bool {
+        match self.max_age_seconds {
+            None => false,
+            Some(max_age) => {
+                let modified = fs::metadata(fullpath).and_then(|meta| meta.modified());
+                match modified {
+                    Ok(mtime) => {
+                        SystemTime::now()
+                            .duration_since(mtime)
+                            .map(|age| age > Duration::from_secs(max_age))
+                            .unwrap_or(false) // mtime in the future
+                    }
+                    Err(_e) => true,
+                }
             }
-            Err(_e) => false,
         }
     }
+}
+
+impl Cache for Filecache {
+    fn read<F>(&self, path: &str, read: F) -> Result<bool, io::Error>
+        where F: FnMut(&mut Read)
+    {
+        let fullpath = format!("{}/{}", self.basepath, path);
+        debug!("Filecache.read {}", fullpath);
+        if self.expired(&fullpath) {
+            return Ok(false);
+        }
+        self.read_file(&fullpath, read)
+    }
+    fn read_stale<F>(&self, path: &str, read: F) -> Result<bool, io::Error>
+        where F: FnMut(&mut Read)
+    {
+        let fullpath = format!("{}/{}", self.basepath, path);
+        debug!("Filecache.read_stale {}", fullpath);
+        self.read_file(&fullpath, read)
+    }
+    fn read_tile(&self, path: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        let fullpath = format!("{}/{}", self.basepath, path);
+        debug!("Filecache.read_tile {}", fullpath);
+        if self.expired(&fullpath) {
+            return Ok(None);
+        }
+        self.read_file_data(&fullpath)
+    }
+    fn read_tile_stale(&self, path: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        let fullpath = format!("{}/{}", self.basepath, path);
+        debug!("Filecache.read_tile_stale {}", fullpath);
+        self.read_file_data(&fullpath)
+    }
     fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error> {
         let fullpath = format!("{}/{}", self.basepath, path);
         debug!("Filecache.write {}", fullpath);
         let p = Path::new(&fullpath);
         try!(fs::create_dir_all(p.parent().unwrap()));
-        let mut f = try!(File::create(&fullpath));
-        f.write_all(obj)
+        // Write into temporary file and rename it, which is atomic on the same filesystem.
+        // Concurrent readers see either the old or the complete new file.
+        let tmppath = format!("{}.tmp.{}.{}",
+                              fullpath,
+                              process::id(),
+                              TMPFILE_COUNTER.fetch_add(1, Ordering::SeqCst));
+        let written = File::create(&tmppath).and_then(|mut f| f.write_all(obj));
+        if let Err(e) = written {
+            let _ = fs::remove_file(&tmppath);
+            return Err(e);
+        }
+        fs::rename(&tmppath, &fullpath).map_err(|e| {
+                                                    let _ = fs::remove_file(&tmppath);
+                                                    e
+                                                })
     }
 
     fn exists(&self, path: &str) -> bool {
         let fullpath = format!("{}/{}", self.basepath, path);
-        Path::new(&fullpath).exists()
+        Path::new(&fullpath).exists() && !self.expired(&fullpath)
+    }
+
+    fn purge(&self,
+             tileset: &str,
+             minzoom: u8,
+             maxzoom: u8,
+             limits: Option<&[ExtentInt]>)
+             -> Result<u64, io::Error> {
+        let mut count = 0;
+        for zoom in minzoom..(maxzoom + 1) {
+            let zoompath = Path::new(&self.basepath)
+                .join(tileset)
+                .join(zoom.to_string());
+            if !zoompath.is_dir() {
+                continue;
+            }
+            for xentry in try!(fs::read_dir(&zoompath)) {
+                let xpath = try!(xentry).path();
+                if !xpath.is_dir() {
+                    continue;
+                }
+                for yentry in try!(fs::read_dir(&xpath)) {
+                    let ypath = try!(yentry).path();
+                    let relpath = format!("{}/{}/{}/{}",
+                                          tileset,
+                                          zoom,
+                                          xpath.file_name().unwrap().to_string_lossy(),
+                                          ypath.file_name().unwrap().to_string_lossy());
+                    if path_matches(&relpath, tileset, minzoom, maxzoom, limits) {
+                        debug!("Filecache.purge {}", relpath);
+                        try!(fs::remove_file(&ypath));
+                        count += 1;
+                    }
+                }
+            }
+        }
+        Ok(count)
     }
 }
@@ -18,23 +18,275 @@ fn test_dircache() {
     let basepath = format!("{}", &dir.display());
     let _ = fs::remove_dir_all(&basepath);
 
-    let cache = Filecache { basepath: basepath };
+    let cache = Filecache {
+        basepath: basepath,
+        max_age_seconds: None,
+    };
     let path = "tileset/0/1/2.pbf";
     let fullpath = format!("{}/{}", cache.basepath, path);
     let obj = "0123456789";
 
     // Cache miss
-    assert_eq!(cache.read(path, |_| {}), false);
+    assert_eq!(cache.read(path, |_| {}).unwrap(), false);
 
     // Write into cache
     let _ = cache.write(path, obj.as_bytes());
     assert!(Path::new(&fullpath).exists());
 
     // Cache hit
-    assert_eq!(cache.read(path, |_| {}), true);
+    assert_eq!(cache.read(path, |_| {}).unwrap(), true);
 
     // Read from cache
     let mut s = String::new();
-    cache.read(path, |f| { let _ = f.read_to_string(&mut s); });
+    cache.read(path, |f| { let _ = f.read_to_string(&mut s); }).unwrap();
     assert_eq!(&s, "0123456789");
 }
+
+#[test]
+fn test_dircache_expiration() {
+    use std::env;
+    use std::time::{Duration, SystemTime};
+    use filetime::{FileTime, set_file_times};
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_test_expiration");
+    let basepath = format!("{}", &dir.display());
+    let _ = fs::remove_dir_all(&basepath);
+
+    let cache = Filecache {
+        basepath: basepath,
+        max_age_seconds: Some(3600),
+    };
+    let path = "tileset/0/1/2.pbf";
+    let fullpath = format!("{}/{}", cache.basepath, path);
+    let _ = cache.write(path, "0123456789".as_bytes());
+
+    // Fresh tile
+    assert!(cache.exists(path));
+    assert_eq!(cache.read(path, |_| {}).unwrap(), true);
+
+    // Backdate tile by two hours
+    let mtime = SystemTime::now() - Duration::from_secs(7200);
+    let ft = FileTime::from_system_time(mtime);
+    set_file_times(&fullpath, ft, ft).unwrap();
+    assert!(!cache.exists(path));
+    assert_eq!(cache.read(path, |_| {}).unwrap(), false);
+    // Expired tiles are still available as stale tiles
+    let mut s = String::new();
+    assert_eq!(cache
+                   .read_stale(path, |f| { let _ = f.read_to_string(&mut s); })
+                   .unwrap(),
+               true);
+    assert_eq!(&s, "0123456789");
+    assert_eq!(cache.read_stale("tileset/0/1/3.pbf", |_| {}).unwrap(), false);
+
+    // Rewrite refreshes the tile
+    let _ = cache.write(path, "0123456789".as_bytes());
+    assert_eq!(cache.read(path, |_| {}).unwrap(), true);
+
+    // No expiration without max_age_seconds
+    set_file_times(&fullpath, ft, ft).unwrap();
+    let cache = Filecache {
+        basepath: cache.basepath.clone(),
+        max_age_seconds: None,
+    };
+    assert!(cache.exists(path));
+    assert_eq!(cache.read(path, |_| {}).unwrap(), true);
+}
+
+#[test]
+fn test_dircache_purge() {
+    use std::env;
+    use core::grid::ExtentInt;
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_test_purge");
+    let basepath = format!("{}", &dir.display());
+    let _ = fs::remove_dir_all(&basepath);
+
+    let cache = Filecache {
+        basepath: basepath,
+        max_age_seconds: None,
+    };
+    for path in &["tileset/0/0/0.pbf",
+                  "tileset/1/0/0.pbf",
+                  "tileset/1/1/0.pbf",
+                  "tileset/2/3/3.pbf",
+                  "other/1/0/0.pbf"] {
+        let _ = cache.write(path, "0123456789".as_bytes());
+    }
+
+    // Purge tiles within limits of level 1
+    let limits = vec![ExtentInt { minx: 0, miny: 0, maxx: 1, maxy: 1 },
+                      ExtentInt { minx: 1, miny: 0, maxx: 2, maxy: 2 },
+                      ExtentInt { minx: 0, miny: 0, maxx: 1, maxy: 1 }];
+    assert_eq!(cache.purge("tileset", 1, 2, Some(&limits)).unwrap(), 1);
+    assert!(cache.exists("tileset/1/0/0.pbf"));
+    assert!(!cache.exists("tileset/1/1/0.pbf"));
+    assert!(cache.exists("tileset/2/3/3.pbf"));
+
+    // Purge all zoom levels
+    assert_eq!(cache.purge("tileset", 0, 22, None).unwrap(), 3);
+    assert!(!cache.exists("tileset/0/0/0.pbf"));
+    assert!(cache.exists("other/1/0/0.pbf"));
+}
+
+#[test]
+fn test_dircache_concurrent_write() {
+    use std::env;
+    use std::sync::Arc;
+    use std::thread;
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_test_concurrent");
+    let basepath = format!("{}", &dir.display());
+    let _ = fs::remove_dir_all(&basepath);
+
+    let cache = Arc::new(Filecache {
+                             basepath: basepath,
+                             max_age_seconds: None,
+                         });
+    let path = "tileset/0/0/0.pbf";
+    const SIZE: usize = 1024 * 1024;
+
+    let writers: Vec<_> = (0..4u8)
+        .map(|i| {
+            let cache = cache.clone();
+            thread::spawn(move || for _ in 0..10 {
+                              let obj = vec![b'a' + i; SIZE];
+                              cache.write(path, &obj).unwrap();
+                          })
+        })
+        .collect();
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let cache = cache.clone();
+            thread::spawn(move || for _ in 0..50 {
+                              let mut data = Vec::new();
+                              if cache.read(path, |f| { let _ = f.read_to_end(&mut data); }).unwrap() {
+                                  assert_eq!(data.len(), SIZE);
+                                  assert!(data.iter().all(|b| *b == data[0]));
+                              }
+                          })
+        })
+        .collect();
+    for t in writers.into_iter().chain(readers.into_iter()) {
+        t.join().unwrap();
+    }
+
+    // No temporary files left
+    let entries = fs::read_dir(format!("{}/tileset/0/0", cache.basepath)).unwrap();
+    assert_eq!(entries.count(), 1);
+}
+
+#[test]
+fn test_read_tile() {
+    use std::env;
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_test_read_tile");
+    let basepath = format!("{}", &dir.display());
+    let _ = fs::remove_dir_all(&basepath);
+
+    let cache = Filecache {
+        basepath: basepath,
+        max_age_seconds: None,
+    };
+    let path = "tileset/0/1/2.pbf";
+    assert_eq!(cache.read_tile(path).unwrap(), None);
+    let obj = vec![7u8; 100_000];
+    cache.write(path, &obj).unwrap();
+    assert_eq!(cache.read_tile(path).unwrap(), Some(obj.clone()));
+    assert_eq!(cache.read_tile_stale(path).unwrap(), Some(obj));
+}
+
+/// Run with `cargo test bench_read_large_tiles -- --ignored --nocapture`
+#[test]
+#[ignore]
+fn bench_read_large_tiles() {
+    use std::env;
+    use std::time::Instant;
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_bench_read_tile");
+    let basepath = format!("{}", &dir.display());
+    let _ = fs::remove_dir_all(&basepath);
+
+    let cache = Filecache {
+        basepath: basepath,
+        max_age_seconds: None,
+    };
+    let path = "tileset/0/0/0.pbf.gz";
+    for size_mb in &[1, 4, 16] {
+        let obj = vec![1u8; size_mb * 1024 * 1024];
+        cache.write(path, &obj).unwrap();
+        let rounds = 50;
+
+        let start = Instant::now();
+        let mut capacity = 0;
+        for _ in 0..rounds {
+            cache
+                .read(path, |f| {
+                    let mut data = Vec::new();
+                    let _ = f.read_to_end(&mut data);
+                    capacity = data.capacity();
+                })
+                .unwrap();
+        }
+        println!("{} MB tile, read_to_end:  {:?}/read, {} bytes allocated",
+                 size_mb,
+                 start.elapsed() / rounds,
+                 capacity);
+
+        let start = Instant::now();
+        for _ in 0..rounds {
+            capacity = cache.read_tile(path).unwrap().unwrap().capacity();
+        }
+        println!("{} MB tile, read_tile:    {:?}/read, {} bytes allocated",
+                 size_mb,
+                 start.elapsed() / rounds,
+                 capacity);
+    }
+}
+
+#[test]
+fn test_read_tile_error() {
+    use core::grid::ExtentInt;
+    use std::io::{self, Read};
+
+    struct FailingReader;
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "read failed"))
+        }
+    }
+
+    // Cache with the default `read_tile` implementation
+    struct FailingCache;
+    impl Cache for FailingCache {
+        fn read<F>(&self, _path: &str, mut read: F) -> Result<bool, io::Error>
+            where F: FnMut(&mut Read)
+        {
+            read(&mut FailingReader);
+            Ok(true)
+        }
+        fn write(&self, _path: &str, _obj: &[u8]) -> Result<(), io::Error> {
+            Ok(())
+        }
+        fn exists(&self, _path: &str) -> bool {
+            true
+        }
+        fn purge(&self,
+                 _tileset: &str,
+                 _minzoom: u8,
+                 _maxzoom: u8,
+                 _limits: Option<&[ExtentInt]>)
+                 -> Result<u64, io::Error> {
+            Ok(0)
+        }
+    }
+
+    let err = FailingCache.read_tile("tileset/0/0/0.pbf.gz").unwrap_err();
+    assert_eq!(err.to_string(), "read failed");
+    assert!(FailingCache.read_tile_stale("tileset/0/0/0.pbf.gz").is_err());
+}
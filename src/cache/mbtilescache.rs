@@ -0,0 +1,181 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use cache::cache::{Cache, tile_path_coords};
+use core::grid::ExtentInt;
+use core::sqlite::SqlitePool;
+use rusqlite::{self, Connection, OpenFlags};
+use serde_json;
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+
+
+/// Tile cache in MBTiles files (https://github.com/mapbox/mbtiles-spec)
+///
+/// The placeholder `{tileset}` in `path` is replaced with the tileset name,
+/// which allows caching multiple tilesets in separate files.
+pub struct Mbtilescache {
+    pub path: String,
+    /// Connection pool of each file
+    pools: Mutex<HashMap<String, Arc<SqlitePool>>>,
+}
+
+const SCHEMA: &'static str = "
+    CREATE TABLE IF NOT EXISTS tiles (zoom_level integer, tile_column integer, tile_row integer, tile_data blob);
+    CREATE UNIQUE INDEX IF NOT EXISTS tile_index ON tiles (zoom_level, tile_column, tile_row);
+    CREATE TABLE IF NOT EXISTS metadata (name text, value text);
+    CREATE UNIQUE INDEX IF NOT EXISTS name ON metadata (name);
+";
+
+fn sql_error(e: rusqlite::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("{}", e))
+}
+
+/// MBTiles uses TMS row numbering of the Web Mercator grid
+fn tms_row(zoom: u8, ytile: u32) -> i64 {
+    (1i64 << zoom) - 1 - ytile as i64
+}
+
+impl Mbtilescache {
+    pub fn new(path: &str) -> Mbtilescache {
+        Mbtilescache {
+            path: path.to_string(),
+            pools: Mutex::new(HashMap::new()),
+        }
+    }
+    pub fn tileset_path(&self, tileset: &str) -> String {
+        self.path.replace("{tileset}", tileset)
+    }
+    /// Connection pool of the (newly created) database of `tileset`
+    fn pool(&self, tileset: &str) -> Result<Arc<SqlitePool>, io::Error> {
+        let mut pools = self.pools.lock().unwrap();
+        let path = self.tileset_path(tileset);
+        if !pools.contains_key(&path) {
+            let pool = try!(SqlitePool::open(&path, OpenFlags::default(), SCHEMA)
+                                .map_err(sql_error));
+            pools.insert(path.clone(), Arc::new(pool));
+        }
+        Ok(pools[&path].clone())
+    }
+    /// Call `f` with a connection to the database of `tileset`.
+    /// The lock of the pool map is not held while `f` runs.
+    fn with_connection<T, F>(&self, tileset: &str, f: F) -> Result<T, io::Error>
+        where F: FnOnce(&Connection) -> Result<T, rusqlite::Error>
+    {
+        let pool = try!(self.pool(tileset));
+        pool.with_connection(f).map_err(sql_error)
+    }
+    /// Write tileset metadata (e.g. from `get_mbtiles_metadata`) into metadata table
+    pub fn write_metadata(&self,
+                          tileset: &str,
+                          metadata: &serde_json::Value)
+                          -> Result<(), io::Error> {
+        let entries = match metadata.as_object() {
+            Some(obj) => obj,
+            None => return Ok(()),
+        };
+        self.with_connection(tileset, |conn| {
+            for (name, value) in entries {
+                let value = match value {
+                    &serde_json::Value::String(ref s) => s.clone(),
+                    _ => value.to_string(),
+                };
+                try!(conn.execute("INSERT OR REPLACE INTO metadata (name, value) VALUES (?, ?)",
+                                  &[name, &value]));
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Cache for Mbtilescache {
+    fn read<F>(&self, path: &str, mut read: F) -> Result<bool, io::Error>
+        where F: FnMut(&mut Read)
+    {
+        debug!("Mbtilescache.read {}", path);
+        let (tileset, z, x, y) = match tile_path_coords(path) {
+            Some(coords) => coords,
+            None => return Ok(false),
+        };
+        let data = try!(self.with_connection(tileset, |conn| {
+            let tile = conn.query_row("SELECT tile_data FROM tiles WHERE zoom_level = ? AND tile_column = ? AND tile_row = ?",
+                                      &[&(z as i64), &(x as i64), &tms_row(z, y)],
+                                      |row| row.get::<_, Vec<u8>>(0));
+            match tile {
+                Ok(data) => Ok(Some(data)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(e),
+            }
+        }));
+        match data {
+            Some(data) => {
+                read(&mut &data[..]);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+    fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error> {
+        debug!("Mbtilescache.write {}", path);
+        let (tileset, z, x, y) = match tile_path_coords(path) {
+            Some(coords) => coords,
+            None => {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                          format!("Invalid tile path '{}'", path)))
+            }
+        };
+        self.with_connection(tileset, |conn| {
+            conn.execute("INSERT OR REPLACE INTO tiles (zoom_level, tile_column, tile_row, tile_data) VALUES (?, ?, ?, ?)",
+                         &[&(z as i64), &(x as i64), &tms_row(z, y), &obj])
+                .map(|_| ())
+        })
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        let (tileset, z, x, y) = match tile_path_coords(path) {
+            Some(coords) => coords,
+            None => return false,
+        };
+        self.with_connection(tileset, |conn| {
+                conn.query_row("SELECT count(*) FROM tiles WHERE zoom_level = ? AND tile_column = ? AND tile_row = ?",
+                               &[&(z as i64), &(x as i64), &tms_row(z, y)],
+                               |row| row.get::<_, i64>(0))
+            })
+            .map(|cnt| cnt > 0)
+            .unwrap_or(false)
+    }
+
+    fn purge(&self,
+             tileset: &str,
+             minzoom: u8,
+             maxzoom: u8,
+             limits: Option<&[ExtentInt]>)
+             -> Result<u64, io::Error> {
+        self.with_connection(tileset, |conn| {
+            let mut count = 0;
+            for zoom in minzoom..=maxzoom {
+                count += match limits.and_then(|l| l.get(zoom as usize)) {
+                    Some(limit) => {
+                        if limit.maxy <= limit.miny {
+                            continue;
+                        }
+                        try!(conn.execute("DELETE FROM tiles WHERE zoom_level = ? AND tile_column >= ? AND tile_column < ? AND tile_row > ? AND tile_row <= ?",
+                                          &[&(zoom as i64),
+                                            &(limit.minx as i64),
+                                            &(limit.maxx as i64),
+                                            &tms_row(zoom, limit.maxy),
+                                            &tms_row(zoom, limit.miny)]))
+                    }
+                    None => {
+                        try!(conn.execute("DELETE FROM tiles WHERE zoom_level = ?",
+                                          &[&(zoom as i64)]))
+                    }
+                } as u64;
+            }
+            Ok(count)
+        })
+    }
+}
@@ -0,0 +1,92 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use cache::cache::Cache;
+use cache::mbtilescache::Mbtilescache;
+use rusqlite::Connection;
+use std::fs;
+
+
+#[test]
+fn test_mbtilescache() {
+    use std::env;
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_test_mbtiles");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = format!("{}/{{tileset}}.mbtiles", &dir.display());
+
+    let cache = Mbtilescache::new(&path);
+    let path = "tileset/2/1/0.pbf.gz";
+    let obj = "0123456789";
+
+    // Cache miss
+    assert_eq!(cache.read(path, |_| {}).unwrap(), false);
+    assert!(!cache.exists(path));
+
+    // Write into cache
+    let _ = cache.write(path, obj.as_bytes());
+    assert!(cache.exists(path));
+
+    // Read from cache
+    let mut s = String::new();
+    assert!(cache.read(path, |f| { let _ = f.read_to_string(&mut s); }).unwrap());
+    assert_eq!(&s, "0123456789");
+
+    // Tile row in TMS scheme
+    let conn = Connection::open(cache.tileset_path("tileset")).unwrap();
+    let row: i64 = conn.query_row("SELECT tile_row FROM tiles WHERE zoom_level = 2",
+                                  &[],
+                                  |row| row.get(0))
+        .unwrap();
+    assert_eq!(row, 3);
+
+    // Metadata
+    let _ = cache.write_metadata("tileset", &json!({"name": "tileset", "minzoom": 0}));
+    let name: String = conn.query_row("SELECT value FROM metadata WHERE name = 'name'",
+                                      &[],
+                                      |row| row.get(0))
+        .unwrap();
+    assert_eq!(name, "tileset");
+
+    // Purge
+    assert_eq!(cache.purge("tileset", 0, 22, None).unwrap(), 1);
+    assert!(!cache.exists(path));
+}
+
+#[test]
+fn test_mbtilescache_threads() {
+    use std::env;
+    use std::sync::Arc;
+    use std::thread;
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_test_mbtiles_threads");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = format!("{}/{{tileset}}.mbtiles", &dir.display());
+
+    let cache = Arc::new(Mbtilescache::new(&path));
+    let workers: Vec<_> = (0..4u32)
+        .map(|x| {
+                 let cache = cache.clone();
+                 thread::spawn(move || for y in 0..16 {
+                                   let path = format!("tileset/4/{}/{}.pbf.gz", x, y);
+                                   cache.write(&path, path.as_bytes()).unwrap();
+                                   let mut s = String::new();
+                                   assert!(cache.read(&path, |f| {
+                                                            let _ = f.read_to_string(&mut s);
+                                                        })
+                                               .unwrap());
+                                   assert_eq!(s, path);
+                               })
+             })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    assert_eq!(cache.purge("tileset", 4, 4, None).unwrap(), 64);
+}
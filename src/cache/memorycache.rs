@@ -0,0 +1,106 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use cache::cache::{Cache, path_matches};
+use core::grid::ExtentInt;
+use lru_cache::LruCache;
+use std::io::{self, Read};
+use std::sync::Mutex;
+
+
+/// In-memory tile cache with least-recently-used eviction.
+///
+/// All entries are kept behind a single `Mutex`, which is locked for the
+/// duration of a lookup or insert only. Tile data is copied out before the
+/// `read` closure is called, so slow readers don't block other threads.
+pub struct Memorycache {
+    /// Maximal total size of cached tiles in bytes
+    pub max_bytes: usize,
+    entries: Mutex<MemoryEntries>,
+}
+
+struct MemoryEntries {
+    tiles: LruCache<String, Vec<u8>>,
+    bytes: usize,
+}
+
+impl Memorycache {
+    pub fn new(max_bytes: usize) -> Memorycache {
+        Memorycache {
+            max_bytes: max_bytes,
+            // Entry count is limited by max_bytes
+            entries: Mutex::new(MemoryEntries {
+                                    tiles: LruCache::new(usize::max_value()),
+                                    bytes: 0,
+                                }),
+        }
+    }
+    pub fn with_size_mb(size_mb: usize) -> Memorycache {
+        Memorycache::new(size_mb * 1024 * 1024)
+    }
+}
+
+impl Cache for Memorycache {
+    fn read<F>(&self, path: &str, mut read: F) -> Result<bool, io::Error>
+        where F: FnMut(&mut Read)
+    {
+        debug!("Memorycache.read {}", path);
+        let data = {
+            let mut entries = self.entries.lock().unwrap();
+            // get_mut bumps recency
+            entries.tiles.get_mut(path).map(|data| data.clone())
+        };
+        match data {
+            Some(data) => {
+                read(&mut &data[..]);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+    fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error> {
+        debug!("Memorycache.write {}", path);
+        if obj.len() > self.max_bytes {
+            return Ok(());
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(old) = entries.tiles.insert(path.to_string(), obj.to_vec()) {
+            entries.bytes -= old.len();
+        }
+        entries.bytes += obj.len();
+        while entries.bytes > self.max_bytes {
+            match entries.tiles.remove_lru() {
+                Some((_, data)) => entries.bytes -= data.len(),
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.entries.lock().unwrap().tiles.contains_key(path)
+    }
+
+    fn purge(&self,
+             tileset: &str,
+             minzoom: u8,
+             maxzoom: u8,
+             limits: Option<&[ExtentInt]>)
+             -> Result<u64, io::Error> {
+        let mut entries = self.entries.lock().unwrap();
+        let paths: Vec<String> = entries
+            .tiles
+            .iter()
+            .map(|(path, _)| path.clone())
+            .filter(|path| path_matches(path, tileset, minzoom, maxzoom, limits))
+            .collect();
+        for path in &paths {
+            if let Some(data) = entries.tiles.remove(path) {
+                entries.bytes -= data.len();
+            }
+        }
+        Ok(paths.len() as u64)
+    }
+}
@@ -0,0 +1,49 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use cache::cache::Cache;
+use cache::memorycache::Memorycache;
+
+
+#[test]
+fn test_memorycache() {
+    let cache = Memorycache::new(20);
+    let path = "tileset/0/1/2.pbf";
+    let obj = "0123456789";
+
+    // Cache miss
+    assert_eq!(cache.read(path, |_| {}).unwrap(), false);
+    assert!(!cache.exists(path));
+
+    // Write into cache
+    let _ = cache.write(path, obj.as_bytes());
+    assert!(cache.exists(path));
+
+    // Read from cache
+    let mut s = String::new();
+    assert_eq!(cache.read(path, |f| { let _ = f.read_to_string(&mut s); }).unwrap(), true);
+    assert_eq!(&s, "0123456789");
+}
+
+#[test]
+fn test_memorycache_eviction() {
+    let cache = Memorycache::new(20);
+    let obj = "0123456789";
+
+    let _ = cache.write("tileset/0/0/0.pbf", obj.as_bytes());
+    let _ = cache.write("tileset/1/0/0.pbf", obj.as_bytes());
+    // Bump recency of first entry
+    assert!(cache.read("tileset/0/0/0.pbf", |_| {}).unwrap());
+    // Exceeds budget of 20 bytes
+    let _ = cache.write("tileset/1/1/0.pbf", obj.as_bytes());
+
+    assert!(cache.exists("tileset/0/0/0.pbf"));
+    assert!(!cache.exists("tileset/1/0/0.pbf"));
+    assert!(cache.exists("tileset/1/1/0.pbf"));
+
+    // Tiles larger than the budget are not cached
+    let _ = cache.write("tileset/2/0/0.pbf", "012345678901234567890".as_bytes());
+    assert!(!cache.exists("tileset/2/0/0.pbf"));
+}
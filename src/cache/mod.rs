@@ -5,64 +5,273 @@
 
 pub mod cache;
 pub mod filecache;
+pub mod s3cache;
+pub mod memorycache;
+pub mod rediscache;
+pub mod mbtilescache;
 
 #[cfg(test)]
 mod filecache_test;
+#[cfg(test)]
+mod memorycache_test;
+#[cfg(test)]
+mod s3cache_test;
+#[cfg(test)]
+mod rediscache_test;
+#[cfg(test)]
+mod mbtilescache_test;
 
 pub use self::cache::Cache;
 pub use self::cache::Nocache;
 pub use self::filecache::Filecache;
+pub use self::s3cache::S3cache;
+pub use self::memorycache::Memorycache;
+pub use self::rediscache::Rediscache;
+pub use self::mbtilescache::Mbtilescache;
 use std::io::Read;
 use std::io;
 use core::Config;
+use core::grid::ExtentInt;
 use toml;
 
 
+/// Configuration sections of the cache backends
+const BACKENDS: [&'static str; 5] = ["file", "memory", "mbtiles", "s3", "redis"];
+
 pub enum Tilecache {
     Nocache(Nocache),
     Filecache(Filecache),
+    S3cache(S3cache),
+    Memorycache(Memorycache),
+    Rediscache(Rediscache),
+    Mbtilescache(Mbtilescache),
 }
 
 impl Cache for Tilecache {
-    fn read<F>(&self, path: &str, read: F) -> bool
+    fn read<F>(&self, path: &str, read: F) -> Result<bool, io::Error>
         where F: FnMut(&mut Read)
     {
         match self {
             &Tilecache::Nocache(ref cache) => cache.read(path, read),
             &Tilecache::Filecache(ref cache) => cache.read(path, read),
+            &Tilecache::S3cache(ref cache) => cache.read(path, read),
+            &Tilecache::Memorycache(ref cache) => cache.read(path, read),
+            &Tilecache::Rediscache(ref cache) => cache.read(path, read),
+            &Tilecache::Mbtilescache(ref cache) => cache.read(path, read),
+        }
+    }
+    fn read_stale<F>(&self, path: &str, read: F) -> Result<bool, io::Error>
+        where F: FnMut(&mut Read)
+    {
+        match self {
+            &Tilecache::Nocache(ref cache) => cache.read_stale(path, read),
+            &Tilecache::Filecache(ref cache) => cache.read_stale(path, read),
+            &Tilecache::S3cache(ref cache) => cache.read_stale(path, read),
+            &Tilecache::Memorycache(ref cache) => cache.read_stale(path, read),
+            &Tilecache::Rediscache(ref cache) => cache.read_stale(path, read),
+            &Tilecache::Mbtilescache(ref cache) => cache.read_stale(path, read),
+        }
+    }
+    fn read_tile(&self, path: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        match self {
+            &Tilecache::Nocache(ref cache) => cache.read_tile(path),
+            &Tilecache::Filecache(ref cache) => cache.read_tile(path),
+            &Tilecache::S3cache(ref cache) => cache.read_tile(path),
+            &Tilecache::Memorycache(ref cache) => cache.read_tile(path),
+            &Tilecache::Rediscache(ref cache) => cache.read_tile(path),
+            &Tilecache::Mbtilescache(ref cache) => cache.read_tile(path),
+        }
+    }
+    fn read_tile_stale(&self, path: &str) -> Result<Option<Vec<u8>>, io::Error> {
+        match self {
+            &Tilecache::Nocache(ref cache) => cache.read_tile_stale(path),
+            &Tilecache::Filecache(ref cache) => cache.read_tile_stale(path),
+            &Tilecache::S3cache(ref cache) => cache.read_tile_stale(path),
+            &Tilecache::Memorycache(ref cache) => cache.read_tile_stale(path),
+            &Tilecache::Rediscache(ref cache) => cache.read_tile_stale(path),
+            &Tilecache::Mbtilescache(ref cache) => cache.read_tile_stale(path),
         }
     }
     fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error> {
         match self {
             &Tilecache::Nocache(ref cache) => cache.write(path, obj),
             &Tilecache::Filecache(ref cache) => cache.write(path, obj),
+            &Tilecache::S3cache(ref cache) => cache.write(path, obj),
+            &Tilecache::Memorycache(ref cache) => cache.write(path, obj),
+            &Tilecache::Rediscache(ref cache) => cache.write(path, obj),
+            &Tilecache::Mbtilescache(ref cache) => cache.write(path, obj),
         }
     }
     fn exists(&self, path: &str) -> bool {
         match self {
             &Tilecache::Nocache(ref cache) => cache.exists(path),
             &Tilecache::Filecache(ref cache) => cache.exists(path),
+            &Tilecache::S3cache(ref cache) => cache.exists(path),
+            &Tilecache::Memorycache(ref cache) => cache.exists(path),
+            &Tilecache::Rediscache(ref cache) => cache.exists(path),
+            &Tilecache::Mbtilescache(ref cache) => cache.exists(path),
+        }
+    }
+    fn purge(&self,
+             tileset: &str,
+             minzoom: u8,
+             maxzoom: u8,
+             limits: Option<&[ExtentInt]>)
+             -> Result<u64, io::Error> {
+        match self {
+            &Tilecache::Nocache(ref cache) => cache.purge(tileset, minzoom, maxzoom, limits),
+            &Tilecache::Filecache(ref cache) => cache.purge(tileset, minzoom, maxzoom, limits),
+            &Tilecache::S3cache(ref cache) => cache.purge(tileset, minzoom, maxzoom, limits),
+            &Tilecache::Memorycache(ref cache) => cache.purge(tileset, minzoom, maxzoom, limits),
+            &Tilecache::Rediscache(ref cache) => cache.purge(tileset, minzoom, maxzoom, limits),
+            &Tilecache::Mbtilescache(ref cache) => cache.purge(tileset, minzoom, maxzoom, limits),
         }
     }
 }
 
+impl Tilecache {
+    fn s3cache_from_config(s3cfg: &toml::Value) -> Result<Self, String> {
+        let bucket = try!(s3cfg
+                              .get("bucket")
+                              .ok_or("Missing configuration entry bucket in [cache.s3]"
+                                         .to_string())
+                              .and_then(|val| {
+                                            val.as_str()
+                                                .ok_or("cache.s3.bucket entry is not a string"
+                                                           .to_string())
+                                        }));
+        let region = try!(s3cfg
+                              .get("region")
+                              .map_or(Ok("us-east-1"), |val| {
+                                  val.as_str()
+                                      .ok_or("cache.s3.region entry is not a string".to_string())
+                              }));
+        let prefix = s3cfg
+            .get("prefix")
+            .and_then(|val| val.as_str())
+            .map(|s| s.to_string());
+        let endpoint = s3cfg
+            .get("endpoint")
+            .and_then(|val| val.as_str())
+            .map(|s| s.to_string());
+        S3cache::new(bucket, region, prefix, endpoint).map(|cache| Tilecache::S3cache(cache))
+    }
+    fn rediscache_from_config(rediscfg: &toml::Value) -> Result<Self, String> {
+        let url = try!(rediscfg
+                           .get("url")
+                           .ok_or("Missing configuration entry url in [cache.redis]".to_string())
+                           .and_then(|val| {
+                                         val.as_str()
+                                             .ok_or("cache.redis.url entry is not a string"
+                                                        .to_string())
+                                     }));
+        let prefix = rediscfg
+            .get("prefix")
+            .map_or("t-rex", |val| val.as_str().unwrap_or("t-rex"));
+        let ttl = match rediscfg.get("ttl") {
+            Some(val) => {
+                let ttl = try!(val.as_integer()
+                                   .ok_or("cache.redis.ttl entry is not an integer".to_string()));
+                if ttl < 1 {
+                    return Err(format!("Invalid cache.redis.ttl {} (expected at least 1 second)",
+                                       ttl));
+                }
+                Some(ttl as usize)
+            }
+            None => None,
+        };
+        Rediscache::new(url, prefix, ttl).map(|cache| Tilecache::Rediscache(cache))
+    }
+}
+
 impl Config<Tilecache> for Tilecache {
     fn from_config(config: &toml::Value) -> Result<Self, String> {
+        if let Some(cachecfg) = config.get("cache").and_then(|c| c.as_table()) {
+            let backends: Vec<&str> = BACKENDS
+                .iter()
+                .cloned()
+                .filter(|backend| cachecfg.contains_key(*backend))
+                .collect();
+            if backends.len() > 1 {
+                return Err(format!("Multiple cache backends configured ({}), expected only one",
+                                   backends.join(", ")));
+            }
+        }
+        if let Some(s3cfg) = config.get("cache").and_then(|c| c.get("s3")) {
+            return Tilecache::s3cache_from_config(s3cfg);
+        }
+        if let Some(rediscfg) = config.get("cache").and_then(|c| c.get("redis")) {
+            return Tilecache::rediscache_from_config(rediscfg);
+        }
+        if let Some(mbtilescfg) = config.get("cache").and_then(|c| c.get("mbtiles")) {
+            let path = try!(mbtilescfg
+                                .get("path")
+                                .ok_or("Missing configuration entry path in [cache.mbtiles]"
+                                           .to_string())
+                                .and_then(|val| {
+                                              val.as_str()
+                                                  .ok_or("cache.mbtiles.path entry is not a string"
+                                                             .to_string())
+                                          }));
+            return Ok(Tilecache::Mbtilescache(Mbtilescache::new(path)));
+        }
+        if let Some(memcfg) = config.get("cache").and_then(|c| c.get("memory")) {
+            let size_mb = try!(memcfg
+                                   .get("size_mb")
+                                   .map_or(Ok(64), |val| {
+                                       val.as_integer()
+                                           .ok_or("cache.memory.size_mb entry is not an integer"
+                                                      .to_string())
+                                   }));
+            if size_mb < 0 {
+                return Err(format!("Invalid cache.memory.size_mb {}", size_mb));
+            }
+            return Ok(Tilecache::Memorycache(Memorycache::with_size_mb(size_mb as usize)));
+        }
+        let max_age_seconds = config
+            .get("cache")
+            .and_then(|c| c.get("file"))
+            .and_then(|c| c.get("max_age_seconds"))
+            .and_then(|val| val.as_integer())
+            .map(|secs| secs as u64);
         config
             .get("cache")
             .and_then(|c| c.get("file"))
             .and_then(|c| c.get("base"))
             .and_then(|val| val.as_str().or(None))
             .and_then(|basedir| {
-                          Some(Tilecache::Filecache(Filecache { basepath: basedir.to_string() }))
+                          Some(Tilecache::Filecache(Filecache {
+                                                        basepath: basedir.to_string(),
+                                                        max_age_seconds: max_age_seconds,
+                                                    }))
                       })
             .or(Some(Tilecache::Nocache(Nocache)))
             .ok_or("config error".to_string())
     }
     fn gen_config() -> String {
         let toml = r#"
+# Tile cache (default: no cache). Configure only one of the following backends.
+# Tiles in a directory, regenerated after max_age_seconds
 #[cache.file]
 #base = "/tmp/mvtcache"
+#max_age_seconds = 86400
+# Tiles in memory with least-recently-used eviction
+#[cache.memory]
+#size_mb = 64
+# MBTiles file per tileset
+#[cache.mbtiles]
+#path = "/tmp/{tileset}.mbtiles"
+# S3 compatible object store
+#[cache.s3]
+#bucket = "mvtcache"
+#region = "eu-central-1"
+#prefix = "tiles"
+#endpoint = "http://localhost:9000"
+# Redis server shared by multiple t-rex instances, ttl in seconds
+#[cache.redis]
+#url = "redis://127.0.0.1/"
+#prefix = "t-rex"
+#ttl = 86400
 "#;
         toml.to_string()
     }
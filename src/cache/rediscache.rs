@@ -0,0 +1,133 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use cache::cache::{Cache, path_matches};
+use core::grid::ExtentInt;
+use redis::{self, Commands};
+use std::io::{self, Read};
+use std::sync::Mutex;
+
+
+/// Maximal number of idle connections kept open
+const MAX_IDLE_CONNECTIONS: usize = 16;
+
+
+pub struct Rediscache {
+    /// Key prefix for all tiles
+    pub prefix: String,
+    /// Expiration time of tiles in seconds
+    pub ttl: Option<usize>,
+    client: redis::Client,
+    /// Open connections reused by later requests
+    idle: Mutex<Vec<redis::Connection>>,
+}
+
+impl Rediscache {
+    pub fn new(url: &str, prefix: &str, ttl: Option<usize>) -> Result<Rediscache, String> {
+        let client = try!(redis::Client::open(url)
+                              .map_err(|e| format!("Invalid Redis URL '{}': {}", url, e)));
+        Ok(Rediscache {
+               prefix: prefix.to_string(),
+               ttl: ttl,
+               client: client,
+               idle: Mutex::new(Vec::new()),
+           })
+    }
+    /// Redis key of tile path `{tileset}/{z}/{x}/{y}.pbf.gz` -> `{prefix}:{tileset}:{z}:{x}:{y}`
+    pub fn key(&self, path: &str) -> String {
+        let path = path.trim_right_matches(".gz").trim_right_matches(".pbf");
+        format!("{}:{}", self.prefix, path.replace("/", ":"))
+    }
+    /// Call `f` with an idle connection or a new one if none is available.
+    /// Connections are dropped after a failed command, because they may be broken.
+    fn with_connection<T, F>(&self, f: F) -> redis::RedisResult<T>
+        where F: FnOnce(&redis::Connection) -> redis::RedisResult<T>
+    {
+        let idle = self.idle.lock().unwrap().pop();
+        let con = match idle {
+            Some(con) => con,
+            None => try!(self.client.get_connection()),
+        };
+        let res = f(&con);
+        if res.is_ok() {
+            let mut idle = self.idle.lock().unwrap();
+            if idle.len() < MAX_IDLE_CONNECTIONS {
+                idle.push(con);
+            }
+        }
+        res
+    }
+}
+
+impl Cache for Rediscache {
+    fn read<F>(&self, path: &str, mut read: F) -> Result<bool, io::Error>
+        where F: FnMut(&mut Read)
+    {
+        let key = self.key(path);
+        debug!("Rediscache.read {}", key);
+        let data: redis::RedisResult<Option<Vec<u8>>> = self.with_connection(|con| con.get(&key));
+        match data {
+            Ok(Some(data)) => {
+                read(&mut &data[..]);
+                Ok(true)
+            }
+            Ok(None) => Ok(false),
+            Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+        }
+    }
+    fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error> {
+        let key = self.key(path);
+        debug!("Rediscache.write {}", key);
+        let res: redis::RedisResult<()> = self.with_connection(|con| match self.ttl {
+            Some(ttl) => con.set_ex(&key, obj, ttl),
+            None => con.set(&key, obj),
+        });
+        res.map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        let key = self.key(path);
+        self.with_connection(|con| con.exists(&key))
+            .unwrap_or(false)
+    }
+
+    fn purge(&self,
+             tileset: &str,
+             minzoom: u8,
+             maxzoom: u8,
+             limits: Option<&[ExtentInt]>)
+             -> Result<u64, io::Error> {
+        let pattern = format!("{}:{}:*", self.prefix, tileset);
+        let keyprefix = format!("{}:", self.prefix);
+        // Iterate with SCAN instead of the blocking KEYS command
+        let keys: redis::RedisResult<Vec<String>> = self.with_connection(|con| {
+            let iter = try!(redis::cmd("SCAN")
+                                .cursor_arg(0)
+                                .arg("MATCH")
+                                .arg(&pattern)
+                                .arg("COUNT")
+                                .arg(1000)
+                                .iter::<String>(con));
+            Ok(iter.filter(|key| {
+                               let path = format!("{}.pbf.gz",
+                                                  key[keyprefix.len()..].replace(":", "/"));
+                               path_matches(&path, tileset, minzoom, maxzoom, limits)
+                           })
+                   .collect())
+        });
+        let mut keys = try!(keys.map_err(|e| io::Error::new(io::ErrorKind::Other, e)));
+        // SCAN may return a key more than once
+        keys.sort();
+        keys.dedup();
+        let mut count = 0;
+        for key in keys {
+            debug!("Rediscache.purge {}", key);
+            let res: redis::RedisResult<()> = self.with_connection(|con| con.del(&key));
+            try!(res.map_err(|e| io::Error::new(io::ErrorKind::Other, e)));
+            count += 1;
+        }
+        Ok(count)
+    }
+}
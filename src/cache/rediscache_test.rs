@@ -0,0 +1,54 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use cache::cache::Cache;
+use cache::rediscache::Rediscache;
+
+
+#[test]
+fn test_redis_key() {
+    let cache = Rediscache::new("redis://127.0.0.1/", "mvt", None).unwrap();
+    assert_eq!(cache.key("tileset/0/1/2.pbf.gz"), "mvt:tileset:0:1:2");
+    assert_eq!(cache.key("tileset/0/1/2.pbf"), "mvt:tileset:0:1:2");
+}
+
+#[test]
+fn test_redis_connection_loss() {
+    // Nothing listening on this port
+    let cache = Rediscache::new("redis://127.0.0.1:1/", "mvt", Some(60)).unwrap();
+    assert!(cache.read("tileset/0/1/2.pbf", |_| {}).is_err());
+    assert!(!cache.exists("tileset/0/1/2.pbf"));
+    assert!(cache.write("tileset/0/1/2.pbf", "0123456789".as_bytes()).is_err());
+}
+
+#[test]
+#[ignore]
+fn test_rediscache() {
+    use std::env;
+
+    let url = env::var("REDISURL").expect("REDISURL undefined");
+    let cache = Rediscache::new(&url, "t_rex_test", Some(60)).unwrap();
+    let path = "tileset/0/1/2.pbf";
+    let obj = "0123456789";
+
+    let _ = cache.write(path, obj.as_bytes());
+    assert!(cache.exists(path));
+
+    let mut s = String::new();
+    assert!(cache.read(path, |f| { let _ = f.read_to_string(&mut s); }).unwrap());
+    assert_eq!(&s, "0123456789");
+
+    // Connections are reused
+    for _ in 0..100 {
+        assert!(cache.exists(path));
+    }
+
+    let _ = cache.write("tileset/1/0/0.pbf", obj.as_bytes());
+    assert_eq!(cache.purge("tileset", 1, 1, None).unwrap(), 1);
+    assert!(cache.exists(path));
+    assert!(!cache.exists("tileset/1/0/0.pbf"));
+    assert_eq!(cache.purge("tileset", 0, 22, None).unwrap(), 1);
+    assert!(!cache.exists(path));
+}
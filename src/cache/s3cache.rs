@@ -0,0 +1,183 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use cache::cache::{Cache, path_matches};
+use core::grid::ExtentInt;
+use rusoto_core::{Region, RusotoError};
+use rusoto_s3::{S3, S3Client, GetObjectError, GetObjectRequest, HeadObjectRequest, PutObjectRequest,
+                DeleteObjectRequest, ListObjectsV2Request};
+use std::io::{self, Read};
+use std::str::FromStr;
+
+
+pub struct S3cache {
+    pub bucket: String,
+    /// Key prefix for all tiles (without trailing slash)
+    pub prefix: Option<String>,
+    client: S3Client,
+}
+
+impl S3cache {
+    pub fn new(bucket: &str,
+               region: &str,
+               prefix: Option<String>,
+               endpoint: Option<String>)
+               -> Result<S3cache, String> {
+        let s3region = match endpoint {
+            Some(ref url) => {
+                Region::Custom {
+                    name: region.to_string(),
+                    endpoint: url.clone(),
+                }
+            }
+            None => {
+                try!(Region::from_str(region)
+                         .map_err(|e| format!("Invalid S3 region '{}': {}", region, e)))
+            }
+        };
+        Ok(S3cache {
+               bucket: bucket.to_string(),
+               prefix: prefix,
+               client: S3Client::new(s3region),
+           })
+    }
+    /// Object key of tile path `{tileset}/{z}/{x}/{y}.pbf.gz` -> `{prefix}/{tileset}/{z}/{x}/{y}.pbf.gz`
+    pub fn key(&self, path: &str) -> String {
+        match self.prefix {
+            Some(ref prefix) => format!("{}/{}", prefix.trim_right_matches('/'), path),
+            None => path.to_string(),
+        }
+    }
+}
+
+/// Whether a request failed because the object does not exist.
+/// HEAD responses and some S3 compatible services don't include an error code.
+fn is_not_found<E>(err: &RusotoError<E>) -> bool {
+    match *err {
+        RusotoError::Unknown(ref response) => response.status.as_u16() == 404,
+        _ => false,
+    }
+}
+
+/// Map a failed GetObject request to a cache miss (`Ok(false)`) or a read error
+pub fn read_error(err: RusotoError<GetObjectError>) -> Result<bool, io::Error> {
+    match err {
+        RusotoError::Service(GetObjectError::NoSuchKey(_)) => Ok(false),
+        ref err if is_not_found(err) => Ok(false),
+        err => Err(io::Error::new(io::ErrorKind::Other, format!("S3 read failed: {}", err))),
+    }
+}
+
+impl Cache for S3cache {
+    fn read<F>(&self, path: &str, mut read: F) -> Result<bool, io::Error>
+        where F: FnMut(&mut Read)
+    {
+        let key = self.key(path);
+        debug!("S3cache.read {}", key);
+        let request = GetObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key,
+            ..Default::default()
+        };
+        match self.client.get_object(request).sync() {
+            Ok(output) => {
+                match output.body {
+                    Some(body) => {
+                        read(&mut body.into_blocking_read());
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            }
+            Err(err) => read_error(err),
+        }
+    }
+    fn write(&self, path: &str, obj: &[u8]) -> Result<(), io::Error> {
+        let key = self.key(path);
+        debug!("S3cache.write {}", key);
+        let request = PutObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key,
+            body: Some(obj.to_vec().into()),
+            content_type: Some("application/x-protobuf".to_string()),
+            content_encoding: if path.ends_with(".gz") {
+                Some("gzip".to_string())
+            } else {
+                None
+            },
+            ..Default::default()
+        };
+        self.client
+            .put_object(request)
+            .sync()
+            .map(|_| ())
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{}", e)))
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        let key = self.key(path);
+        let request = HeadObjectRequest {
+            bucket: self.bucket.clone(),
+            key: key.clone(),
+            ..Default::default()
+        };
+        match self.client.head_object(request).sync() {
+            Ok(_) => true,
+            Err(ref err) if is_not_found(err) => false,
+            Err(err) => {
+                warn!("S3cache.exists {} failed: {}", key, err);
+                false
+            }
+        }
+    }
+
+    fn purge(&self,
+             tileset: &str,
+             minzoom: u8,
+             maxzoom: u8,
+             limits: Option<&[ExtentInt]>)
+             -> Result<u64, io::Error> {
+        let keyprefix = self.key("");
+        let mut count = 0;
+        let mut continuation_token = None;
+        loop {
+            let request = ListObjectsV2Request {
+                bucket: self.bucket.clone(),
+                prefix: Some(self.key(&format!("{}/", tileset))),
+                continuation_token: continuation_token,
+                ..Default::default()
+            };
+            let output = try!(self.client
+                                  .list_objects_v2(request)
+                                  .sync()
+                                  .map_err(|e| {
+                                               io::Error::new(io::ErrorKind::Other,
+                                                              format!("{}", e))
+                                           }));
+            for object in output.contents.unwrap_or(Vec::new()) {
+                let key = object.key.unwrap_or(String::new());
+                let path = key[keyprefix.len()..].to_string();
+                if path_matches(&path, tileset, minzoom, maxzoom, limits) {
+                    debug!("S3cache.purge {}", key);
+                    let request = DeleteObjectRequest {
+                        bucket: self.bucket.clone(),
+                        key: key,
+                        ..Default::default()
+                    };
+                    try!(self.client
+                             .delete_object(request)
+                             .sync()
+                             .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("{}", e))));
+                    count += 1;
+                }
+            }
+            continuation_token = output.next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,47 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use cache::s3cache::{S3cache, read_error};
+use rusoto_core::{HttpDispatchError, RusotoError};
+use rusoto_s3::GetObjectError;
+
+
+#[test]
+fn test_s3_key() {
+    let cache = S3cache::new("mvtcache", "eu-central-1", None, None).unwrap();
+    assert_eq!(cache.key("tileset/0/1/2.pbf.gz"), "tileset/0/1/2.pbf.gz");
+
+    let cache = S3cache::new("mvtcache", "eu-central-1", Some("tiles".to_string()), None)
+        .unwrap();
+    assert_eq!(cache.key("tileset/0/1/2.pbf.gz"), "tiles/tileset/0/1/2.pbf.gz");
+
+    let cache = S3cache::new("mvtcache", "eu-central-1", Some("tiles/".to_string()), None)
+        .unwrap();
+    assert_eq!(cache.key("tileset/0/1/2.pbf.gz"), "tiles/tileset/0/1/2.pbf.gz");
+    assert_eq!(cache.key("tileset/"), "tiles/tileset/");
+
+    assert!(S3cache::new("mvtcache", "no-such-region", None, None).is_err());
+    assert!(S3cache::new("mvtcache",
+                         "local",
+                         None,
+                         Some("http://localhost:9000".to_string()))
+                    .is_ok());
+}
+
+#[test]
+fn test_s3_read_error() {
+    let missing = RusotoError::Service(GetObjectError::NoSuchKey("tileset/0/1/2.pbf.gz"
+                                                                     .to_string()));
+    assert_eq!(read_error(missing).unwrap(), false);
+
+    let denied: RusotoError<GetObjectError> = RusotoError::Validation("Access Denied"
+                                                                          .to_string());
+    let err = read_error(denied).unwrap_err();
+    assert!(err.to_string().contains("Access Denied"));
+
+    let unreachable = RusotoError::HttpDispatch(HttpDispatchError::new("Connection refused"
+                                                                           .to_string()));
+    assert!(read_error(unreachable).is_err());
+}
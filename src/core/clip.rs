@@ -0,0 +1,431 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use core::geom::*;
+use core::grid::Extent;
+
+
+fn for_each_point(geom: &GeometryType, f: &mut FnMut(&Point)) {
+    let mut points: Vec<&Point> = Vec::new();
+    match geom {
+        &GeometryType::Point(ref p) => points.push(p),
+        &GeometryType::LineString(ref l) => points.extend(l.points.iter()),
+        &GeometryType::Polygon(ref p) => {
+            for ring in &p.rings {
+                points.extend(ring.points.iter());
+            }
+        }
+        &GeometryType::MultiPoint(ref mp) => points.extend(mp.points.iter()),
+        &GeometryType::MultiLineString(ref ml) => {
+            for line in &ml.lines {
+                points.extend(line.points.iter());
+            }
+        }
+        &GeometryType::MultiPolygon(ref mp) => {
+            for polygon in &mp.polygons {
+                for ring in &polygon.rings {
+                    points.extend(ring.points.iter());
+                }
+            }
+        }
+        &GeometryType::GeometryCollection(_) => {}
+    }
+    for p in points {
+        f(p);
+    }
+}
+
+/// Bounding box of geometry. None for empty geometries.
+pub fn geometry_extent(geom: &GeometryType) -> Option<Extent> {
+    let mut extent: Option<Extent> = None;
+    for_each_point(geom, &mut |p| {
+        extent = Some(match extent.take() {
+                          Some(ext) => {
+                              Extent {
+                                  minx: ext.minx.min(p.x),
+                                  miny: ext.miny.min(p.y),
+                                  maxx: ext.maxx.max(p.x),
+                                  maxy: ext.maxy.max(p.y),
+                              }
+                          }
+                          None => {
+                              Extent {
+                                  minx: p.x,
+                                  miny: p.y,
+                                  maxx: p.x,
+                                  maxy: p.y,
+                              }
+                          }
+                      });
+    });
+    extent
+}
+
+pub fn intersects(a: &Extent, b: &Extent) -> bool {
+    a.minx <= b.maxx && a.maxx >= b.minx && a.miny <= b.maxy && a.maxy >= b.miny
+}
+
+pub fn contains(outer: &Extent, inner: &Extent) -> bool {
+    inner.minx >= outer.minx && inner.maxx <= outer.maxx && inner.miny >= outer.miny &&
+    inner.maxy <= outer.maxy
+}
+
+type Coord = (f64, f64);
+
+/// Clip line segment to extent (Liang-Barsky)
+fn clip_segment(p0: Coord, p1: Coord, ext: &Extent) -> Option<(Coord, Coord)> {
+    let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+    let mut t0 = 0.0;
+    let mut t1 = 1.0;
+    for &(p, q) in &[(-dx, p0.0 - ext.minx),
+                     (dx, ext.maxx - p0.0),
+                     (-dy, p0.1 - ext.miny),
+                     (dy, ext.maxy - p0.1)] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let r = q / p;
+            if p < 0.0 {
+                if r > t1 {
+                    return None;
+                }
+                if r > t0 {
+                    t0 = r;
+                }
+            } else {
+                if r < t0 {
+                    return None;
+                }
+                if r < t1 {
+                    t1 = r;
+                }
+            }
+        }
+    }
+    // Keep end points inside exactly, which are compared for joining clipped segments
+    let a = if t0 == 0.0 { p0 } else { (p0.0 + t0 * dx, p0.1 + t0 * dy) };
+    let b = if t1 == 1.0 { p1 } else { (p0.0 + t1 * dx, p0.1 + t1 * dy) };
+    Some((a, b))
+}
+
+fn to_line(coords: &[Coord]) -> LineString {
+    LineString {
+        points: coords.iter().map(|c| Point::new(c.0, c.1, None)).collect(),
+        srid: None,
+    }
+}
+
+/// Parts of a line inside the extent.
+/// A line leaving and re-entering the extent is split into parts.
+fn clip_coords(coords: &[Coord], ext: &Extent) -> Vec<Vec<Coord>> {
+    let mut parts = Vec::new();
+    let mut current: Vec<Coord> = Vec::new();
+    for seg in coords.windows(2) {
+        match clip_segment(seg[0], seg[1], ext) {
+            Some((a, b)) => {
+                if current.last() != Some(&a) {
+                    if current.len() > 1 {
+                        parts.push(current);
+                    }
+                    current = vec![a];
+                }
+                current.push(b);
+            }
+            None => {
+                if current.len() > 1 {
+                    parts.push(current);
+                }
+                current = Vec::new();
+            }
+        }
+    }
+    if current.len() > 1 {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Clip line to extent. A line leaving and re-entering the extent is split into parts.
+fn clip_line(line: &LineString, ext: &Extent) -> Vec<LineString> {
+    let coords: Vec<Coord> = line.points.iter().map(|p| (p.x, p.y)).collect();
+    clip_coords(&coords, ext)
+        .iter()
+        .map(|part| to_line(part))
+        .collect()
+}
+
+/// Signed area of ring (positive for counter-clockwise rings)
+fn ring_area(coords: &[Coord]) -> f64 {
+    (0..coords.len())
+        .map(|i| {
+                 let (a, b) = (coords[i], coords[(i + 1) % coords.len()]);
+                 a.0 * b.1 - b.0 * a.1
+             })
+        .sum::<f64>() / 2.0
+}
+
+/// Point in ring test (ray casting)
+fn ring_contains(coords: &[Coord], p: Coord) -> bool {
+    let mut inside = false;
+    let mut j = coords.len() - 1;
+    for i in 0..coords.len() {
+        let (a, b) = (coords[i], coords[j]);
+        if (a.1 > p.1) != (b.1 > p.1) && p.0 < (b.0 - a.0) * (p.1 - a.1) / (b.1 - a.1) + a.0 {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Position of a point on the extent boundary, counter-clockwise from the lower left corner.
+/// Intersection points are not exactly on the boundary, so the nearest edge is used.
+fn boundary_position(c: Coord, ext: &Extent) -> f64 {
+    let (w, h) = (ext.maxx - ext.minx, ext.maxy - ext.miny);
+    let x = c.0.max(ext.minx).min(ext.maxx);
+    let y = c.1.max(ext.miny).min(ext.maxy);
+    let edges = [(y - ext.miny).abs(),
+                 (x - ext.maxx).abs(),
+                 (y - ext.maxy).abs(),
+                 (x - ext.minx).abs()];
+    let nearest = (1..4).fold(0, |best, i| if edges[i] < edges[best] { i } else { best });
+    match nearest {
+        0 => x - ext.minx,
+        1 => w + y - ext.miny,
+        2 => w + h + ext.maxx - x,
+        _ => 2.0 * w + h + ext.maxy - y,
+    }
+}
+
+/// Clip polygon ring to extent. Parts of the ring which are only connected outside
+/// the extent result in separate rings. Returns the clipped rings with the orientation
+/// of `ring`.
+///
+/// The parts of the ring inside the extent are connected by walking counter-clockwise
+/// along the extent boundary (Weiler-Atherton for rectangles), which avoids the
+/// zero-width edges along the boundary of a Sutherland-Hodgman clipping.
+fn clip_ring(ring: &LineString, ext: &Extent) -> Vec<LineString> {
+    let mut coords: Vec<Coord> = ring.points.iter().map(|p| (p.x, p.y)).collect();
+    coords.dedup();
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    if coords.len() < 3 {
+        return Vec::new();
+    }
+    let clockwise = ring_area(&coords) < 0.0;
+    if clockwise {
+        coords.reverse();
+    }
+    let first = coords[0];
+    coords.push(first);
+    let mut parts = clip_coords(&coords, ext);
+    // Join last and first part if the ring starts inside the extent
+    if parts.len() > 1 && parts[0].first() == parts[parts.len() - 1].last() {
+        let first = parts.remove(0);
+        let last = parts.len() - 1;
+        parts[last].extend_from_slice(&first[1..]);
+    }
+    // Ring touching the extent from outside
+    parts.retain(|part| part.iter().any(|c| *c != part[0]));
+    let mut rings: Vec<Vec<Coord>> = Vec::new();
+    if parts.len() == 1 && parts[0].first() == parts[0].last() {
+        // Ring completely inside
+        rings.push(parts.pop().unwrap());
+    } else if parts.is_empty() {
+        // Ring completely outside or around the extent
+        let center = ((ext.minx + ext.maxx) / 2.0, (ext.miny + ext.maxy) / 2.0);
+        if ring_contains(&coords, center) {
+            rings.push(vec![(ext.minx, ext.miny),
+                            (ext.maxx, ext.miny),
+                            (ext.maxx, ext.maxy),
+                            (ext.minx, ext.maxy),
+                            (ext.minx, ext.miny)]);
+        }
+    } else {
+        let perimeter = 2.0 * (ext.maxx - ext.minx + ext.maxy - ext.miny);
+        let corners = [(0.0, (ext.minx, ext.miny)),
+                       (ext.maxx - ext.minx, (ext.maxx, ext.miny)),
+                       (perimeter / 2.0, (ext.maxx, ext.maxy)),
+                       (perimeter - (ext.maxy - ext.miny), (ext.minx, ext.maxy))];
+        let starts: Vec<f64> = parts
+            .iter()
+            .map(|part| boundary_position(part[0], ext))
+            .collect();
+        let mut used = vec![false; parts.len()];
+        for first in 0..parts.len() {
+            if used[first] {
+                continue;
+            }
+            let mut coords: Vec<Coord> = Vec::new();
+            let mut current = first;
+            loop {
+                used[current] = true;
+                coords.extend_from_slice(&parts[current]);
+                let exit = boundary_position(parts[current][parts[current].len() - 1], ext);
+                // Next part entering the extent counter-clockwise from the exit point
+                let distance = |start: f64| (start - exit + perimeter) % perimeter;
+                let next = (0..parts.len())
+                    .filter(|&i| !used[i] || i == first)
+                    .fold(None,
+                          |best: Option<usize>, i| match best {
+                              Some(b) if distance(starts[b]) <= distance(starts[i]) => best,
+                              _ => Some(i),
+                          })
+                    .unwrap_or(first);
+                let walk = distance(starts[next]);
+                let mut passed: Vec<(f64, Coord)> = corners
+                    .iter()
+                    .map(|&(pos, corner)| (distance(pos), corner))
+                    .filter(|&(d, _)| d > 0.0 && d < walk)
+                    .collect();
+                passed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                coords.extend(passed.iter().map(|&(_, corner)| corner));
+                if next == first {
+                    break;
+                }
+                current = next;
+            }
+            coords.push(parts[first][0]);
+            rings.push(coords);
+        }
+    }
+    // Rings collapsed onto the extent boundary have no area
+    let min_area = (ext.maxx - ext.minx) * (ext.maxy - ext.miny) * 1e-12;
+    rings
+        .into_iter()
+        .filter_map(|mut coords| {
+            coords.dedup();
+            if coords.len() < 4 || ring_area(&coords).abs() <= min_area {
+                return None;
+            }
+            if clockwise {
+                coords.reverse();
+            }
+            Some(to_line(&coords))
+        })
+        .collect()
+}
+
+/// Clip polygon to extent. Exterior rings split by the extent result in multiple polygons.
+fn clip_polygon(polygon: &Polygon, ext: &Extent) -> Vec<Polygon> {
+    let mut rings = polygon.rings.iter();
+    let mut polygons: Vec<Polygon> = match rings.next() {
+        Some(ring) => {
+            clip_ring(ring, ext)
+                .into_iter()
+                .map(|exterior| {
+                         Polygon {
+                             rings: vec![exterior],
+                             srid: None,
+                         }
+                     })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    if polygons.is_empty() {
+        return polygons;
+    }
+    for hole in rings.flat_map(|ring| clip_ring(ring, ext)) {
+        let idx = if polygons.len() == 1 {
+            0
+        } else {
+            // Assign hole to the polygon containing one of its vertices off the boundary
+            let p = hole.points
+                .iter()
+                .find(|p| p.x > ext.minx && p.x < ext.maxx && p.y > ext.miny && p.y < ext.maxy)
+                .unwrap_or(&hole.points[0]);
+            polygons
+                .iter()
+                .position(|polygon| {
+                    let exterior: Vec<Coord> =
+                        polygon.rings[0].points.iter().map(|p| (p.x, p.y)).collect();
+                    ring_contains(&exterior, (p.x, p.y))
+                })
+                .unwrap_or(0)
+        };
+        polygons[idx].rings.push(hole);
+    }
+    polygons
+}
+
+fn point_in_extent(p: &Point, ext: &Extent) -> bool {
+    p.x >= ext.minx && p.x <= ext.maxx && p.y >= ext.miny && p.y <= ext.maxy
+}
+
+/// Clip geometry to extent. Returns None if the geometry is outside.
+pub fn clip_geometry(geom: &GeometryType, ext: &Extent) -> Option<GeometryType> {
+    match geometry_extent(geom) {
+        Some(ref bbox) if contains(ext, bbox) => return Some(geom.clone()),
+        Some(ref bbox) if !intersects(ext, bbox) => return None,
+        Some(_) => {}
+        None => return None,
+    }
+    let clipped = match geom {
+        &GeometryType::Point(ref p) => Some(GeometryType::Point(Point::new(p.x, p.y, None))),
+        &GeometryType::MultiPoint(ref mp) => {
+            let points: Vec<Point> = mp.points
+                .iter()
+                .filter(|p| point_in_extent(p, ext))
+                .map(|p| Point::new(p.x, p.y, None))
+                .collect();
+            Some(GeometryType::MultiPoint(MultiPoint {
+                                              points: points,
+                                              srid: None,
+                                          }))
+        }
+        &GeometryType::LineString(ref l) => {
+            let mut lines = clip_line(l, ext);
+            match lines.len() {
+                0 => None,
+                1 => lines.pop().map(|line| GeometryType::LineString(line)),
+                _ => {
+                    Some(GeometryType::MultiLineString(MultiLineString {
+                                                           lines: lines,
+                                                           srid: None,
+                                                       }))
+                }
+            }
+        }
+        &GeometryType::MultiLineString(ref ml) => {
+            let lines: Vec<LineString> = ml.lines
+                .iter()
+                .flat_map(|l| clip_line(l, ext))
+                .collect();
+            Some(GeometryType::MultiLineString(MultiLineString {
+                                                   lines: lines,
+                                                   srid: None,
+                                               }))
+        }
+        &GeometryType::Polygon(ref p) => {
+            let mut polygons = clip_polygon(p, ext);
+            match polygons.len() {
+                0 => None,
+                1 => polygons.pop().map(|p| GeometryType::Polygon(p)),
+                _ => {
+                    Some(GeometryType::MultiPolygon(MultiPolygon {
+                                                        polygons: polygons,
+                                                        srid: None,
+                                                    }))
+                }
+            }
+        }
+        &GeometryType::MultiPolygon(ref mp) => {
+            let polygons: Vec<Polygon> = mp.polygons
+                .iter()
+                .flat_map(|p| clip_polygon(p, ext))
+                .collect();
+            Some(GeometryType::MultiPolygon(MultiPolygon {
+                                                polygons: polygons,
+                                                srid: None,
+                                            }))
+        }
+        &GeometryType::GeometryCollection(_) => None,
+    };
+    clipped.and_then(|geom| if geom.is_empty() { None } else { Some(geom) })
+}
@@ -0,0 +1,178 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use core::clip::{clip_geometry, geometry_extent};
+use core::geom::*;
+use core::grid::Extent;
+
+
+fn extent(minx: f64, miny: f64, maxx: f64, maxy: f64) -> Extent {
+    Extent {
+        minx: minx,
+        miny: miny,
+        maxx: maxx,
+        maxy: maxy,
+    }
+}
+
+fn polygon(coords: &[(f64, f64)]) -> Polygon {
+    Polygon {
+        rings: vec![LineString {
+                        points: coords.iter().map(|c| Point::new(c.0, c.1, None)).collect(),
+                        srid: None,
+                    }],
+        srid: None,
+    }
+}
+
+fn ring_coords(ring: &LineString) -> Vec<(f64, f64)> {
+    ring.points.iter().map(|p| (p.x, p.y)).collect()
+}
+
+#[test]
+fn test_clip_geometry() {
+    let ext = extent(0.0, 0.0, 10.0, 10.0);
+
+    let point = GeometryType::Point(Point::new(5.0, 5.0, None));
+    assert!(clip_geometry(&point, &ext).is_some());
+    let point = GeometryType::Point(Point::new(15.0, 5.0, None));
+    assert!(clip_geometry(&point, &ext).is_none());
+
+    // Line leaving and re-entering the extent
+    let line = GeometryType::LineString(LineString {
+                                            points: vec![Point::new(-5.0, 5.0, None),
+                                                         Point::new(5.0, 5.0, None),
+                                                         Point::new(5.0, 15.0, None),
+                                                         Point::new(8.0, 5.0, None)],
+                                            srid: None,
+                                        });
+    let clipped = clip_geometry(&line, &ext).unwrap();
+    match clipped {
+        GeometryType::MultiLineString(ref ml) => {
+            assert_eq!(ml.lines.len(), 2);
+            assert_eq!((ml.lines[0].points[0].x, ml.lines[0].points[0].y), (0.0, 5.0));
+            assert_eq!((ml.lines[0].points[2].x, ml.lines[0].points[2].y), (5.0, 10.0));
+            assert_eq!(ml.lines[1].points.len(), 2);
+        }
+        _ => panic!("MultiLineString expected"),
+    }
+
+    let polygon = GeometryType::Polygon(Polygon {
+                                            rings: vec![LineString {
+                                                            points: vec![Point::new(-5.0, -5.0, None),
+                                                                         Point::new(5.0, -5.0, None),
+                                                                         Point::new(5.0, 5.0, None),
+                                                                         Point::new(-5.0, 5.0, None),
+                                                                         Point::new(-5.0, -5.0, None)],
+                                                            srid: None,
+                                                        }],
+                                            srid: None,
+                                        });
+    let clipped = clip_geometry(&polygon, &ext).unwrap();
+    assert_eq!(format!("{:?}", geometry_extent(&clipped).unwrap()),
+               "Extent { minx: 0.0, miny: 0.0, maxx: 5.0, maxy: 5.0 }");
+    match clipped {
+        GeometryType::Polygon(ref p) => {
+            let ring = &p.rings[0].points;
+            assert_eq!(ring.len(), 5);
+            assert_eq!((ring[0].x, ring[0].y), (ring[4].x, ring[4].y));
+        }
+        _ => panic!("Polygon expected"),
+    }
+    assert!(clip_geometry(&polygon, &extent(20.0, 20.0, 30.0, 30.0)).is_none());
+}
+
+#[test]
+fn test_clip_concave_polygon() {
+    let ext = extent(0.0, 0.0, 10.0, 10.0);
+    // Arch with two legs inside the extent, connected above the extent
+    let arch = polygon(&[(2.0, 2.0),
+                         (4.0, 2.0),
+                         (4.0, 15.0),
+                         (6.0, 15.0),
+                         (6.0, 2.0),
+                         (8.0, 2.0),
+                         (8.0, 20.0),
+                         (2.0, 20.0),
+                         (2.0, 2.0)]);
+    let clipped = clip_geometry(&GeometryType::Polygon(arch), &ext).unwrap();
+    match clipped {
+        GeometryType::MultiPolygon(ref mp) => {
+            assert_eq!(mp.polygons.len(), 2);
+            // No edges along the extent boundary between the legs
+            assert_eq!(ring_coords(&mp.polygons[0].rings[0]),
+                       vec![(6.0, 10.0), (6.0, 2.0), (8.0, 2.0), (8.0, 10.0), (6.0, 10.0)]);
+            assert_eq!(ring_coords(&mp.polygons[1].rings[0]),
+                       vec![(2.0, 10.0), (2.0, 2.0), (4.0, 2.0), (4.0, 10.0), (2.0, 10.0)]);
+        }
+        _ => panic!("MultiPolygon expected"),
+    }
+    assert_eq!(clipped.area(), 32.0);
+
+    // Clockwise rings keep their orientation
+    let arch_cw = polygon(&[(2.0, 2.0),
+                            (2.0, 20.0),
+                            (8.0, 20.0),
+                            (8.0, 2.0),
+                            (6.0, 2.0),
+                            (6.0, 15.0),
+                            (4.0, 15.0),
+                            (4.0, 2.0),
+                            (2.0, 2.0)]);
+    match clip_geometry(&GeometryType::Polygon(arch_cw), &ext).unwrap() {
+        GeometryType::MultiPolygon(ref mp) => {
+            assert_eq!(mp.polygons.len(), 2);
+            assert_eq!(ring_coords(&mp.polygons[0].rings[0]),
+                       vec![(6.0, 10.0), (8.0, 10.0), (8.0, 2.0), (6.0, 2.0), (6.0, 10.0)]);
+        }
+        _ => panic!("MultiPolygon expected"),
+    }
+
+    // Notched polygon with the part above the notch outside the extent
+    let u = polygon(&[(5.0, 5.0),
+                      (15.0, 5.0),
+                      (15.0, 15.0),
+                      (5.0, 15.0),
+                      (5.0, 12.0),
+                      (12.0, 12.0),
+                      (12.0, 8.0),
+                      (5.0, 8.0),
+                      (5.0, 5.0)]);
+    match clip_geometry(&GeometryType::Polygon(u), &ext).unwrap() {
+        GeometryType::Polygon(ref p) => {
+            assert_eq!(ring_coords(&p.rings[0]),
+                       vec![(10.0, 8.0), (5.0, 8.0), (5.0, 5.0), (10.0, 5.0), (10.0, 8.0)]);
+        }
+        _ => panic!("Polygon expected"),
+    }
+
+    // Polygon around the extent
+    let around = polygon(&[(-5.0, -5.0), (15.0, -5.0), (15.0, 15.0), (-5.0, 15.0), (-5.0, -5.0)]);
+    let clipped = clip_geometry(&GeometryType::Polygon(around), &ext).unwrap();
+    assert_eq!(clipped.area(), 100.0);
+}
+
+#[test]
+fn test_clip_polygon_with_hole() {
+    let ext = extent(0.0, 0.0, 10.0, 10.0);
+    let mut p = polygon(&[(-5.0, -5.0), (15.0, -5.0), (15.0, 15.0), (-5.0, 15.0), (-5.0, -5.0)]);
+    // Hole crossing the extent boundary
+    p.rings.push(LineString {
+                     points: vec![Point::new(8.0, 4.0, None),
+                                  Point::new(8.0, 6.0, None),
+                                  Point::new(12.0, 6.0, None),
+                                  Point::new(12.0, 4.0, None),
+                                  Point::new(8.0, 4.0, None)],
+                     srid: None,
+                 });
+    match clip_geometry(&GeometryType::Polygon(p), &ext).unwrap() {
+        GeometryType::Polygon(ref p) => {
+            assert_eq!(p.rings.len(), 2);
+            assert_eq!(ring_coords(&p.rings[1]),
+                       vec![(10.0, 6.0), (10.0, 4.0), (8.0, 4.0), (8.0, 6.0), (10.0, 6.0)]);
+        }
+        _ => panic!("Polygon expected"),
+    }
+}
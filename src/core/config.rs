@@ -4,8 +4,17 @@
 //
 
 use toml::Value;
+use hyper::Client;
+use hyper::net::HttpsConnector;
+use hyper::status::StatusCode;
+use hyper_openssl::OpensslClient;
+use std::io;
 use std::io::prelude::*;
-use std::fs::File;
+use std::fs::{self, File};
+use std::env;
+use std::path::Path;
+use std::collections::HashSet;
+use std::time::Duration;
 
 
 pub trait Config<T> {
@@ -19,25 +28,274 @@ pub trait Config<T> {
     }
 }
 
+/// Config path for reading the configuration from stdin
+pub const STDIN_CONFIG: &'static str = "-";
+
+/// Timeout for fetching configuration from URLs
+const FETCH_TIMEOUT_SECS: u64 = 30;
+
+fn is_url(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+/// Name of configuration source in error messages
+fn source_name(path: &str) -> &str {
+    if path == STDIN_CONFIG { "stdin" } else { path }
+}
+
 /// Load and parse the config file into Toml table structure.
+/// The configuration is read from stdin with path `-` or fetched from `http(s)://` URLs.
+/// Files listed in the top-level `include` entry are merged into the configuration.
 pub fn read_config(path: &str) -> Result<Value, String> {
-    let mut file = match File::open(path) {
-        Ok(file) => file,
-        Err(_) => {
-            return Err("Could not find config file!".to_string());
+    let mut config = try!(read_config_file(path));
+    let path = source_name(path);
+    let includes = match config.as_table_mut().and_then(|table| table.remove("include")) {
+        Some(Value::String(pattern)) => vec![pattern],
+        Some(Value::Array(patterns)) => {
+            try!(patterns
+                     .into_iter()
+                     .map(|p| match p {
+                              Value::String(pattern) => Ok(pattern),
+                              _ => Err(format!("{} - include entry is not a string", path)),
+                          })
+                     .collect())
         }
+        Some(_) => return Err(format!("{} - include entry is not a string", path)),
+        None => return Ok(config),
     };
+    if is_url(path) {
+        return Err(format!("{} - include is not supported in configurations from URLs",
+                           path));
+    }
+    let basedir = Path::new(path).parent().unwrap_or(Path::new(""));
+    for pattern in includes {
+        for include_path in try!(expand_include(basedir, &pattern).map_err(|e| format!("{} - {}", path, e))) {
+            if !Path::new(&include_path).is_file() {
+                return Err(format!("{} - Included file '{}' not found", path, include_path));
+            }
+            let included = try!(read_config_file(&include_path));
+            if included.get("include").is_some() {
+                return Err(format!("{} - Nested includes are not supported", include_path));
+            }
+            try!(merge_config(&mut config, included).map_err(|e| format!("{} - {}", include_path, e)));
+        }
+    }
+    try!(check_tileset_names(&config).map_err(|e| format!("{} - {}", path, e)));
+    Ok(config)
+}
+
+fn read_config_file(path: &str) -> Result<Value, String> {
+    let config_toml = if path == STDIN_CONFIG {
+        let mut config_toml = String::new();
+        if let Err(err) = io::stdin().read_to_string(&mut config_toml) {
+            return Err(format!("Error while reading config from stdin: [{}]", err));
+        }
+        config_toml
+    } else if is_url(path) {
+        try!(fetch_config(path))
+    } else {
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(_) => {
+                return Err(format!("Could not find config file '{}'", path));
+            }
+        };
+        let mut config_toml = String::new();
+        if let Err(err) = file.read_to_string(&mut config_toml) {
+            return Err(format!("Error while reading config '{}': [{}]", path, err));
+        };
+        config_toml
+    };
+
+    parse_config(config_toml, source_name(path))
+}
+
+/// Fetch configuration from HTTP(S) URL
+fn fetch_config(url: &str) -> Result<String, String> {
+    let ssl = try!(OpensslClient::new().map_err(|e| format!("{} - {}", url, e)));
+    let mut client = Client::with_connector(HttpsConnector::new(ssl));
+    client.set_read_timeout(Some(Duration::from_secs(FETCH_TIMEOUT_SECS)));
+    let mut response = try!(client
+                                .get(url)
+                                .send()
+                                .map_err(|e| format!("{} - {}", url, e)));
+    if response.status != StatusCode::Ok {
+        return Err(format!("{} - HTTP status {}", url, response.status));
+    }
     let mut config_toml = String::new();
-    if let Err(err) = file.read_to_string(&mut config_toml) {
-        return Err(format!("Error while reading config: [{}]", err));
+    try!(response
+             .read_to_string(&mut config_toml)
+             .map_err(|e| format!("{} - {}", url, e)));
+    Ok(config_toml)
+}
+
+/// Match file name against pattern with wildcards `*` and `?`
+pub fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // Position after last `*` in pattern and name, for backtracking
+    let mut star: Option<(usize, usize)> = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p + 1, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Paths of included files relative to `basedir`. Wildcards are supported in file names.
+pub fn expand_include(basedir: &Path, pattern: &str) -> Result<Vec<String>, String> {
+    let path = basedir.join(pattern);
+    let filename = path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or("".to_string());
+    if !filename.contains('*') && !filename.contains('?') {
+        return Ok(vec![path.to_string_lossy().to_string()]);
+    }
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let entries = try!(fs::read_dir(dir)
+                           .map_err(|e| format!("Error reading include directory '{}': {}", dir.display(), e)));
+    let mut paths: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter(|entry| wildcard_match(&filename, &entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path().to_string_lossy().to_string())
+        .collect();
+    paths.sort();
+    if paths.is_empty() {
+        warn!("No configuration files matching include '{}'", pattern);
+    }
+    Ok(paths)
+}
+
+/// Merge included configuration. Arrays like `[[tileset]]` are appended, tables are merged.
+pub fn merge_config(config: &mut Value, included: Value) -> Result<(), String> {
+    merge_table(config, included, "")
+}
+
+fn merge_table(config: &mut Value, included: Value, prefix: &str) -> Result<(), String> {
+    let entries = match included {
+        Value::Table(table) => table,
+        _ => return Err("Included configuration is not a table".to_string()),
     };
+    let table = match config.as_table_mut() {
+        Some(table) => table,
+        None => return Err(format!("Configuration entry '{}' is not a table", prefix)),
+    };
+    for (key, value) in entries {
+        let name = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        if !table.contains_key(&key) {
+            table.insert(key, value);
+            continue;
+        }
+        let existing = table.get_mut(&key).unwrap();
+        match (existing, value) {
+            (&mut Value::Array(ref mut items), Value::Array(new_items)) => {
+                items.extend(new_items);
+            }
+            (existing @ &mut Value::Table(_), value @ Value::Table(_)) => {
+                try!(merge_table(existing, value, &name));
+            }
+            _ => return Err(format!("Duplicate configuration entry '{}'", name)),
+        }
+    }
+    Ok(())
+}
 
-    parse_config(config_toml, path)
+fn check_tileset_names(config: &Value) -> Result<(), String> {
+    let mut names = HashSet::new();
+    if let Some(tilesets) = config.get("tileset").and_then(|val| val.as_array()) {
+        for tileset in tilesets {
+            if let Some(name) = tileset.get("name").and_then(|val| val.as_str()) {
+                if !names.insert(name) {
+                    return Err(format!("Duplicate tileset '{}'", name));
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
 /// Parse the configuration into Toml table structure.
+/// Environment variables are expanded in string values after parsing.
 pub fn parse_config(config_toml: String, path: &str) -> Result<Value, String> {
-    config_toml
-        .parse::<Value>()
-        .map_err(|err| format!("{} - {}", path, err))
+    let mut config = try!(config_toml
+                              .parse::<Value>()
+                              .map_err(|err| format!("{} - {}", path, err)));
+    try!(expand_env_values(&mut config).map_err(|err| format!("{} - {}", path, err)));
+    Ok(config)
+}
+
+/// Expand environment variables in all string values. Comments and keys are not expanded
+/// and values don't need TOML escaping.
+fn expand_env_values(value: &mut Value) -> Result<(), String> {
+    match *value {
+        Value::String(ref mut text) => {
+            if text.contains("${") {
+                let expanded = try!(expand_env_vars(text));
+                *text = expanded;
+            }
+        }
+        Value::Array(ref mut items) => {
+            for item in items.iter_mut() {
+                try!(expand_env_values(item));
+            }
+        }
+        Value::Table(ref mut table) => {
+            for (_, item) in table.iter_mut() {
+                try!(expand_env_values(item));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace `${VAR}` and `${VAR:-default}` with the value of environment variable `VAR`.
+/// A `$` not followed by `{` is kept as is.
+pub fn expand_env_vars(text: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find("${") {
+        result.push_str(&rest[..pos]);
+        let end = try!(rest[pos..]
+                           .find('}')
+                           .ok_or(format!("Unterminated variable reference '{}'",
+                                          rest[pos..].lines().next().unwrap_or(""))));
+        let reference = &rest[pos + 2..pos + end];
+        let (name, default) = match reference.find(":-") {
+            Some(sep) => (&reference[..sep], Some(&reference[sep + 2..])),
+            None => (reference, None),
+        };
+        if name.is_empty() {
+            return Err(format!("Invalid variable reference '${{{}}}'", reference));
+        }
+        let value = match (env::var(name), default) {
+            (Ok(ref val), Some(default)) if val.is_empty() => default.to_string(),
+            (Ok(val), _) => val,
+            (Err(_), Some(default)) => default.to_string(),
+            (Err(_), None) => {
+                return Err(format!("Environment variable '{}' is not set", name));
+            }
+        };
+        result.push_str(&value);
+        rest = &rest[pos + end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
 }
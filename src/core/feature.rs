@@ -4,6 +4,7 @@
 //
 
 use core::geom::GeometryType;
+use serde_json;
 
 /// Supported feature attribute value types
 #[derive(Clone,PartialEq,Debug)]
@@ -17,6 +18,19 @@ pub enum FeatureAttrValType {
     Bool(bool),
 }
 
+/// JSON value of attribute
+pub fn json_attr_value(value: &FeatureAttrValType) -> serde_json::Value {
+    match value {
+        &FeatureAttrValType::String(ref v) => json!(v),
+        &FeatureAttrValType::Float(v) => json!(v),
+        &FeatureAttrValType::Double(v) => json!(v),
+        &FeatureAttrValType::Int(v) => json!(v),
+        &FeatureAttrValType::UInt(v) => json!(v),
+        &FeatureAttrValType::SInt(v) => json!(v),
+        &FeatureAttrValType::Bool(v) => json!(v),
+    }
+}
+
 pub trait Feature {
     fn fid(&self) -> Option<u64>;
     fn attributes(&self) -> Vec<FeatureAttr>; //TODO: return tuples
@@ -48,3 +62,27 @@ impl Feature for FeatureStruct {
         Ok(self.geometry.clone())
     }
 }
+
+/// Feature with an additional attribute replacing an attribute with the same key
+pub struct TaggedFeature<'a> {
+    pub feature: &'a Feature,
+    pub tag: &'a FeatureAttr,
+}
+
+impl<'a> Feature for TaggedFeature<'a> {
+    fn fid(&self) -> Option<u64> {
+        self.feature.fid()
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        let mut attrs: Vec<FeatureAttr> = self.feature
+            .attributes()
+            .into_iter()
+            .filter(|attr| attr.key != self.tag.key)
+            .collect();
+        attrs.push(self.tag.clone());
+        attrs
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        self.feature.geometry()
+    }
+}
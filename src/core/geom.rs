@@ -15,6 +15,14 @@ pub type MultiLineString = ewkb::MultiLineString;
 pub type MultiPolygon = ewkb::MultiPolygon;
 pub type GeometryCollection = ewkb::GeometryCollection;
 
+/// Geometry types which can be encoded as MVT features
+pub const MVT_GEOMETRY_TYPES: [&'static str; 6] = ["POINT",
+                                                   "LINESTRING",
+                                                   "POLYGON",
+                                                   "MULTIPOINT",
+                                                   "MULTILINESTRING",
+                                                   "MULTIPOLYGON"];
+
 /// Generic Geometry Data Type
 #[derive(Debug)]
 pub enum GeometryType {
@@ -27,16 +35,96 @@ pub enum GeometryType {
     GeometryCollection(GeometryCollection),
 }
 
+fn clone_line(line: &LineString) -> LineString {
+    LineString {
+        points: line.points.iter().map(|p| Point::new(p.x, p.y, p.srid)).collect(),
+        srid: line.srid,
+    }
+}
+
+fn clone_polygon(polygon: &Polygon) -> Polygon {
+    Polygon {
+        rings: polygon.rings.iter().map(clone_line).collect(),
+        srid: polygon.srid,
+    }
+}
+
+fn clone_multipoint(multipoint: &MultiPoint) -> MultiPoint {
+    MultiPoint {
+        points: multipoint.points.iter().map(|p| Point::new(p.x, p.y, p.srid)).collect(),
+        srid: multipoint.srid,
+    }
+}
+
+fn clone_multiline(multiline: &MultiLineString) -> MultiLineString {
+    MultiLineString {
+        lines: multiline.lines.iter().map(clone_line).collect(),
+        srid: multiline.srid,
+    }
+}
+
+fn clone_multipolygon(multipolygon: &MultiPolygon) -> MultiPolygon {
+    MultiPolygon {
+        polygons: multipolygon.polygons.iter().map(clone_polygon).collect(),
+        srid: multipolygon.srid,
+    }
+}
+
+fn clone_collection(collection: &GeometryCollection) -> GeometryCollection {
+    GeometryCollection { geometries: collection.geometries.iter().map(clone_ewkb).collect() }
+}
+
+fn clone_ewkb(geom: &ewkb::GeometryT<Point>) -> ewkb::GeometryT<Point> {
+    match geom {
+        &ewkb::GeometryT::Point(ref p) => ewkb::GeometryT::Point(Point::new(p.x, p.y, p.srid)),
+        &ewkb::GeometryT::LineString(ref l) => ewkb::GeometryT::LineString(clone_line(l)),
+        &ewkb::GeometryT::Polygon(ref p) => ewkb::GeometryT::Polygon(clone_polygon(p)),
+        &ewkb::GeometryT::MultiPoint(ref mp) => ewkb::GeometryT::MultiPoint(clone_multipoint(mp)),
+        &ewkb::GeometryT::MultiLineString(ref ml) => {
+            ewkb::GeometryT::MultiLineString(clone_multiline(ml))
+        }
+        &ewkb::GeometryT::MultiPolygon(ref mp) => {
+            ewkb::GeometryT::MultiPolygon(clone_multipolygon(mp))
+        }
+        &ewkb::GeometryT::GeometryCollection(ref gc) => {
+            ewkb::GeometryT::GeometryCollection(clone_collection(gc))
+        }
+    }
+}
+
 impl Clone for GeometryType {
     fn clone(&self) -> Self {
         match self {
             &GeometryType::Point(ref p) => GeometryType::Point(Point::new(p.x, p.y, None)),
-            _ => panic!("Not implemented yet"), // TODO: either implement other types or don't clone (FeatureStruct)...
+            &GeometryType::LineString(ref l) => GeometryType::LineString(clone_line(l)),
+            &GeometryType::Polygon(ref p) => GeometryType::Polygon(clone_polygon(p)),
+            &GeometryType::MultiPoint(ref mp) => GeometryType::MultiPoint(clone_multipoint(mp)),
+            &GeometryType::MultiLineString(ref ml) => {
+                GeometryType::MultiLineString(clone_multiline(ml))
+            }
+            &GeometryType::MultiPolygon(ref mp) => {
+                GeometryType::MultiPolygon(clone_multipolygon(mp))
+            }
+            &GeometryType::GeometryCollection(ref gc) => {
+                GeometryType::GeometryCollection(clone_collection(gc))
+            }
         }
     }
 }
 
 impl GeometryType {
+    /// Type name like returned by PostGIS `GeometryType()`
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            &GeometryType::Point(_) => "POINT",
+            &GeometryType::LineString(_) => "LINESTRING",
+            &GeometryType::Polygon(_) => "POLYGON",
+            &GeometryType::MultiPoint(_) => "MULTIPOINT",
+            &GeometryType::MultiLineString(_) => "MULTILINESTRING",
+            &GeometryType::MultiPolygon(_) => "MULTIPOLYGON",
+            &GeometryType::GeometryCollection(_) => "GEOMETRYCOLLECTION",
+        }
+    }
     pub fn is_empty(&self) -> bool {
         match self {
             &GeometryType::LineString(ref p) => p.points.len() == 0,
@@ -47,4 +135,65 @@ impl GeometryType {
             _ => false,
         }
     }
+    /// Area of polygons (holes subtracted). 0 for other geometry types.
+    pub fn area(&self) -> f64 {
+        match self {
+            &GeometryType::Polygon(ref p) => polygon_area(p),
+            &GeometryType::MultiPolygon(ref mp) => mp.polygons.iter().map(polygon_area).sum(),
+            _ => 0.0,
+        }
+    }
+    /// Length of lines. 0 for other geometry types.
+    pub fn length(&self) -> f64 {
+        match self {
+            &GeometryType::LineString(ref l) => line_length(l),
+            &GeometryType::MultiLineString(ref ml) => ml.lines.iter().map(line_length).sum(),
+            _ => 0.0,
+        }
+    }
+    /// Components of geometry collections (nested collections are flattened).
+    /// Other geometries are returned unchanged.
+    pub fn explode(self) -> Vec<GeometryType> {
+        match self {
+            GeometryType::GeometryCollection(gc) => {
+                gc.geometries
+                    .into_iter()
+                    .flat_map(|geom| GeometryType::from_ewkb(geom).explode())
+                    .collect()
+            }
+            geom => vec![geom],
+        }
+    }
+    fn from_ewkb(geom: ewkb::GeometryT<Point>) -> GeometryType {
+        match geom {
+            ewkb::GeometryT::Point(g) => GeometryType::Point(g),
+            ewkb::GeometryT::LineString(g) => GeometryType::LineString(g),
+            ewkb::GeometryT::Polygon(g) => GeometryType::Polygon(g),
+            ewkb::GeometryT::MultiPoint(g) => GeometryType::MultiPoint(g),
+            ewkb::GeometryT::MultiLineString(g) => GeometryType::MultiLineString(g),
+            ewkb::GeometryT::MultiPolygon(g) => GeometryType::MultiPolygon(g),
+            ewkb::GeometryT::GeometryCollection(g) => GeometryType::GeometryCollection(g),
+        }
+    }
+}
+
+fn ring_area(ring: &LineString) -> f64 {
+    let area: f64 = ring.points
+        .windows(2)
+        .map(|seg| seg[0].x * seg[1].y - seg[1].x * seg[0].y)
+        .sum();
+    (area / 2.0).abs()
+}
+
+fn polygon_area(polygon: &Polygon) -> f64 {
+    let mut rings = polygon.rings.iter();
+    let exterior = rings.next().map_or(0.0, ring_area);
+    (exterior - rings.map(ring_area).sum::<f64>()).max(0.0)
+}
+
+fn line_length(line: &LineString) -> f64 {
+    line.points
+        .windows(2)
+        .map(|seg| ((seg[1].x - seg[0].x).powi(2) + (seg[1].y - seg[0].y).powi(2)).sqrt())
+        .sum()
 }
@@ -25,3 +25,93 @@ fn test_geom_creation() {
     };
     assert_eq!(p.x, 960000.0);
 }
+
+#[test]
+fn test_type_name() {
+    use core::geom::{GeometryCollection, MultiPoint, MVT_GEOMETRY_TYPES};
+
+    let point = GeometryType::new_point(1.0, 2.0);
+    assert_eq!(point.type_name(), "POINT");
+    let multipoint = GeometryType::MultiPoint(MultiPoint {
+                                                  points: vec![Point::new(1.0, 2.0, None)],
+                                                  srid: None,
+                                              });
+    assert_eq!(multipoint.type_name(), "MULTIPOINT");
+    assert!(MVT_GEOMETRY_TYPES.contains(&point.type_name()));
+    assert!(MVT_GEOMETRY_TYPES.contains(&multipoint.type_name()));
+    let collection = GeometryType::GeometryCollection(GeometryCollection {
+                                                          geometries: Vec::new(),
+                                                      });
+    assert!(!MVT_GEOMETRY_TYPES.contains(&collection.type_name()));
+}
+
+#[test]
+fn test_explode() {
+    use core::geom::{GeometryCollection, LineString};
+
+    let line = LineString {
+        points: vec![Point::new(0.0, 0.0, None), Point::new(1.0, 1.0, None)],
+        srid: None,
+    };
+    let inner = GeometryCollection {
+        geometries: vec![ewkb::GeometryT::Point(Point::new(2.0, 2.0, None))],
+    };
+    let collection = GeometryType::GeometryCollection(GeometryCollection {
+        geometries: vec![ewkb::GeometryT::Point(Point::new(1.0, 1.0, None)),
+                         ewkb::GeometryT::LineString(line),
+                         ewkb::GeometryT::GeometryCollection(inner)],
+    });
+    let parts = collection.explode();
+    assert_eq!(parts.len(), 3);
+    match (&parts[0], &parts[1], &parts[2]) {
+        (&GeometryType::Point(ref p1),
+         &GeometryType::LineString(ref l),
+         &GeometryType::Point(ref p2)) => {
+            assert_eq!((p1.x, l.points.len(), p2.x), (1.0, 2, 2.0));
+        }
+        _ => panic!("Unexpected components {:?}", parts),
+    }
+
+    let parts = GeometryType::new_point(1.0, 2.0).explode();
+    assert_eq!(parts.len(), 1);
+}
+
+#[test]
+fn test_geom_clone() {
+    use core::geom::{LineString, MultiLineString};
+
+    let line = LineString {
+        points: vec![Point::new(0.0, 0.0, None), Point::new(1.0, 1.0, None)],
+        srid: None,
+    };
+    let geom = GeometryType::MultiLineString(MultiLineString {
+                                                 lines: vec![line],
+                                                 srid: Some(3857),
+                                             });
+    assert_eq!(format!("{:?}", geom.clone()), format!("{:?}", geom));
+}
+
+#[test]
+fn test_area_length() {
+    use core::geom::{LineString, Polygon};
+
+    let ring = |coords: &[(f64, f64)]| {
+        LineString {
+            points: coords.iter().map(|&(x, y)| Point::new(x, y, None)).collect(),
+            srid: None,
+        }
+    };
+    let polygon = GeometryType::Polygon(Polygon {
+                                            rings: vec![ring(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0),
+                                                               (0.0, 4.0), (0.0, 0.0)]),
+                                                        ring(&[(1.0, 1.0), (1.0, 2.0), (2.0, 2.0),
+                                                               (2.0, 1.0), (1.0, 1.0)])],
+                                            srid: None,
+                                        });
+    assert_eq!(polygon.area(), 15.0);
+    assert_eq!(polygon.length(), 0.0);
+    let line = GeometryType::LineString(ring(&[(0.0, 0.0), (3.0, 4.0), (3.0, 6.0)]));
+    assert_eq!(line.length(), 7.0);
+    assert_eq!(line.area(), 0.0);
+    assert_eq!(GeometryType::new_point(1.0, 1.0).area(), 0.0);
+}
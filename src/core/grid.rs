@@ -11,7 +11,7 @@ use serde::de::{Deserialize, Deserializer};
 use std::fmt;
 
 
-#[derive(PartialEq, Deserialize, Debug)]
+#[derive(PartialEq, Clone, Deserialize, Debug)]
 pub struct Extent {
     pub minx: f64,
     pub miny: f64,
@@ -106,6 +106,8 @@ pub struct Grid {
     resolutions: Vec<f64>,
     /// Grid origin
     pub origin: Origin,
+    /// Extent of grid in WGS84 coordinates (advertised in TileJSON)
+    pub bounds: Option<Extent>,
 }
 
 impl Grid {
@@ -141,6 +143,7 @@ impl Grid {
                               1.07288360595703e-5,
                               5.36441802978516e-6],
             origin: Origin::BottomLeft,
+            bounds: None,
         }
     }
 
@@ -181,15 +184,86 @@ impl Grid {
                               0.0746455354347424,
                               0.0373227677173712],
             origin: Origin::BottomLeft,
+            bounds: None,
         }
     }
 
+    /// Check grid definition
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.height == 0 {
+            return Err("Grid tile size must be greater than 0".to_string());
+        }
+        if self.extent.maxx <= self.extent.minx || self.extent.maxy <= self.extent.miny {
+            return Err("Invalid grid extent (expected minx < maxx and miny < maxy)".to_string());
+        }
+        if self.resolutions.is_empty() {
+            return Err("Missing grid resolutions".to_string());
+        }
+        if self.resolutions.iter().any(|res| *res <= 0.0) ||
+           self.resolutions
+               .windows(2)
+               .any(|pair| pair[1] >= pair[0]) {
+            return Err("Grid resolutions must be positive and ordered from largest to smallest"
+                           .to_string());
+        }
+        Ok(())
+    }
+    /// Swiss LV95 grid (swisstopo tile matrix set EPSG:2056)
+    pub fn lv95() -> Grid {
+        Grid {
+            width: 256,
+            height: 256,
+            extent: Extent {
+                minx: 2420000.0,
+                miny: 1030000.0,
+                maxx: 2900000.0,
+                maxy: 1350000.0,
+            },
+            srid: 2056,
+            units: Unit::M,
+            resolutions: vec![4000.0, 3750.0, 3500.0, 3250.0, 3000.0, 2750.0, 2500.0, 2250.0,
+                              2000.0, 1750.0, 1500.0, 1250.0, 1000.0, 750.0, 650.0, 500.0, 250.0,
+                              100.0, 50.0, 20.0, 10.0, 5.0, 2.5, 2.0, 1.5, 1.0, 0.5, 0.25, 0.1],
+            origin: Origin::TopLeft,
+            bounds: Some(Extent {
+                             minx: 5.140242,
+                             miny: 45.398181,
+                             maxx: 11.47757,
+                             maxy: 48.230651,
+                         }),
+        }
+    }
+
+    /// Grid with `tile_size` pixel tiles covering the same extent per tile and zoom level
+    pub fn with_tile_size(mut self, tile_size: u16) -> Grid {
+        let factor = self.width as f64 / tile_size as f64;
+        self.resolutions = self.resolutions.iter().map(|res| res * factor).collect();
+        self.width = tile_size;
+        self.height = tile_size;
+        self
+    }
+    /// Tile width in pixels
+    pub fn tile_size(&self) -> u32 {
+        self.width as u32
+    }
+    /// Tile height in pixels
+    pub fn tile_height(&self) -> u32 {
+        self.height as u32
+    }
+    /// Extent of MVT tiles in tile units (16 units per pixel)
+    pub fn mvt_extent(&self) -> u32 {
+        self.tile_size() * 16
+    }
     pub fn nlevels(&self) -> u8 {
         self.resolutions.len() as u8
     }
     pub fn maxzoom(&self) -> u8 {
         self.nlevels() - 1
     }
+    /// Grid with 2^z x 2^z tiles at zoom level z, like the Web Mercator grid
+    pub fn is_quadtree(&self) -> bool {
+        (0..self.nlevels()).all(|z| z < 32 && self.level_limit(z) == (1 << z, 1 << z))
+    }
     pub fn pixel_width(&self, zoom: u8) -> f64 {
         self.resolutions[zoom as usize] //TODO: assumes grid unit 'm'
     }
@@ -224,14 +298,20 @@ impl Grid {
     }
     /// reverse y tile for XYZ adressing scheme
     pub fn ytile_from_xyz(&self, ytile: u32, zoom: u8) -> u32 {
-        let res = self.resolutions[zoom as usize];
-        let unitheight = self.height as f64 * res;
+        if self.origin == Origin::TopLeft {
+            // Grid rows are already counted from the top
+            return ytile;
+        }
         // TODO: cache maxy for each resolution
-        let maxy = ((self.extent.maxy - self.extent.minx - 0.01 * unitheight) / unitheight)
-            .ceil() as u32;
+        let (_, maxy) = self.level_limit(zoom);
         let y = maxy.saturating_sub(ytile).saturating_sub(1); // y = maxy-ytile-1
         y
     }
+    /// reverse y tile of TMS adressing scheme (rows counted from bottom) for XYZ adressing scheme
+    pub fn ytile_from_tms(&self, ytile: u32, zoom: u8) -> u32 {
+        let (_, maxy) = self.level_limit(zoom);
+        maxy.saturating_sub(ytile).saturating_sub(1)
+    }
     /// Extent of a given tile in XYZ adressing scheme
     pub fn tile_extent_xyz(&self, xtile: u32, ytile: u32, zoom: u8) -> Extent {
         let y = self.ytile_from_xyz(ytile, zoom);
@@ -245,10 +325,18 @@ impl Grid {
 
         let maxy = ((self.extent.maxy - self.extent.miny - 0.01 * unitheight) / unitheight)
             .ceil() as u32;
-        let maxx = ((self.extent.maxx - self.extent.miny - 0.01 * unitwidth) / unitwidth)
+        let maxx = ((self.extent.maxx - self.extent.minx - 0.01 * unitwidth) / unitwidth)
             .ceil() as u32;
         (maxx, maxy)
     }
+    /// Check whether tile indices are within the grid
+    pub fn tile_in_range(&self, xtile: u32, ytile: u32, zoom: u8) -> bool {
+        if zoom >= self.nlevels() {
+            return false;
+        }
+        let (maxx, maxy) = self.level_limit(zoom);
+        xtile < maxx && ytile < maxy
+    }
     /// Tile index limits covering extent
     pub fn tile_limits(&self, extent: Extent, tolerance: i32) -> Vec<ExtentInt> {
         // Based on mapcache_grid_compute_limits
@@ -314,26 +402,59 @@ impl Config<Grid> for Grid {
         if config.get("grid").is_none() {
             return Err("Missing configuration entry [grid]".to_string());
         }
-        if let Some(predef) = config.get("grid").and_then(|g| g.get("predefined")) {
-            predef
-                .as_str()
-                .ok_or("grid.predefined entry is not a string".to_string())
-                .and_then(|gridname| match gridname {
-                              "wgs84" => Ok(Grid::wgs84()),
-                              "web_mercator" => Ok(Grid::web_mercator()),
-                              _ => Err(format!("Unkown grid '{}'", gridname)),
-                          })
+        // `grid = "lv95"` is a shortcut for `[grid] predefined = "lv95"`
+        let predefined = match config["grid"] {
+            toml::Value::String(_) => config.get("grid"),
+            _ => config.get("grid").and_then(|g| g.get("predefined")),
+        };
+        if let Some(predef) = predefined {
+            let grid = try!(predef
+                                .as_str()
+                                .ok_or("grid.predefined entry is not a string".to_string())
+                                .and_then(|gridname| match gridname {
+                                              "wgs84" => Ok(Grid::wgs84()),
+                                              "web_mercator" => Ok(Grid::web_mercator()),
+                                              "lv95" => Ok(Grid::lv95()),
+                                              _ => Err(format!("Unkown grid '{}'", gridname)),
+                                          }));
+            match config["grid"].get("tile_size") {
+                None => Ok(grid),
+                Some(&toml::Value::Integer(256)) => Ok(grid),
+                Some(&toml::Value::Integer(512)) => Ok(grid.with_tile_size(512)),
+                Some(_) => {
+                    Err("Tile size of predefined grids must be 256 or 512".to_string())
+                }
+            }
         } else {
-            let ref gridcfg = config["grid"];
-            let grid = gridcfg.clone().try_into::<Grid>();
-            grid.map_err(|e| format!("Error reading configuration - {}", e))
+            let mut gridcfg = config["grid"].clone();
+            // `tile_size` sets width and height
+            if let Some(tile_size) = gridcfg.get("tile_size").cloned() {
+                if let Some(table) = gridcfg.as_table_mut() {
+                    table.entry("width".to_string()).or_insert(tile_size.clone());
+                    table.entry("height".to_string()).or_insert(tile_size);
+                }
+            }
+            let grid = try!(gridcfg
+                                .try_into::<Grid>()
+                                .map_err(|e| format!("Error reading configuration - {}", e)));
+            try!(grid.validate());
+            Ok(grid)
         }
     }
     fn gen_config() -> String {
         let toml = r#"
 [grid]
-# Predefined grids: web_mercator, wgs84
+# Predefined grids: web_mercator, wgs84, lv95
 predefined = "web_mercator"
+# Tile size in pixels (256 or 512)
+#tile_size = 512
+# Custom grid:
+#tile_size = 256
+#extent = { minx = 2420000.0, miny = 1030000.0, maxx = 2900000.0, maxy = 1350000.0 }
+#srid = 2056
+#units = "M"
+#resolutions = [4000.0,3750.0,3500.0,3250.0,3000.0,2750.0,2500.0,2250.0,2000.0,1750.0,1500.0,1250.0,1000.0,750.0,650.0,500.0,250.0,100.0,50.0,20.0,10.0,5.0,2.5,2.0,1.5,1.0,0.5]
+#origin = "TopLeft"
 "#;
         toml.to_string()
     }
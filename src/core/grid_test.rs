@@ -108,6 +108,14 @@ fn test_grid_calculations() {
     assert_eq!(grid.level_limit(0), (1, 1));
     assert_eq!(grid.level_limit(10), (1024, 1024));
 
+    assert!(grid.tile_in_range(0, 0, 0));
+    assert!(!grid.tile_in_range(1, 0, 0));
+    assert!(!grid.tile_in_range(0, 1, 0));
+    assert!(grid.tile_in_range(1023, 1023, 10));
+    assert!(!grid.tile_in_range(1024, 0, 10));
+    assert!(grid.tile_in_range(0, 0, 22));
+    assert!(!grid.tile_in_range(0, 0, 23));
+
     let limits = grid.tile_limits(grid.tile_extent(0, 0, 0), 0);
     assert_eq!(limits[0],
                ExtentInt {
@@ -213,17 +221,135 @@ fn test_grid_from_config() {
                    maxy: 1247600.,
                });
     //BBOX ZH: (2669255.48 1223902.28, 2716899.60125 1283304.23625)
+    // Rows of top-left grids are counted from the top
     let extent = grid.tile_extent_xyz(10, 4, 17);
     assert_eq!(extent,
                Extent {
                    minx: 2676000.,
-                   miny: -109951160275600.,
+                   miny: 1222000.,
+                   maxx: 2701600.,
+                   maxy: 1247600.,
+               });
+    assert_eq!(grid.level_limit(17), (19, 13));
+    assert!(grid.tile_in_range(18, 12, 17));
+    assert!(!grid.tile_in_range(19, 0, 17));
+
+    // tile_size shortcut and asymmetric extent with bottom-left origin
+    let toml = r#"
+        [grid]
+        tile_size = 512
+        extent = { minx = 2420000.0, miny = 1030000.0, maxx = 2900000.0, maxy = 1350000.0 }
+        srid = 2056
+        units = "M"
+        resolutions = [1000.0,500.0,250.0]
+        origin = "BottomLeft"
+        "#;
+    let config = parse_config(toml.to_string(), "").unwrap();
+    let grid = Grid::from_config(&config).unwrap();
+    assert_eq!(grid.level_limit(0), (1, 1));
+    assert_eq!(grid.level_limit(2), (4, 3));
+    assert_eq!(grid.ytile_from_xyz(0, 2), 2);
+    assert_eq!(grid.ytile_from_tms(0, 2), 2);
+    assert_eq!(grid.tile_extent(0, 0, 2),
+               Extent {
+                   minx: 2420000.,
+                   miny: 1030000.,
+                   maxx: 2548000.,
+                   maxy: 1158000.,
+               });
+
+    // errors
+    let toml = r#"
+        [grid]
+        tile_size = 256
+        extent = { minx = 2420000.0, miny = 1030000.0, maxx = 2900000.0, maxy = 1350000.0 }
+        srid = 2056
+        units = "M"
+        resolutions = [250.0,500.0]
+        origin = "BottomLeft"
+        "#;
+    let config = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(Grid::from_config(&config).err(),
+               Some("Grid resolutions must be positive and ordered from largest to smallest"
+                        .to_string()));
+}
+
+
+#[test]
+fn test_lv95_grid() {
+    use core::parse_config;
+
+    let config = parse_config("grid = \"lv95\"".to_string(), "").unwrap();
+    let grid = Grid::from_config(&config).unwrap();
+    assert_eq!(grid.srid, 2056);
+    assert_eq!(grid.nlevels(), 29);
+    assert_eq!(grid.pixel_width(28), 0.1);
+
+    // swisstopo WMTS tile matrix set 2056
+    assert_eq!(grid.level_limit(0), (1, 1));
+    assert_eq!(grid.level_limit(28), (18750, 12500));
+    assert!(!grid.is_quadtree());
+    assert!(Grid::web_mercator().is_quadtree());
+    assert_eq!(grid.tile_extent(0, 0, 0),
+               Extent {
+                   minx: 2420000.,
+                   miny: 326000.,
+                   maxx: 3444000.,
+                   maxy: 1350000.,
+               });
+    // Tile containing Bern (2600000, 1200000)
+    assert_eq!(grid.tile_extent_xyz(70, 58, 20),
+               Extent {
+                   minx: 2599200.,
+                   miny: 1198960.,
+                   maxx: 2601760.,
+                   maxy: 1201520.,
+               });
+    assert_eq!(grid.tile_extent(10, 4, 17),
+               Extent {
+                   minx: 2676000.,
+                   miny: 1222000.,
                    maxx: 2701600.,
-                   maxy: -109951160250000.,
+                   maxy: 1247600.,
                });
 }
 
 
+#[test]
+fn test_tile_size() {
+    use core::parse_config;
+
+    let grid256 = Grid::web_mercator();
+    assert_eq!(grid256.tile_size(), 256);
+    assert_eq!(grid256.mvt_extent(), 4096);
+
+    let toml = r#"
+        [grid]
+        predefined = "web_mercator"
+        tile_size = 512
+        "#;
+    let config = parse_config(toml.to_string(), "").unwrap();
+    let grid512 = Grid::from_config(&config).unwrap();
+    assert_eq!(grid512.tile_size(), 512);
+    assert_eq!(grid512.mvt_extent(), 8192);
+    assert_eq!(grid512.nlevels(), grid256.nlevels());
+    assert_eq!(grid512.pixel_width(10), grid256.pixel_width(10) / 2.0);
+    // Tiles cover the same area
+    assert_eq!(grid512.level_limit(10), grid256.level_limit(10));
+    assert_eq!(grid512.tile_extent_xyz(536, 358, 10),
+               grid256.tile_extent_xyz(536, 358, 10));
+
+    let toml = r#"
+        [grid]
+        predefined = "web_mercator"
+        tile_size = 300
+        "#;
+    let config = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(Grid::from_config(&config).err(),
+               Some("Tile size of predefined grids must be 256 or 512".to_string()));
+}
+
+
 mod web_mercator {
 
     // --- Web Mercator calculations ---
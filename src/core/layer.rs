@@ -4,22 +4,58 @@
 //
 
 use core::Config;
+use core::geom::GeometryType;
+use core::feature::{FeatureAttr, FeatureAttrValType};
 use service::glstyle_converter::toml_style_to_gljson;
 use toml;
 use std::collections::HashMap;
+use std::cmp;
 use datasource::PostgisInput;
 
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct LayerQuery {
     pub minzoom: Option<u8>,
     pub maxzoom: Option<u8>,
+    /// Source table for this zoom level range (e.g. generalized copy of the layer table)
+    pub table_name: Option<String>,
+    pub sql: Option<String>,
+}
+
+/// Source table or query of a combined layer
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct LayerSource {
+    /// Value of the source attribute of features from this source
+    pub name: String,
+    pub table_name: Option<String>,
     pub sql: Option<String>,
 }
 
-#[derive(Default, Serialize, Deserialize, Debug)]
+/// Layer setting for a zoom level range
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ZoomSetting<T> {
+    pub minzoom: Option<u8>,
+    pub maxzoom: Option<u8>,
+    pub value: T,
+}
+
+impl<T: Copy> ZoomSetting<T> {
+    /// Value of first setting containing zoom level
+    pub fn lookup(settings: &[ZoomSetting<T>], level: u8) -> Option<T> {
+        settings
+            .iter()
+            .find(|s| {
+                      level >= s.minzoom.unwrap_or(0) && level <= s.maxzoom.unwrap_or(22)
+                  })
+            .map(|s| s.value)
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, Debug)]
 pub struct Layer {
     pub name: String,
+    /// Datasource name (default: first datasource)
+    pub datasource: Option<String>,
     pub geometry_field: Option<String>,
     pub geometry_type: Option<String>,
     /// Spatial reference system (PostGIS SRID)
@@ -28,13 +64,62 @@ pub struct Layer {
     // Input for derived queries
     pub table_name: Option<String>,
     pub query_limit: Option<u32>,
+    /// Zoom level dependent maximal number of features
+    #[serde(default)]
+    pub query_limit_zoom: Vec<ZoomSetting<u32>>,
+    /// Zoom levels without features from this layer
+    pub minzoom: Option<u8>,
+    pub maxzoom: Option<u8>,
     // Explicit queries
     #[serde(default)]
     pub query: Vec<LayerQuery>,
     /// Simplify geometry (lines and polygons)
     pub simplify: Option<bool>,
+    /// Simplify polygons with ST_SimplifyPreserveTopology instead of snapping to a grid
+    pub simplify_preserve_topology: Option<bool>,
+    /// Simplification tolerance in pixels (default 0.5)
+    pub tolerance: Option<f64>,
+    /// Zoom level dependent simplification tolerance in pixels
+    #[serde(default)]
+    pub tolerance_zoom: Vec<ZoomSetting<f64>>,
+    /// Minimal area of polygons in square pixels
+    pub min_area: Option<f64>,
+    /// Minimal length of lines in pixels
+    pub min_length: Option<f64>,
     /// Tile buffer size in pixels
     pub buffer_size: Option<u32>,
+    /// Zoom level dependent tile buffer size in pixels
+    #[serde(default)]
+    pub buffer_size_zoom: Vec<ZoomSetting<u32>>,
+    /// Clip geometries to the buffered tile extent before encoding
+    pub clip_geometries: Option<bool>,
+    /// Extent of the MVT layer in tile units (default: 16 units per pixel, e.g. 4096)
+    pub mvt_extent: Option<u32>,
+    /// Handling of fields with unsupported types: "stringify" (default) or "skip"
+    pub unsupported_types: Option<String>,
+    /// Encoding of date and timestamp fields: "iso" (default) or "epoch"
+    pub datetime_format: Option<String>,
+    /// Handling of geometry collections: "explode" into component features (default) or "skip"
+    pub geometry_collections: Option<String>,
+    /// Add layer `<name>_label` with a label point for each polygon
+    pub label_point: Option<bool>,
+    /// Attributes encoded as feature properties (all if None)
+    pub fields: Option<Vec<String>>,
+    /// Attributes never encoded as feature properties
+    pub exclude_fields: Option<Vec<String>>,
+    /// Attributes included in UTFGrid interaction grids (layer not in grid if None)
+    pub utfgrid_fields: Option<Vec<String>>,
+    /// Sources combined into this layer
+    #[serde(default)]
+    pub source: Vec<LayerSource>,
+    /// Attribute with the source name of combined layers (default: "source")
+    pub source_field: Option<String>,
+    /// Geometries are label points of polygons (generated label layer)
+    #[serde(skip_serializing, skip_deserializing)]
+    pub point_on_surface: bool,
+    /// Source name of a layer reading a source of a combined layer
+    #[serde(skip_serializing, skip_deserializing)]
+    pub source_value: Option<String>,
     // Inline style
     pub style: Option<String>,
 }
@@ -55,6 +140,10 @@ impl Layer {
             ..Default::default()
         }
     }
+    /// Source table or feature collection. Defaults to the layer name.
+    pub fn table_name(&self) -> &String {
+        self.table_name.as_ref().unwrap_or(&self.name)
+    }
     pub fn layers_from_config(config: &toml::Value) -> Result<Vec<Self>, String> {
         config
             .get("layer")
@@ -65,25 +154,292 @@ impl Layer {
                                          .to_string())
                       })
             .and_then(|layers| {
-                          Ok(layers
-                                 .iter()
-                                 .map(|layer| Layer::from_config(layer).unwrap())
-                                 .collect())
+                          layers
+                              .iter()
+                              .map(|layer| Layer::from_config(layer))
+                              .collect()
                       })
     }
     pub fn minzoom(&self) -> u8 {
-        self.query
-            .iter()
-            .map(|q| q.minzoom())
-            .min()
-            .unwrap_or(0)
+        self.minzoom
+            .unwrap_or(self.query
+                           .iter()
+                           .map(|q| q.minzoom())
+                           .min()
+                           .unwrap_or(0))
     }
     pub fn maxzoom(&self) -> u8 {
-        self.query
+        self.maxzoom
+            .unwrap_or(self.query
+                           .iter()
+                           .map(|q| q.maxzoom())
+                           .max()
+                           .unwrap_or(22))
+    }
+    /// Maximal number of features for zoom level
+    pub fn query_limit(&self, level: u8) -> Option<u32> {
+        ZoomSetting::lookup(&self.query_limit_zoom, level).or(self.query_limit)
+    }
+    /// Simplification is enabled explicitly or by configuring a tolerance
+    pub fn simplify_enabled(&self) -> bool {
+        self.simplify
+            .unwrap_or(self.tolerance.is_some() || !self.tolerance_zoom.is_empty())
+    }
+    /// Simplification tolerance in pixels for zoom level
+    pub fn tolerance(&self, level: u8) -> f64 {
+        ZoomSetting::lookup(&self.tolerance_zoom, level).unwrap_or(self.tolerance.unwrap_or(0.5))
+    }
+    /// Polygons smaller than `min_area` or lines shorter than `min_length`
+    /// at resolution `pixel_width`
+    pub fn below_min_size(&self, geom: &GeometryType, pixel_width: f64) -> bool {
+        match geom {
+            &GeometryType::Polygon(_) |
+            &GeometryType::MultiPolygon(_) => {
+                self.min_area
+                    .map_or(false, |min| geom.area() < min * pixel_width * pixel_width)
+            }
+            &GeometryType::LineString(_) |
+            &GeometryType::MultiLineString(_) => {
+                self.min_length
+                    .map_or(false, |min| geom.length() < min * pixel_width)
+            }
+            _ => false,
+        }
+    }
+    /// Geometries are clipped by t-rex independent of the datasource
+    pub fn clip_geometries_enabled(&self) -> bool {
+        self.clip_geometries.unwrap_or(false)
+    }
+    /// Geometries are clipped when a buffer size is configured
+    pub fn clip_enabled(&self) -> bool {
+        self.buffer_size.is_some() || !self.buffer_size_zoom.is_empty()
+    }
+    /// Extent of the MVT layer in tile units. Grid default if not configured.
+    pub fn mvt_extent(&self, default: u32) -> u32 {
+        self.mvt_extent.unwrap_or(default)
+    }
+    /// Tile buffer size in pixels for zoom level
+    pub fn buffer_size(&self, level: u8) -> u32 {
+        ZoomSetting::lookup(&self.buffer_size_zoom, level).unwrap_or(self.buffer_size.unwrap_or(0))
+    }
+    /// Skip fields with unsupported types instead of converting them to text
+    pub fn skip_unsupported_types(&self) -> bool {
+        self.unsupported_types
+            .as_ref()
+            .map_or(false, |val| val == "skip")
+    }
+    /// Encode date and timestamp fields as seconds since 1970-01-01 UTC
+    pub fn datetime_epoch(&self) -> bool {
+        self.datetime_format
+            .as_ref()
+            .map_or(false, |val| val == "epoch")
+    }
+    /// Encode components of geometry collections as separate features
+    pub fn explode_collections(&self) -> bool {
+        self.geometry_collections
+            .as_ref()
+            .map_or(true, |val| val == "explode")
+    }
+    /// Geometry column with mixed geometry types or geometry collections
+    pub fn has_collections(&self) -> bool {
+        match self.geometry_type.as_ref().map(|val| val.as_str()) {
+            Some("GEOMETRY") | Some("GEOMETRYCOLLECTION") => true,
+            _ => false,
+        }
+    }
+    /// Attribute is encoded in tiles according to `fields` and `exclude_fields`
+    pub fn field_exposed(&self, name: &str) -> bool {
+        self.fields
+            .as_ref()
+            .map_or(true, |fields| fields.iter().any(|f| f == name)) &&
+        !self.exclude_fields
+             .as_ref()
+             .map_or(false, |fields| fields.iter().any(|f| f == name))
+    }
+    /// Generated layer `<name>_label` with label points of polygons
+    pub fn label_layer(&self) -> Option<Layer> {
+        if !self.label_point.unwrap_or(false) {
+            return None;
+        }
+        let mut layer = self.clone();
+        layer.name = format!("{}_label", self.name);
+        layer.table_name = Some(self.table_name().clone());
+        layer.geometry_type = Some("POINT".to_string());
+        layer.label_point = None;
+        layer.point_on_surface = true;
+        // Label points are not clipped, but included in the tile containing them
+        layer.buffer_size = None;
+        layer.buffer_size_zoom = Vec::new();
+        layer.style = None;
+        Some(layer)
+    }
+    /// Attribute with the source name of combined layers
+    pub fn source_field(&self) -> &str {
+        self.source_field.as_ref().map_or("source", |field| field.as_str())
+    }
+    /// Queries select the source table by zoom level
+    pub fn has_zoom_tables(&self) -> bool {
+        self.query.iter().any(|q| q.table_name.is_some())
+    }
+    /// Features are read by multiple input layers
+    pub fn is_combined(&self) -> bool {
+        !self.source.is_empty() || self.has_zoom_tables()
+    }
+    /// Layers reading the features of this layer.
+    /// Each source of a combined layer is read by a layer named `<name>.<source>`.
+    /// With zoom dependent tables, each query is read by a layer named `<name>.z<minzoom>-<maxzoom>`.
+    pub fn input_layers(&self) -> Vec<Layer> {
+        if self.has_zoom_tables() {
+            return self.query
+                .iter()
+                .map(|query| {
+                    let mut layer = self.clone();
+                    layer.name = format!("{}.z{}-{}", self.name, query.minzoom(), query.maxzoom());
+                    layer.minzoom = Some(cmp::max(self.minzoom(), query.minzoom()));
+                    layer.maxzoom = Some(cmp::min(self.maxzoom(), query.maxzoom()));
+                    layer.label_point = None;
+                    layer.style = None;
+                    if query.table_name.is_some() {
+                        layer.table_name = query.table_name.clone();
+                        layer.query = Vec::new();
+                    } else {
+                        layer.query = vec![query.clone()];
+                    }
+                    layer
+                })
+                .collect();
+        }
+        if self.source.is_empty() {
+            return vec![self.clone()];
+        }
+        self.source
             .iter()
-            .map(|q| q.maxzoom())
-            .max()
-            .unwrap_or(22)
+            .map(|source| {
+                let mut layer = self.clone();
+                layer.name = format!("{}.{}", self.name, source.name);
+                layer.source = Vec::new();
+                layer.source_value = Some(source.name.clone());
+                layer.label_point = None;
+                layer.style = None;
+                if source.table_name.is_some() {
+                    layer.table_name = source.table_name.clone();
+                    layer.query = Vec::new();
+                }
+                if source.sql.is_some() {
+                    layer.query = vec![LayerQuery {
+                                           minzoom: None,
+                                           maxzoom: None,
+                                           table_name: None,
+                                           sql: source.sql.clone(),
+                                       }];
+                }
+                layer
+            })
+            .collect()
+    }
+    /// Source attribute added to features of a source layer
+    pub fn source_tag(&self) -> Option<FeatureAttr> {
+        self.source_value
+            .as_ref()
+            .map(|value| {
+                     FeatureAttr {
+                         key: self.source_field().to_string(),
+                         value: FeatureAttrValType::String(value.clone()),
+                     }
+                 })
+    }
+    /// Check layer settings
+    pub fn validate(&self) -> Result<(), String> {
+        if self.label_point.unwrap_or(false) {
+            match self.geometry_type.as_ref().map(|val| val.as_str()) {
+                Some("POLYGON") | Some("MULTIPOLYGON") => {}
+                _ => {
+                    return Err(format!("label_point in layer '{}' requires geometry_type POLYGON or MULTIPOLYGON",
+                                       self.name))
+                }
+            }
+        }
+        match self.datetime_format.as_ref().map(|val| val.as_str()) {
+            None | Some("iso") | Some("epoch") => {}
+            Some(val) => {
+                return Err(format!("Invalid datetime_format '{}' in layer '{}' (expected 'iso' or 'epoch')",
+                                   val,
+                                   self.name))
+            }
+        }
+        if let Some(extent) = self.mvt_extent {
+            if !extent.is_power_of_two() || extent < 64 || extent > 65536 {
+                return Err(format!("Invalid mvt_extent {} in layer '{}' (expected a power of two between 64 and 65536)",
+                                   extent,
+                                   self.name));
+            }
+        }
+        match self.geometry_collections.as_ref().map(|val| val.as_str()) {
+            None | Some("explode") | Some("skip") => {}
+            Some(val) => {
+                return Err(format!("Invalid geometry_collections '{}' in layer '{}' (expected 'explode' or 'skip')",
+                                   val,
+                                   self.name))
+            }
+        }
+        match self.unsupported_types.as_ref().map(|val| val.as_str()) {
+            None | Some("stringify") | Some("skip") => {}
+            Some(val) => {
+                return Err(format!("Invalid unsupported_types '{}' in layer '{}' (expected 'stringify' or 'skip')",
+                                   val,
+                                   self.name))
+            }
+        }
+        for (i, source) in self.source.iter().enumerate() {
+            if self.source[..i].iter().any(|s| s.name == source.name) {
+                return Err(format!("Duplicate source '{}' in layer '{}'",
+                                   source.name,
+                                   self.name));
+            }
+            if source.table_name.is_some() == source.sql.is_some() {
+                return Err(format!("Source '{}' in layer '{}' requires either table_name or sql",
+                                   source.name,
+                                   self.name));
+            }
+        }
+        for (i, query) in self.query.iter().enumerate() {
+            if query.table_name.is_some() && query.sql.is_some() {
+                return Err(format!("Query of layer '{}' requires either table_name or sql",
+                                   self.name));
+            }
+            if self.has_zoom_tables() &&
+               self.query[..i]
+                   .iter()
+                   .any(|q| q.minzoom() <= query.maxzoom() && query.minzoom() <= q.maxzoom()) {
+                return Err(format!("Overlapping zoom levels of queries in layer '{}' with table_name",
+                                   self.name));
+            }
+        }
+        if self.has_zoom_tables() && !self.source.is_empty() {
+            return Err(format!("Layer '{}' with sources can't have queries with table_name",
+                               self.name));
+        }
+        let source_queries = self.source.iter().map(|source| &source.sql);
+        for sql in self.query.iter().map(|query| &query.sql).chain(source_queries) {
+            if let Some(ref sql) = *sql {
+                let geom = try!(self.geometry_field
+                                    .as_ref()
+                                    .ok_or(format!("Missing geometry_field in layer '{}' with custom query",
+                                                   self.name)));
+                // Queries without bbox condition are wrapped with one by the datasource
+                if !sql.contains("!bbox!") {
+                    warn!("Query of layer '{}' has no !bbox! condition - filtering result with WHERE {} && !bbox!",
+                          self.name,
+                          geom);
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Check configured minzoom/maxzoom
+    pub fn in_zoom_range(&self, level: u8) -> bool {
+        self.minzoom.map_or(true, |minzoom| level >= minzoom) &&
+        self.maxzoom.map_or(true, |maxzoom| level <= maxzoom)
     }
     // SQL query for zoom level
     pub fn query(&self, level: u8) -> Option<&String> {
@@ -126,6 +482,13 @@ impl Config<Layer> for Layer {
         // Remove TOML style - will be converted separately
         let mut layercfg = layerval.as_table().unwrap().clone();
         let layerstyle = layercfg.remove("style");
+        // `table` is an alias for `table_name`
+        if let Some(table) = layercfg.remove("table") {
+            if layercfg.contains_key("table_name") {
+                return Err("Layer configuration contains both table and table_name".to_string());
+            }
+            layercfg.insert("table_name".to_string(), table);
+        }
         let layer = toml::Value::Table(layercfg).try_into::<Layer>();
         layer
             .and_then(|mut lyr| {
@@ -150,12 +513,20 @@ table_name = "mytable"
 geometry_field = "wkb_geometry"
 geometry_type = "POINT"
 #fid_field = "id"
+# Additional layer points_label with a label point for each polygon
+#label_point = true
+# Encode only these attributes / never encode these attributes
+#fields = ["name", "population"]
+#exclude_fields = ["internal_id"]
 #simplify = true
+#tolerance = 0.5
 #buffer-size = 10
+#minzoom = 0
+#maxzoom = 22
 #[[tileset.layer.query]]
 #minzoom = 0
 #maxzoom = 22
-#sql = "SELECT name,wkb_geometry FROM mytable"
+#sql = "SELECT name,wkb_geometry FROM mytable WHERE wkb_geometry && !bbox!"
 "#;
         toml.to_string()
     }
@@ -165,6 +536,9 @@ geometry_type = "POINT"
         lines.push(format!(r#"name = "{}""#, self.name));
         lines.push("\n[[tileset.layer]]".to_string());
         lines.push(format!(r#"name = "{}""#, self.name));
+        if let Some(ref datasource) = self.datasource {
+            lines.push(format!(r#"datasource = "{}""#, datasource));
+        }
         match self.table_name {
             Some(ref table_name) => lines.push(format!(r#"table_name = "{}""#, table_name)),
             _ => lines.push(r#"#table_name = "mytable""#.to_string()),
@@ -179,6 +553,10 @@ geometry_type = "POINT"
             Some(ref geometry_type) => lines.push(format!("geometry_type = \"{}\"", geometry_type)),
             _ => lines.push("#geometry_type = \"POINT\"".to_string()),
         }
+        if self.has_collections() {
+            // Mixed geometry types are read as geometry collections
+            lines.push("#geometry_collections = \"explode\"".to_string());
+        }
         match self.srid {
             Some(ref srid) => lines.push(format!("srid = {}", srid)),
             _ => lines.push("#srid = 3857".to_string()),
@@ -197,11 +575,20 @@ geometry_type = "POINT"
                 Some(ref simplify) => lines.push(format!("simplify = {}", simplify)),
                 _ => lines.push(format!("#simplify = true")),
             }
+            if let Some(ref tolerance) = self.tolerance {
+                lines.push(format!("tolerance = {}", tolerance));
+            }
         }
         match self.query_limit {
             Some(ref query_limit) => lines.push(format!("query_limit = {}", query_limit)),
             _ => lines.push("#query_limit = 1000".to_string()),
         }
+        if let Some(ref minzoom) = self.minzoom {
+            lines.push(format!("minzoom = {}", minzoom));
+        }
+        if let Some(ref maxzoom) = self.maxzoom {
+            lines.push(format!("maxzoom = {}", maxzoom));
+        }
         match self.query(0) {
             Some(ref query) => {
                 lines.push("[[tileset.layer.query]]".to_string());
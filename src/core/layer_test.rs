@@ -4,6 +4,7 @@
 //
 
 use core::layer::Layer;
+use core::feature::FeatureAttrValType;
 
 
 #[test]
@@ -83,6 +84,8 @@ fn test_toml_decode() {
     assert_eq!(cfg.query.len(), 0);
     assert_eq!(cfg.minzoom(), 0);
     assert_eq!(cfg.maxzoom(), 22);
+    assert!(cfg.in_zoom_range(0));
+    assert!(cfg.in_zoom_range(22));
 
     // Invalid config: missing required field
     let ref layer = layers[2];
@@ -126,23 +129,335 @@ fn test_layers_from_config() {
         [[tileset.layer]]
         name = "layer2"
         buffer-size = 10
+
+        [[tileset.layer]]
+        name = "roads"
+        table = "public.t_roads_2024"
         "#;
 
     let config = parse_config(toml.to_string(), "").unwrap();
 
     let tilesets = config["tileset"].as_array().unwrap();
     let layers = Layer::layers_from_config(&tilesets[0]).unwrap();
-    assert_eq!(layers.len(), 2);
+    assert_eq!(layers.len(), 3);
     assert_eq!(layers[0].name, "points");
     assert_eq!(layers[0].table_name,
                Some("ne_10m_populated_places".to_string()));
     assert_eq!(layers[0].buffer_size, Some(10));
     assert_eq!(layers[1].table_name, None);
+    assert_eq!(layers[1].table_name(), "layer2");
     assert_eq!(layers[1].buffer_size, None); // toml deserialization bug!!
+    // Layer name independent of source table
+    assert_eq!(layers[2].name, "roads");
+    assert_eq!(layers[2].table_name(), "public.t_roads_2024");
 
     // errors
     let emptyconfig = parse_config("".to_string(), "").unwrap();
     let layers = Layer::layers_from_config(&emptyconfig);
     assert_eq!(layers.err(),
                Some("Missing configuration entry [[tileset.layer]]".to_string()));
+    let config = parse_config(r#"
+        [[layer]]
+        name = "roads"
+        table = "roads"
+        table_name = "roads"
+        "#
+                                      .to_string(),
+                              "")
+            .unwrap();
+    assert_eq!(Layer::layers_from_config(&config).err(),
+               Some("Layer configuration contains both table and table_name".to_string()));
+}
+
+#[test]
+fn test_layer_zoom_range() {
+    use core::parse_config;
+    let toml = r#"
+        [[tileset.layer]]
+        name = "buildings"
+        minzoom = 14
+        maxzoom = 18
+        [[tileset.layer.query]]
+        minzoom = 10
+        sql = "SELECT wkb_geometry FROM buildings"
+        "#;
+
+    let tomlcfg = parse_config(toml.to_string(), "").unwrap();
+    let layers = tomlcfg["tileset"]["layer"].as_array().unwrap();
+    let cfg: Layer = layers[0].clone().try_into().unwrap();
+    assert_eq!(cfg.minzoom(), 14);
+    assert_eq!(cfg.maxzoom(), 18);
+    assert!(!cfg.in_zoom_range(13));
+    assert!(cfg.in_zoom_range(14));
+    assert!(cfg.in_zoom_range(18));
+    assert!(!cfg.in_zoom_range(19));
+}
+
+#[test]
+fn test_layer_tolerance() {
+    use core::parse_config;
+    let toml = r#"
+        [[tileset.layer]]
+        name = "roads"
+        tolerance = 1.0
+        [[tileset.layer.tolerance_zoom]]
+        maxzoom = 8
+        value = 2.0
+        [[tileset.layer.tolerance_zoom]]
+        minzoom = 14
+        value = 0.0
+
+        [[tileset.layer]]
+        name = "rivers"
+        simplify = true
+        "#;
+
+    let tomlcfg = parse_config(toml.to_string(), "").unwrap();
+    let layers = tomlcfg["tileset"]["layer"].as_array().unwrap();
+    let cfg: Layer = layers[0].clone().try_into().unwrap();
+    assert!(cfg.simplify_enabled());
+    assert_eq!(cfg.tolerance(0), 2.0);
+    assert_eq!(cfg.tolerance(8), 2.0);
+    assert_eq!(cfg.tolerance(9), 1.0);
+    assert_eq!(cfg.tolerance(14), 0.0);
+
+    // Boolean shortcut with default tolerance
+    let cfg: Layer = layers[1].clone().try_into().unwrap();
+    assert!(cfg.simplify_enabled());
+    assert_eq!(cfg.tolerance(10), 0.5);
+
+    assert!(!Layer::new("points").simplify_enabled());
+}
+
+#[test]
+fn test_layer_query_limit() {
+    use core::parse_config;
+    let toml = r#"
+        [[tileset.layer]]
+        name = "points"
+        query_limit = 1000
+        [[tileset.layer.query_limit_zoom]]
+        maxzoom = 5
+        value = 100
+        [[tileset.layer.query_limit_zoom]]
+        minzoom = 14
+        value = 10000
+        "#;
+
+    let tomlcfg = parse_config(toml.to_string(), "").unwrap();
+    let layers = tomlcfg["tileset"]["layer"].as_array().unwrap();
+    let cfg: Layer = layers[0].clone().try_into().unwrap();
+    assert_eq!(cfg.query_limit(0), Some(100));
+    assert_eq!(cfg.query_limit(5), Some(100));
+    assert_eq!(cfg.query_limit(6), Some(1000));
+    assert_eq!(cfg.query_limit(14), Some(10000));
+
+    assert_eq!(Layer::new("points").query_limit(10), None);
+}
+
+#[test]
+fn test_layer_buffer_size() {
+    use core::parse_config;
+    let toml = r#"
+        [[tileset.layer]]
+        name = "roads"
+        buffer_size = 4
+        [[tileset.layer.buffer_size_zoom]]
+        maxzoom = 8
+        value = 16
+        [[tileset.layer.buffer_size_zoom]]
+        minzoom = 14
+        value = 0
+
+        [[tileset.layer]]
+        name = "labels"
+        [[tileset.layer.buffer_size_zoom]]
+        minzoom = 10
+        value = 64
+        "#;
+
+    let tomlcfg = parse_config(toml.to_string(), "").unwrap();
+    let layers = tomlcfg["tileset"]["layer"].as_array().unwrap();
+    let cfg: Layer = layers[0].clone().try_into().unwrap();
+    assert!(cfg.clip_enabled());
+    assert_eq!(cfg.buffer_size(0), 16);
+    assert_eq!(cfg.buffer_size(8), 16);
+    assert_eq!(cfg.buffer_size(9), 4);
+    assert_eq!(cfg.buffer_size(14), 0);
+
+    // Zoom levels without setting are clipped without buffer
+    let cfg: Layer = layers[1].clone().try_into().unwrap();
+    assert!(cfg.clip_enabled());
+    assert_eq!(cfg.buffer_size(9), 0);
+    assert_eq!(cfg.buffer_size(10), 64);
+
+    assert!(!Layer::new("points").clip_enabled());
+}
+
+#[test]
+fn test_layer_validate() {
+    use core::parse_config;
+    let toml = r#"
+        [[tileset.layer]]
+        name = "places"
+        geometry_field = "wkb_geometry"
+        [[tileset.layer.query]]
+        sql = "WITH p AS (SELECT * FROM places) SELECT name,wkb_geometry FROM p WHERE wkb_geometry && !bbox!"
+
+        [[tileset.layer]]
+        name = "noindex"
+        geometry_field = "wkb_geometry"
+        [[tileset.layer.query]]
+        sql = "SELECT name,wkb_geometry FROM places"
+
+        [[tileset.layer]]
+        name = "nogeom"
+        [[tileset.layer.query]]
+        sql = "SELECT name,wkb_geometry FROM places WHERE wkb_geometry && !bbox!"
+        "#;
+
+    let tomlcfg = parse_config(toml.to_string(), "").unwrap();
+    let layers = tomlcfg["tileset"]["layer"].as_array().unwrap();
+    let cfg: Layer = layers[0].clone().try_into().unwrap();
+    assert_eq!(cfg.validate(), Ok(()));
+    let cfg: Layer = layers[1].clone().try_into().unwrap();
+    assert_eq!(cfg.validate(), Ok(()));
+    let cfg: Layer = layers[2].clone().try_into().unwrap();
+    assert_eq!(cfg.validate(),
+               Err("Missing geometry_field in layer 'nogeom' with custom query".to_string()));
+    assert_eq!(Layer::new("table").validate(), Ok(()));
+
+    let mut layer = Layer::new("table");
+    layer.unsupported_types = Some("skip".to_string());
+    assert_eq!(layer.validate(), Ok(()));
+    assert!(layer.skip_unsupported_types());
+    layer.datetime_format = Some("epoch".to_string());
+    assert_eq!(layer.validate(), Ok(()));
+    assert!(layer.datetime_epoch());
+    layer.datetime_format = Some("unix".to_string());
+    assert_eq!(layer.validate(),
+               Err("Invalid datetime_format 'unix' in layer 'table' (expected 'iso' or 'epoch')"
+                       .to_string()));
+    layer.datetime_format = None;
+    layer.unsupported_types = Some("ignore".to_string());
+    assert_eq!(layer.validate(),
+               Err("Invalid unsupported_types 'ignore' in layer 'table' (expected 'stringify' or 'skip')"
+                       .to_string()));
+    layer.unsupported_types = None;
+    assert!(layer.explode_collections());
+    layer.geometry_collections = Some("skip".to_string());
+    assert_eq!(layer.validate(), Ok(()));
+    assert!(!layer.explode_collections());
+    layer.geometry_collections = Some("split".to_string());
+    assert_eq!(layer.validate(),
+               Err("Invalid geometry_collections 'split' in layer 'table' (expected 'explode' or 'skip')"
+                       .to_string()));
+    layer.geometry_collections = None;
+    assert_eq!(layer.mvt_extent(4096), 4096);
+    layer.mvt_extent = Some(8192);
+    assert_eq!(layer.validate(), Ok(()));
+    assert_eq!(layer.mvt_extent(4096), 8192);
+    layer.mvt_extent = Some(4000);
+    assert_eq!(layer.validate(),
+               Err("Invalid mvt_extent 4000 in layer 'table' (expected a power of two between 64 and 65536)"
+                       .to_string()));
+}
+
+#[test]
+fn test_field_exposed() {
+    let mut layer = Layer::new("places");
+    assert!(layer.field_exposed("name"));
+    layer.fields = Some(vec!["name".to_string(), "pop".to_string()]);
+    assert!(layer.field_exposed("name"));
+    assert!(!layer.field_exposed("adm0name"));
+    layer.exclude_fields = Some(vec!["pop".to_string()]);
+    assert!(layer.field_exposed("name"));
+    assert!(!layer.field_exposed("pop"));
+    layer.fields = None;
+    assert!(layer.field_exposed("adm0name"));
+    assert!(!layer.field_exposed("pop"));
+}
+
+#[test]
+fn test_input_layers() {
+    use core::{parse_config, Config};
+    let toml = r#"
+        [[tileset.layer]]
+        name = "landuse"
+        geometry_field = "geom"
+        geometry_type = "POLYGON"
+        simplify = true
+          [[tileset.layer.source]]
+          name = "forest"
+          table_name = "forest"
+          [[tileset.layer.source]]
+          name = "water"
+          sql = "SELECT geom, name FROM lakes WHERE geom && !bbox!"
+        "#;
+    let config = parse_config(toml.to_string(), "").unwrap();
+    let layer = Layer::from_config(&config["tileset"]["layer"][0]).unwrap();
+    assert_eq!(layer.validate(), Ok(()));
+    assert!(layer.source_tag().is_none());
+
+    let layers = layer.input_layers();
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0].name, "landuse.forest");
+    assert_eq!(layers[0].table_name, Some("forest".to_string()));
+    assert_eq!(layers[0].query(10), None);
+    assert_eq!(layers[0].simplify, Some(true));
+    assert_eq!(layers[1].query(10),
+               Some(&"SELECT geom, name FROM lakes WHERE geom && !bbox!".to_string()));
+    let tag = layers[1].source_tag().unwrap();
+    assert_eq!(tag.key, "source");
+    assert_eq!(tag.value, FeatureAttrValType::String("water".to_string()));
+
+    let mut invalid = layer.clone();
+    invalid.source[1].table_name = Some("lakes".to_string());
+    assert_eq!(invalid.validate(),
+               Err("Source 'water' in layer 'landuse' requires either table_name or sql"
+                       .to_string()));
+    let mut invalid = layer.clone();
+    invalid.source[1].name = "forest".to_string();
+    assert_eq!(invalid.validate(),
+               Err("Duplicate source 'forest' in layer 'landuse'".to_string()));
+    let mut invalid = layer.clone();
+    invalid.source[1].sql = Some("SELECT geom FROM lakes".to_string());
+    assert_eq!(invalid.validate(), Ok(()));
+
+    // Layers without sources read their own features
+    let layers = Layer::new("places").input_layers();
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].name, "places");
+}
+
+#[test]
+fn test_label_layer() {
+    let mut layer = Layer::new("countries");
+    layer.table_name = Some(String::from("admin_0_countries"));
+    layer.geometry_field = Some(String::from("wkb_geometry"));
+    layer.geometry_type = Some(String::from("MULTIPOLYGON"));
+    layer.buffer_size = Some(4);
+    assert!(layer.label_layer().is_none());
+    // Label layer reads the source table of a layer without table_name
+    layer.table_name = None;
+    layer.label_point = Some(true);
+    assert_eq!(layer.label_layer().unwrap().table_name(), "countries");
+    layer.label_point = None;
+    layer.table_name = Some(String::from("admin_0_countries"));
+
+    layer.label_point = Some(true);
+    assert_eq!(layer.validate(), Ok(()));
+    let label = layer.label_layer().unwrap();
+    assert_eq!(label.name, "countries_label");
+    assert_eq!(label.geometry_type, Some(String::from("POINT")));
+    assert_eq!(label.table_name, Some(String::from("admin_0_countries")));
+    assert!(label.point_on_surface);
+    assert!(!label.clip_enabled());
+    assert!(label.label_layer().is_none());
+    assert_eq!(label.validate(), Ok(()));
+
+    layer.geometry_type = Some(String::from("LINESTRING"));
+    assert_eq!(layer.validate(),
+               Err("label_point in layer 'countries' requires geometry_type POLYGON or MULTIPOLYGON"
+                       .to_string()));
 }
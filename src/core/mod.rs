@@ -6,17 +6,21 @@
 #[macro_use]
 mod enum_serializer;
 pub mod geom;
+pub mod clip;
 pub mod screen;
 pub mod grid;
 pub mod layer;
 pub mod feature;
 pub mod config;
+pub mod sqlite;
 
 pub use self::config::{Config, read_config, parse_config};
 
 #[cfg(test)]
 mod geom_test;
 #[cfg(test)]
+mod clip_test;
+#[cfg(test)]
 mod grid_test;
 #[cfg(test)]
 mod layer_test;
@@ -0,0 +1,64 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use rusqlite::{self, Connection, OpenFlags};
+use std::sync::Mutex;
+
+
+/// Maximal number of idle connections kept open
+const MAX_IDLE_CONNECTIONS: usize = 16;
+
+/// Pool of connections to an SQLite database file.
+///
+/// SQLite connections can't be shared between threads, so each thread
+/// takes its own connection and concurrent reads don't wait for each other.
+pub struct SqlitePool {
+    pub path: String,
+    flags: OpenFlags,
+    /// SQL executed on each new connection
+    init_sql: &'static str,
+    idle: Mutex<Vec<Connection>>,
+}
+
+impl SqlitePool {
+    /// Open the first connection, which also creates a new database when `flags` allow it
+    pub fn open(path: &str,
+                flags: OpenFlags,
+                init_sql: &'static str)
+                -> Result<SqlitePool, rusqlite::Error> {
+        let pool = SqlitePool {
+            path: path.to_string(),
+            flags: flags,
+            init_sql: init_sql,
+            idle: Mutex::new(Vec::new()),
+        };
+        let conn = try!(pool.connect());
+        pool.idle.lock().unwrap().push(conn);
+        Ok(pool)
+    }
+    fn connect(&self) -> Result<Connection, rusqlite::Error> {
+        let conn = try!(Connection::open_with_flags(&self.path, self.flags));
+        // Wait for locks held by other connections instead of failing with SQLITE_BUSY
+        try!(conn.execute_batch("PRAGMA busy_timeout = 5000;"));
+        try!(conn.execute_batch(self.init_sql));
+        Ok(conn)
+    }
+    /// Call `f` with an idle connection or a new one if none is available
+    pub fn with_connection<T, F>(&self, f: F) -> Result<T, rusqlite::Error>
+        where F: FnOnce(&Connection) -> Result<T, rusqlite::Error>
+    {
+        let idle = self.idle.lock().unwrap().pop();
+        let conn = match idle {
+            Some(conn) => conn,
+            None => try!(self.connect()),
+        };
+        let res = f(&conn);
+        let mut idle = self.idle.lock().unwrap();
+        if idle.len() < MAX_IDLE_CONNECTIONS {
+            idle.push(conn);
+        }
+        res
+    }
+}
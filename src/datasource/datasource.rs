@@ -7,6 +7,7 @@ use core::layer::Layer;
 use core::grid::Extent;
 use core::grid::Grid;
 use core::feature::Feature;
+use std::cell::{Cell, RefCell};
 
 
 pub trait DatasourceInput {
@@ -18,3 +19,36 @@ pub trait DatasourceInput {
                             read: F)
         where F: FnMut(&Feature);
 }
+
+thread_local! {
+    /// Set when a tile query of the current thread exceeded its timeout
+    static QUERY_TIMED_OUT: Cell<bool> = Cell::new(false);
+    /// First failed tile query of the current thread
+    static QUERY_ERROR: RefCell<Option<String>> = RefCell::new(None)
+}
+
+/// Returns whether a tile query of the current thread timed out since the last call.
+/// Tiles are generated on the request thread, so the flag belongs to a single tile request.
+pub fn take_query_timeout() -> bool {
+    QUERY_TIMED_OUT.with(|flag| flag.replace(false))
+}
+
+/// Returns the first failed tile query of the current thread since the last call.
+pub fn take_query_error() -> Option<String> {
+    QUERY_ERROR.with(|error| error.borrow_mut().take())
+}
+
+/// Report a timed out tile query of the current thread
+pub fn set_query_timeout() {
+    QUERY_TIMED_OUT.with(|flag| flag.set(true));
+}
+
+/// Report a failed tile query of the current thread. Only the first error is kept.
+pub fn set_query_error(err: String) {
+    QUERY_ERROR.with(|error| {
+        let mut error = error.borrow_mut();
+        if error.is_none() {
+            *error = Some(err);
+        }
+    });
+}
@@ -0,0 +1,211 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use datasource::{Datasource, PostgisInput};
+use core::Config;
+use core::layer::Layer;
+use serde_json;
+use toml;
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+
+
+/// Name of datasource configured with a single [datasource] entry
+pub const DEFAULT_DATASOURCE: &'static str = "default";
+
+/// Named datasources of a service
+pub struct Datasources {
+    pub datasources: BTreeMap<String, Datasource>,
+    /// Datasource used for layers without `datasource` entry
+    pub default: Option<String>,
+}
+
+impl Datasources {
+    pub fn new() -> Datasources {
+        Datasources {
+            datasources: BTreeMap::new(),
+            default: None,
+        }
+    }
+    /// Add datasource. The first datasource is the default.
+    pub fn add(&mut self, name: &str, input: Datasource) {
+        if self.default.is_none() {
+            self.default = Some(name.to_string());
+        }
+        self.datasources.insert(name.to_string(), input);
+    }
+    /// New instance with all datasources connected
+    pub fn connected(&self) -> Datasources {
+        Datasources {
+            datasources: self.datasources
+                .iter()
+                .map(|(name, input)| (name.clone(), input.connected()))
+                .collect(),
+            default: self.default.clone(),
+        }
+    }
+    pub fn get(&self, name: &str) -> Option<&Datasource> {
+        self.datasources.get(name)
+    }
+    pub fn default(&self) -> Option<&Datasource> {
+        self.default.as_ref().and_then(|name| self.get(name))
+    }
+    fn layer_datasource_name<'a>(&'a self, layer: &'a Layer) -> Option<&'a String> {
+        layer.datasource.as_ref().or(self.default.as_ref())
+    }
+    /// Datasource of layer
+    pub fn for_layer(&self, layer: &Layer) -> Option<&Datasource> {
+        self.layer_datasource_name(layer)
+            .and_then(|name| self.datasources.get(name))
+    }
+    pub fn for_layer_mut(&mut self, layer: &Layer) -> Option<&mut Datasource> {
+        let name = self.layer_datasource_name(layer).cloned();
+        match name {
+            Some(name) => self.datasources.get_mut(&name),
+            None => None,
+        }
+    }
+    /// Check that the datasource of each layer exists.
+    /// Queries are prepared per layer name and datasource, so layers with the same name
+    /// in the same datasource must have the same definition.
+    pub fn check_layers(&self, layers: &[&Layer]) -> Result<(), String> {
+        let mut definitions = BTreeMap::new();
+        for layer in layers {
+            let dsname = match self.layer_datasource_name(layer) {
+                Some(name) if self.datasources.contains_key(name) => name,
+                name => {
+                    return Err(format!("Unknown datasource '{}' in layer '{}'",
+                                       name.map_or("", |name| name.as_str()),
+                                       layer.name))
+                }
+            };
+            let definition = serde_json::to_value(layer).unwrap_or(serde_json::Value::Null);
+            match definitions.entry((dsname, &layer.name)) {
+                Entry::Occupied(entry) => {
+                    if *entry.get() != definition {
+                        return Err(format!("Layer '{}' of datasource '{}' is defined more than once with different settings",
+                                           layer.name,
+                                           dsname));
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(definition);
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Detect layers of each datasource. Layers of datasources other than the default
+    /// reference their datasource. Duplicate layer names are prefixed with the datasource name.
+    pub fn detect_layers(&self, detect_geometry_types: bool) -> Vec<Layer> {
+        let mut layers: Vec<Layer> = Vec::new();
+        for (dsname, input) in &self.datasources {
+            for mut layer in input.detect_layers(detect_geometry_types) {
+                if self.default.as_ref() != Some(dsname) {
+                    layer.datasource = Some(dsname.clone());
+                }
+                if layers.iter().any(|l| l.name == layer.name) {
+                    let prefixed = format!("{}_{}", dsname, layer.name);
+                    let mut name = prefixed.clone();
+                    let mut n = 2;
+                    while layers.iter().any(|l| l.name == name) {
+                        name = format!("{}_{}", prefixed, n);
+                        n += 1;
+                    }
+                    warn!("Layer '{}' of datasource '{}' renamed to '{}'",
+                          layer.name,
+                          dsname,
+                          name);
+                    layer.name = name;
+                }
+                layers.push(layer);
+            }
+        }
+        layers
+    }
+}
+
+impl From<Datasource> for Datasources {
+    fn from(input: Datasource) -> Datasources {
+        let mut datasources = Datasources::new();
+        datasources.add(DEFAULT_DATASOURCE, input);
+        datasources
+    }
+}
+
+impl From<PostgisInput> for Datasources {
+    fn from(input: PostgisInput) -> Datasources {
+        Datasources::from(Datasource::Postgis(input))
+    }
+}
+
+/// Check datasource type of configuration entry
+fn check_type(dscfg: &toml::Value) -> Result<(), String> {
+    dscfg
+        .get("type")
+        .ok_or("Missing configuration entry 'datasource.type'".to_string())
+        .and_then(|val| {
+                      val.as_str()
+                          .ok_or("type entry is not a string".to_string())
+                  })
+        .and_then(|tn| match tn {
+                      "postgis" | "gpkg" | "geojson" => Ok(()),
+                      _ => Err(format!("Unsupported datasource '{}'", tn)),
+                  })
+}
+
+impl Config<Datasources> for Datasources {
+    fn from_config(config: &toml::Value) -> Result<Self, String> {
+        let mut datasources = Datasources::new();
+        match config.get("datasource") {
+            Some(&toml::Value::Array(ref entries)) => {
+                for dscfg in entries {
+                    let name = try!(dscfg
+                                        .get("name")
+                                        .ok_or("Missing configuration entry name in [[datasource]]"
+                                                   .to_string())
+                                        .and_then(|val| {
+                                                      val.as_str()
+                                                          .ok_or("datasource.name entry is not a string"
+                                                                     .to_string())
+                                                  }));
+                    try!(check_type(dscfg));
+                    let input = try!(Datasource::from_datasource_config(dscfg, config));
+                    datasources.add(name, input);
+                }
+                if datasources.datasources.is_empty() {
+                    return Err("Missing configuration entry [[datasource]]".to_string());
+                }
+            }
+            Some(dscfg) => {
+                try!(check_type(dscfg));
+                let input = try!(Datasource::from_datasource_config(dscfg, config));
+                datasources.add(DEFAULT_DATASOURCE, input);
+            }
+            None => return Err("Missing configuration entry 'datasource.type'".to_string()),
+        }
+        Ok(datasources)
+    }
+    fn gen_config() -> String {
+        PostgisInput::gen_config()
+    }
+    fn gen_runtime_config(&self) -> String {
+        if self.datasources.len() == 1 && self.default.as_ref().map(|n| n.as_str()) == Some(DEFAULT_DATASOURCE) {
+            return self.default().unwrap().gen_runtime_config();
+        }
+        let mut config = String::new();
+        // The first entry is the default datasource
+        let mut entries: Vec<(&String, &Datasource)> = self.datasources.iter().collect();
+        entries.sort_by_key(|&(name, _)| Some(name) != self.default.as_ref());
+        for (name, input) in entries {
+            // Single datasource settings as [[datasource]] entry
+            let dsconfig = input.gen_runtime_config();
+            config.push_str(&dsconfig.replacen("[datasource]\n",
+                                               &format!("[[datasource]]\nname = \"{}\"\n", name),
+                                               1));
+        }
+        config
+    }
+}
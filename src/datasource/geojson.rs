@@ -0,0 +1,751 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use datasource::DatasourceInput;
+use core::feature::{Feature, FeatureAttr, FeatureAttrValType, FeatureStruct, json_attr_value};
+use core::clip::{clip_geometry, geometry_extent, intersects};
+use core::geom::*;
+use core::grid::Extent;
+use core::grid::Grid;
+use core::layer::Layer;
+use core::Config;
+use serde_json;
+use toml;
+use std::collections::{BTreeMap, BTreeSet};
+use std::f64::consts::PI;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+
+/// Feature of a GeoJSON FeatureCollection
+pub struct JsonFeature {
+    pub fid: Option<u64>,
+    pub attributes: Vec<FeatureAttr>,
+    pub geometry: GeometryType,
+}
+
+impl Feature for JsonFeature {
+    fn fid(&self) -> Option<u64> {
+        self.fid
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        self.attributes.clone()
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        Ok(self.geometry.clone())
+    }
+}
+
+fn json_point(val: &serde_json::Value) -> Result<Point, String> {
+    let coords = try!(val.as_array().ok_or("Invalid GeoJSON position".to_string()));
+    match (coords.get(0).and_then(|v| v.as_f64()), coords.get(1).and_then(|v| v.as_f64())) {
+        (Some(x), Some(y)) => Ok(Point::new(x, y, None)),
+        _ => Err("Invalid GeoJSON position".to_string()),
+    }
+}
+
+fn json_points(val: &serde_json::Value) -> Result<Vec<Point>, String> {
+    let positions = try!(val.as_array().ok_or("Invalid GeoJSON coordinates".to_string()));
+    positions.iter().map(json_point).collect()
+}
+
+fn json_lines(val: &serde_json::Value) -> Result<Vec<LineString>, String> {
+    let lines = try!(val.as_array().ok_or("Invalid GeoJSON coordinates".to_string()));
+    lines
+        .iter()
+        .map(|line| {
+                 json_points(line).map(|points| {
+                                           LineString {
+                                               points: points,
+                                               srid: None,
+                                           }
+                                       })
+             })
+        .collect()
+}
+
+fn json_polygon(val: &serde_json::Value) -> Result<Polygon, String> {
+    json_lines(val).map(|rings| {
+                            Polygon {
+                                rings: rings,
+                                srid: None,
+                            }
+                        })
+}
+
+/// Read GeoJSON geometry object
+pub fn geometry_from_json(geom: &serde_json::Value) -> Result<GeometryType, String> {
+    let geomtype = try!(geom.get("type")
+                            .and_then(|t| t.as_str())
+                            .ok_or("Missing geometry type".to_string()));
+    let coords = try!(geom.get("coordinates")
+                          .ok_or(format!("Missing coordinates of {}", geomtype)));
+    match geomtype {
+        "Point" => json_point(coords).map(|p| GeometryType::Point(p)),
+        "LineString" => {
+            json_points(coords).map(|points| {
+                                        GeometryType::LineString(LineString {
+                                                                     points: points,
+                                                                     srid: None,
+                                                                 })
+                                    })
+        }
+        "Polygon" => json_polygon(coords).map(|p| GeometryType::Polygon(p)),
+        "MultiPoint" => {
+            json_points(coords).map(|points| {
+                                        GeometryType::MultiPoint(MultiPoint {
+                                                                     points: points,
+                                                                     srid: None,
+                                                                 })
+                                    })
+        }
+        "MultiLineString" => {
+            json_lines(coords).map(|lines| {
+                                       GeometryType::MultiLineString(MultiLineString {
+                                                                         lines: lines,
+                                                                         srid: None,
+                                                                     })
+                                   })
+        }
+        "MultiPolygon" => {
+            let polygons = try!(coords
+                                    .as_array()
+                                    .ok_or("Invalid GeoJSON coordinates".to_string()));
+            polygons
+                .iter()
+                .map(json_polygon)
+                .collect::<Result<Vec<_>, _>>()
+                .map(|polygons| {
+                         GeometryType::MultiPolygon(MultiPolygon {
+                                                        polygons: polygons,
+                                                        srid: None,
+                                                    })
+                     })
+        }
+        _ => Err(format!("Unsupported geometry type {}", geomtype)),
+    }
+}
+
+fn attr_value(val: &serde_json::Value) -> Option<FeatureAttrValType> {
+    match val {
+        &serde_json::Value::Null => None,
+        &serde_json::Value::Bool(v) => Some(FeatureAttrValType::Bool(v)),
+        &serde_json::Value::String(ref v) => Some(FeatureAttrValType::String(v.clone())),
+        &serde_json::Value::Number(ref v) => {
+            v.as_i64()
+                .map(|v| FeatureAttrValType::Int(v))
+                .or(v.as_f64().map(|v| FeatureAttrValType::Double(v)))
+        }
+        // Arrays and objects
+        _ => Some(FeatureAttrValType::String(val.to_string())),
+    }
+}
+
+/// Read features of a GeoJSON FeatureCollection. Features with unsupported geometries are skipped.
+pub fn parse_feature_collection(json: &str) -> Result<Vec<JsonFeature>, String> {
+    let collection: serde_json::Value = try!(serde_json::from_str(json)
+                                                 .map_err(|e| format!("{}", e)));
+    if collection.get("type").and_then(|t| t.as_str()) != Some("FeatureCollection") {
+        return Err("GeoJSON FeatureCollection expected".to_string());
+    }
+    let features = try!(collection
+                            .get("features")
+                            .and_then(|f| f.as_array())
+                            .ok_or("Missing features array".to_string()));
+    let mut result = Vec::new();
+    for feature in features {
+        let geometry = match feature.get("geometry") {
+            Some(geom) if !geom.is_null() => {
+                match geometry_from_json(geom) {
+                    Ok(geometry) => geometry,
+                    Err(err) => {
+                        warn!("Skipping GeoJSON feature: {}", err);
+                        continue;
+                    }
+                }
+            }
+            _ => continue,
+        };
+        let attributes = feature
+            .get("properties")
+            .and_then(|props| props.as_object())
+            .map_or(Vec::new(), |props| {
+                props
+                    .iter()
+                    .filter_map(|(key, val)| {
+                                    attr_value(val).map(|value| {
+                                                            FeatureAttr {
+                                                                key: key.clone(),
+                                                                value: value,
+                                                            }
+                                                        })
+                                })
+                    .collect()
+            });
+        result.push(JsonFeature {
+                        fid: feature.get("id").and_then(|id| id.as_u64()),
+                        attributes: attributes,
+                        geometry: geometry,
+                    });
+    }
+    Ok(result)
+}
+
+fn map_line(line: &LineString, f: &Fn(&Point) -> Point) -> LineString {
+    LineString {
+        points: line.points.iter().map(|p| f(p)).collect(),
+        srid: None,
+    }
+}
+
+fn map_polygon(polygon: &Polygon, f: &Fn(&Point) -> Point) -> Polygon {
+    Polygon {
+        rings: polygon.rings.iter().map(|ring| map_line(ring, f)).collect(),
+        srid: None,
+    }
+}
+
+/// New geometry with transformed coordinates
+fn map_points(geom: &GeometryType, f: &Fn(&Point) -> Point) -> GeometryType {
+    match geom {
+        &GeometryType::Point(ref p) => GeometryType::Point(f(p)),
+        &GeometryType::LineString(ref l) => GeometryType::LineString(map_line(l, f)),
+        &GeometryType::Polygon(ref p) => GeometryType::Polygon(map_polygon(p, f)),
+        &GeometryType::MultiPoint(ref mp) => {
+            GeometryType::MultiPoint(MultiPoint {
+                                         points: mp.points.iter().map(|p| f(p)).collect(),
+                                         srid: None,
+                                     })
+        }
+        &GeometryType::MultiLineString(ref ml) => {
+            GeometryType::MultiLineString(MultiLineString {
+                                              lines: ml.lines
+                                                  .iter()
+                                                  .map(|l| map_line(l, f))
+                                                  .collect(),
+                                              srid: None,
+                                          })
+        }
+        &GeometryType::MultiPolygon(ref mp) => {
+            GeometryType::MultiPolygon(MultiPolygon {
+                                           polygons: mp.polygons
+                                               .iter()
+                                               .map(|p| map_polygon(p, f))
+                                               .collect(),
+                                           srid: None,
+                                       })
+        }
+        &GeometryType::GeometryCollection(_) => panic!("GeometryCollection not supported"),
+    }
+}
+
+/// Like `map_points`, but returns None for geometry collections
+pub fn map_points_checked(geom: &GeometryType, f: &Fn(&Point) -> Point) -> Option<GeometryType> {
+    match geom {
+        &GeometryType::GeometryCollection(_) => None,
+        _ => Some(map_points(geom, f)),
+    }
+}
+
+fn extent_union(a: &Extent, b: &Extent) -> Extent {
+    Extent {
+        minx: a.minx.min(b.minx),
+        miny: a.miny.min(b.miny),
+        maxx: a.maxx.max(b.maxx),
+        maxy: a.maxy.max(b.maxy),
+    }
+}
+
+fn web_mercator(p: &Point) -> Point {
+    const R: f64 = 6378137.0;
+    let lat = p.y.max(-85.0511287798).min(85.0511287798);
+    Point::new(p.x * PI / 180.0 * R,
+               (PI / 4.0 + lat * PI / 360.0).tan().ln() * R,
+               None)
+}
+
+fn wgs84(p: &Point) -> Point {
+    Point::new(p.x, p.y, None)
+}
+
+fn web_mercator_inverse(p: &Point) -> Point {
+    const R: f64 = 6378137.0;
+    Point::new(p.x / R * 180.0 / PI,
+               (2.0 * (p.y / R).exp().atan() - PI / 2.0) * 180.0 / PI,
+               None)
+}
+
+/// Transformation from WGS84 into grid SRS
+pub fn wgs84_transformation(srid: i32) -> Result<fn(&Point) -> Point, String> {
+    match srid {
+        4326 => Ok(wgs84),
+        3857 | 900913 => Ok(web_mercator),
+        _ => Err(format!("Reprojection from WGS84 to SRID {} is not supported", srid)),
+    }
+}
+
+/// Transformation from grid SRS into WGS84
+pub fn wgs84_inverse_transformation(srid: i32) -> Result<fn(&Point) -> Point, String> {
+    match srid {
+        4326 => Ok(wgs84),
+        3857 | 900913 => Ok(web_mercator_inverse),
+        _ => Err(format!("Reprojection from SRID {} to WGS84 is not supported", srid)),
+    }
+}
+
+/// Transformation between WGS84 and Web Mercator
+pub fn srid_transformation(from: i32, to: i32) -> Result<fn(&Point) -> Point, String> {
+    if from == 4326 {
+        wgs84_transformation(to)
+    } else if to == 4326 {
+        wgs84_inverse_transformation(from)
+    } else {
+        Err(format!("Reprojection from SRID {} to SRID {} is not supported", from, to))
+    }
+}
+
+fn json_position(p: &Point) -> serde_json::Value {
+    json!([p.x, p.y])
+}
+
+fn json_positions(points: &[Point]) -> serde_json::Value {
+    serde_json::Value::Array(points.iter().map(json_position).collect())
+}
+
+fn json_polygon_coords(polygon: &Polygon) -> serde_json::Value {
+    serde_json::Value::Array(polygon.rings.iter().map(|ring| json_positions(&ring.points)).collect())
+}
+
+/// GeoJSON geometry object. Geometry collections are not supported and returned as null.
+pub fn geometry_to_json(geom: &GeometryType, transform: &Fn(&Point) -> Point) -> serde_json::Value {
+    let geom = map_points_checked(geom, transform);
+    match geom {
+        Some(GeometryType::Point(ref p)) => {
+            json!({"type": "Point", "coordinates": json_position(p)})
+        }
+        Some(GeometryType::LineString(ref l)) => {
+            json!({"type": "LineString", "coordinates": json_positions(&l.points)})
+        }
+        Some(GeometryType::Polygon(ref p)) => {
+            json!({"type": "Polygon", "coordinates": json_polygon_coords(p)})
+        }
+        Some(GeometryType::MultiPoint(ref mp)) => {
+            json!({"type": "MultiPoint", "coordinates": json_positions(&mp.points)})
+        }
+        Some(GeometryType::MultiLineString(ref ml)) => {
+            let lines: Vec<serde_json::Value> =
+                ml.lines.iter().map(|l| json_positions(&l.points)).collect();
+            json!({"type": "MultiLineString", "coordinates": lines})
+        }
+        Some(GeometryType::MultiPolygon(ref mp)) => {
+            let polygons: Vec<serde_json::Value> =
+                mp.polygons.iter().map(json_polygon_coords).collect();
+            json!({"type": "MultiPolygon", "coordinates": polygons})
+        }
+        _ => serde_json::Value::Null,
+    }
+}
+
+/// GeoJSON Feature with properties of `feature`, layer name as foreign member
+/// and a processed geometry (e.g. clipped)
+pub fn feature_geometry_to_json(layer_name: &str,
+                                feature: &Feature,
+                                geometry: &GeometryType,
+                                transform: &Fn(&Point) -> Point)
+                                -> Option<serde_json::Value> {
+    if geometry.is_empty() {
+        return None;
+    }
+    let mut properties = serde_json::Map::new();
+    for attr in feature.attributes() {
+        properties.insert(attr.key, json_attr_value(&attr.value));
+    }
+    let mut json = json!({
+        "type": "Feature",
+        "layer": layer_name,
+        "properties": properties,
+        "geometry": geometry_to_json(geometry, transform)
+    });
+    if let Some(fid) = feature.fid() {
+        json["id"] = json!(fid);
+    }
+    Some(json)
+}
+
+/// Uniform grid index of feature bounding boxes
+pub struct GridIndex {
+    extent: Extent,
+    /// Number of cells per axis
+    size: usize,
+    /// Feature indices of each cell
+    cells: Vec<Vec<usize>>,
+}
+
+impl GridIndex {
+    pub fn new(bboxes: &[Extent]) -> GridIndex {
+        let extent = bboxes
+            .iter()
+            .fold(None, |ext: Option<Extent>, bbox| {
+                Some(ext.map_or(bbox.clone(), |ext| extent_union(&ext, bbox)))
+            })
+            .unwrap_or(Extent {
+                           minx: 0.0,
+                           miny: 0.0,
+                           maxx: 0.0,
+                           maxy: 0.0,
+                       });
+        // About 4 features per cell
+        let size = ((bboxes.len() as f64 / 4.0).sqrt().ceil() as usize).max(1).min(256);
+        let mut index = GridIndex {
+            extent: extent,
+            size: size,
+            cells: vec![Vec::new(); size * size],
+        };
+        for (i, bbox) in bboxes.iter().enumerate() {
+            for cell in index.cells_of(bbox) {
+                index.cells[cell].push(i);
+            }
+        }
+        index
+    }
+    fn cell_col(&self, x: f64) -> usize {
+        let width = (self.extent.maxx - self.extent.minx) / self.size as f64;
+        if width <= 0.0 {
+            return 0;
+        }
+        (((x - self.extent.minx) / width).floor().max(0.0) as usize).min(self.size - 1)
+    }
+    fn cell_row(&self, y: f64) -> usize {
+        let height = (self.extent.maxy - self.extent.miny) / self.size as f64;
+        if height <= 0.0 {
+            return 0;
+        }
+        (((y - self.extent.miny) / height).floor().max(0.0) as usize).min(self.size - 1)
+    }
+    fn cells_of(&self, bbox: &Extent) -> Vec<usize> {
+        if !intersects(&self.extent, bbox) {
+            return Vec::new();
+        }
+        let mut cells = Vec::new();
+        for row in self.cell_row(bbox.miny)..self.cell_row(bbox.maxy) + 1 {
+            for col in self.cell_col(bbox.minx)..self.cell_col(bbox.maxx) + 1 {
+                cells.push(row * self.size + col);
+            }
+        }
+        cells
+    }
+    /// Indices of features with a bounding box in cells intersecting `bbox`
+    pub fn query(&self, bbox: &Extent) -> Vec<usize> {
+        let mut result: Vec<usize> = self.cells_of(bbox)
+            .into_iter()
+            .flat_map(|cell| self.cells[cell].iter().cloned())
+            .collect();
+        result.sort();
+        result.dedup();
+        result
+    }
+}
+
+/// Features of a layer in grid SRS with spatial index
+struct IndexedLayer {
+    features: Vec<JsonFeature>,
+    bboxes: Vec<Extent>,
+    index: GridIndex,
+}
+
+/// GeoJSON file datasource. Each file is a FeatureCollection in WGS84 and
+/// is served as layer named like the file without extension.
+pub struct GeojsonInput {
+    pub paths: Vec<String>,
+    /// Feature collections by name
+    collections: BTreeMap<String, Vec<JsonFeature>>,
+    /// Prepared layers by layer name
+    layers: BTreeMap<String, IndexedLayer>,
+}
+
+/// Collection name of GeoJSON file
+pub fn collection_name(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map_or(path.to_string(), |stem| stem.to_string_lossy().to_string())
+}
+
+impl GeojsonInput {
+    pub fn new(paths: &[&str]) -> GeojsonInput {
+        GeojsonInput {
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            collections: BTreeMap::new(),
+            layers: BTreeMap::new(),
+        }
+    }
+    /// New instance with all files loaded
+    pub fn connected(&self) -> GeojsonInput {
+        self.try_connected().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_connected(&self) -> Result<GeojsonInput, String> {
+        let mut collections = BTreeMap::new();
+        for path in &self.paths {
+            let mut json = String::new();
+            let read = File::open(path).and_then(|mut f| f.read_to_string(&mut json));
+            if let Err(e) = read {
+                return Err(format!("Error reading GeoJSON file '{}': {}", path, e));
+            }
+            let features = try!(parse_feature_collection(&json)
+                                    .map_err(|e| format!("Error reading GeoJSON file '{}': {}", path, e)));
+            info!("Loaded {} features from '{}'", features.len(), path);
+            collections.insert(collection_name(path), features);
+        }
+        Ok(GeojsonInput {
+               paths: self.paths.clone(),
+               collections: collections,
+               layers: BTreeMap::new(),
+           })
+    }
+    /// Add feature collection. Used for testing.
+    #[cfg(test)]
+    pub fn add_collection(&mut self, name: &str, features: Vec<JsonFeature>) {
+        self.collections.insert(name.to_string(), features);
+    }
+    /// Features are kept in memory
+    pub fn ping(&self) -> Result<(), String> {
+        Ok(())
+    }
+    pub fn mvt_supported(&self) -> bool {
+        false
+    }
+    pub fn from_datasource_config(dscfg: &toml::Value,
+                                  _config: &toml::Value)
+                                  -> Result<GeojsonInput, String> {
+        match dscfg.get("path") {
+            Some(&toml::Value::String(ref path)) => Ok(GeojsonInput::new(&[path.as_str()])),
+            Some(&toml::Value::Array(ref paths)) => {
+                let paths = try!(paths
+                                     .iter()
+                                     .map(|p| p.as_str().ok_or("path entry is not a string".to_string()))
+                                     .collect::<Result<Vec<&str>, _>>());
+                Ok(GeojsonInput::new(&paths))
+            }
+            Some(_) => Err("path entry is not a string".to_string()),
+            None => Err("Missing configuration entry 'datasource.path'".to_string()),
+        }
+    }
+    fn collection(&self, layer: &Layer) -> Option<&Vec<JsonFeature>> {
+        self.collections
+            .get(layer.table_name())
+    }
+    /// Check that the feature collection of a layer exists
+    pub fn check_layer(&self, layer: &Layer, _grid: &Grid) -> Result<(), String> {
+        match self.collection(layer) {
+            Some(_) => Ok(()),
+            None => {
+                Err(format!("Feature collection '{}' not found",
+                            layer.table_name()))
+            }
+        }
+    }
+    pub fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        let mut layers = Vec::new();
+        // Reverse order like PostGIS detection
+        for (name, features) in self.collections.iter().rev() {
+            let mut layer = Layer::new(name);
+            layer.table_name = Some(name.clone());
+            let types: BTreeSet<&str> = features
+                .iter()
+                .map(|f| f.geometry.type_name())
+                .collect();
+            layer.geometry_type = if types.len() == 1 {
+                types.iter().next().map(|t| t.to_string())
+            } else {
+                Some("GEOMETRY".to_string())
+            };
+            layer.srid = Some(4326);
+            layers.push(layer);
+        }
+        layers
+    }
+    // Return property names of all features
+    pub fn detect_data_columns(&self,
+                               layer: &Layer,
+                               _sql: Option<&String>)
+                               -> Vec<(String, String)> {
+        let mut cols = BTreeSet::new();
+        if let Some(features) = self.collection(layer) {
+            for feature in features {
+                for attr in &feature.attributes {
+                    cols.insert(attr.key.clone());
+                }
+            }
+        }
+        cols.into_iter().map(|col| (col, "".to_string())).collect()
+    }
+    /// Attribute fields with types "String", "Number" or "Boolean" from first occurence
+    pub fn detect_field_types(&self,
+                              layer: &Layer,
+                              _sql: Option<&String>)
+                              -> Result<Vec<(String, String)>, String> {
+        let features = try!(self.collection(layer)
+                                .ok_or(format!("Feature collection '{}' not found",
+                                               layer.table_name())));
+        let mut fields = BTreeMap::new();
+        for feature in features {
+            for attr in &feature.attributes {
+                let field_type = match attr.value {
+                    FeatureAttrValType::String(_) => "String",
+                    FeatureAttrValType::Bool(_) => "Boolean",
+                    _ => "Number",
+                };
+                fields
+                    .entry(attr.key.clone())
+                    .or_insert(field_type.to_string());
+            }
+        }
+        Ok(fields.into_iter().collect())
+    }
+    /// Reproject features into grid SRS and build spatial index
+    pub fn prepare_queries(&mut self, layer: &Layer, grid: &Grid) {
+        let transform = match wgs84_transformation(grid.srid) {
+            Ok(transform) => transform,
+            Err(err) => {
+                error!("Layer '{}': {}", layer.name, err);
+                return;
+            }
+        };
+        let mut features = Vec::new();
+        let mut bboxes = Vec::new();
+        match self.collection(layer) {
+            Some(collection) => {
+                for feature in collection {
+                    let geometry = map_points(&feature.geometry, &transform);
+                    if let Some(bbox) = geometry_extent(&geometry) {
+                        bboxes.push(bbox);
+                        features.push(JsonFeature {
+                                          fid: feature.fid,
+                                          attributes: feature.attributes.clone(),
+                                          geometry: geometry,
+                                      });
+                    }
+                }
+            }
+            None => {
+                error!("Layer '{}': GeoJSON collection '{}' not found",
+                       layer.name,
+                       layer.table_name());
+                return;
+            }
+        }
+        let index = GridIndex::new(&bboxes);
+        self.layers.insert(layer.name.clone(),
+                           IndexedLayer {
+                               features: features,
+                               bboxes: bboxes,
+                               index: index,
+                           });
+    }
+    pub fn retrieve_mvt_layer(&self,
+                              _layer: &Layer,
+                              _extent: &Extent,
+                              _zoom: u8,
+                              _grid: &Grid)
+                              -> Option<Vec<u8>> {
+        None
+    }
+    /// WGS84 extent of layer data
+    pub fn retrieve_extent(&self, layer: &Layer, _grid: &Grid) -> Option<Extent> {
+        self.collection(layer).and_then(|features| {
+            features
+                .iter()
+                .filter_map(|f| geometry_extent(&f.geometry))
+                .fold(None, |ext: Option<Extent>, bbox| {
+                    Some(ext.map_or(bbox.clone(), |ext| extent_union(&ext, &bbox)))
+                })
+        })
+    }
+}
+
+impl DatasourceInput for GeojsonInput {
+    fn retrieve_features<F>(&self,
+                            layer: &Layer,
+                            extent: &Extent,
+                            zoom: u8,
+                            grid: &Grid,
+                            mut read: F)
+        where F: FnMut(&Feature)
+    {
+        let indexed = match self.layers.get(&layer.name) {
+            Some(indexed) => indexed,
+            None => return,
+        };
+        let buffer = grid.pixel_width(zoom) * layer.buffer_size(zoom) as f64;
+        let clip_extent = Extent {
+            minx: extent.minx - buffer,
+            miny: extent.miny - buffer,
+            maxx: extent.maxx + buffer,
+            maxy: extent.maxy + buffer,
+        };
+        let limit = layer.query_limit(zoom);
+        let mut count = 0;
+        for i in indexed.index.query(&clip_extent) {
+            if !intersects(&clip_extent, &indexed.bboxes[i]) {
+                continue;
+            }
+            if limit.map_or(false, |limit| count >= limit) {
+                break;
+            }
+            let feature = &indexed.features[i];
+            if let Some(geometry) = clip_geometry(&feature.geometry, &clip_extent) {
+                let fid = feature.fid.or(layer.fid_field.as_ref().and_then(|fid_field| {
+                    feature.attributes
+                        .iter()
+                        .find(|attr| &attr.key == fid_field)
+                        .and_then(|attr| match attr.value {
+                                      FeatureAttrValType::Int(fid) if fid >= 0 => Some(fid as u64),
+                                      _ => None,
+                                  })
+                }));
+                let clipped = FeatureStruct {
+                    fid: fid,
+                    attributes: feature
+                        .attributes
+                        .iter()
+                        .filter(|attr| layer.field_exposed(&attr.key))
+                        .cloned()
+                        .collect(),
+                    geometry: geometry,
+                };
+                read(&clipped);
+                count += 1;
+            }
+        }
+    }
+}
+
+impl Config<GeojsonInput> for GeojsonInput {
+    fn from_config(config: &toml::Value) -> Result<Self, String> {
+        let dscfg = try!(config
+                             .get("datasource")
+                             .ok_or("Missing configuration entry 'datasource.path'".to_string()));
+        GeojsonInput::from_datasource_config(dscfg, config)
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[datasource]
+type = "geojson"
+# GeoJSON files in WGS84. Each file is a layer named like the file.
+path = ["boundaries.geojson"]
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        let paths: Vec<String> = self.paths.iter().map(|p| format!("\"{}\"", p)).collect();
+        format!(r#"
+[datasource]
+type = "geojson"
+path = [{}]
+"#,
+                paths.join(", "))
+    }
+}
@@ -0,0 +1,166 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use datasource::DatasourceInput;
+use datasource::geojson::{GeojsonInput, GridIndex, parse_feature_collection,
+                          wgs84_transformation, wgs84_inverse_transformation, geometry_to_json,
+                          feature_geometry_to_json, collection_name};
+use core::feature::FeatureAttrValType;
+use core::geom::*;
+use core::grid::Extent;
+use core::grid::Grid;
+use core::layer::Layer;
+
+
+const CITIES: &'static str = r#"{
+  "type": "FeatureCollection",
+  "features": [
+    {"type": "Feature", "id": 1, "properties": {"name": "Bern", "pop": 121631, "capital": true},
+     "geometry": {"type": "Point", "coordinates": [7.4474, 46.948]}},
+    {"type": "Feature", "properties": {"name": "Zurich", "pop": 396955, "area": 87.88, "tags": ["a"]},
+     "geometry": {"type": "Point", "coordinates": [8.5417, 47.3769]}},
+    {"type": "Feature", "properties": {"name": "Lake"},
+     "geometry": {"type": "Polygon", "coordinates": [[[6.1, 46.2], [6.9, 46.2], [6.9, 46.5], [6.1, 46.5], [6.1, 46.2]]]}},
+    {"type": "Feature", "properties": {"name": "Null"}, "geometry": null},
+    {"type": "Feature", "properties": {"name": "Collection"},
+     "geometry": {"type": "GeometryCollection", "geometries": []}}
+  ]
+}"#;
+
+fn extent(minx: f64, miny: f64, maxx: f64, maxy: f64) -> Extent {
+    Extent {
+        minx: minx,
+        miny: miny,
+        maxx: maxx,
+        maxy: maxy,
+    }
+}
+
+#[test]
+fn test_parse_feature_collection() {
+    let features = parse_feature_collection(CITIES).unwrap();
+    assert_eq!(features.len(), 3);
+    assert_eq!(features[0].fid, Some(1));
+    assert_eq!(&*format!("{:?}", features[0].geometry),
+               "Point(Point { x: 7.4474, y: 46.948, srid: None })");
+    let attrs: Vec<String> = features[1]
+        .attributes
+        .iter()
+        .map(|attr| format!("{}={:?}", attr.key, attr.value))
+        .collect();
+    assert_eq!(attrs,
+               vec!["area=Double(87.88)",
+                    "name=String(\"Zurich\")",
+                    "pop=Int(396955)",
+                    "tags=String(\"[\\\"a\\\"]\")"]);
+    assert_eq!(features[0].attributes[0].value,
+               FeatureAttrValType::Bool(true));
+
+    assert_eq!(parse_feature_collection(r#"{"type": "Feature"}"#)
+                   .err()
+                   .unwrap(),
+               "GeoJSON FeatureCollection expected");
+    assert!(parse_feature_collection("{").is_err());
+}
+
+#[test]
+fn test_grid_index() {
+    // 4x4 boxes indexed in 2x2 cells
+    let bboxes: Vec<Extent> = (0..16)
+        .map(|i| {
+                 let (x, y) = ((i % 4) as f64 * 2.5, (i / 4) as f64 * 2.5);
+                 extent(x, y, x + 1.0, y + 1.0)
+             })
+        .collect();
+    let index = GridIndex::new(&bboxes);
+    assert_eq!(index.query(&extent(0.0, 0.0, 1.0, 1.0)), vec![0, 1, 4, 5]);
+    assert_eq!(index.query(&extent(-1.0, -1.0, 11.0, 11.0)).len(), 16);
+    assert_eq!(index.query(&extent(20.0, 20.0, 30.0, 30.0)), Vec::<usize>::new());
+}
+
+#[test]
+fn test_transformation() {
+    let transform = wgs84_transformation(3857).unwrap();
+    let p = transform(&Point::new(7.4474, 46.948, None));
+    assert_eq!((p.x.round(), p.y.round()), (829041.0, 5933590.0));
+    assert_eq!(wgs84_transformation(2056).err().unwrap(),
+               "Reprojection from WGS84 to SRID 2056 is not supported");
+    assert_eq!(collection_name("data/cities.geojson"), "cities");
+
+    let inverse = wgs84_inverse_transformation(3857).unwrap();
+    let p = inverse(&transform(&Point::new(7.4474, 46.948, None)));
+    assert!((p.x - 7.4474).abs() < 1e-9 && (p.y - 46.948).abs() < 1e-9);
+}
+
+#[test]
+fn test_geojson_output() {
+    let features = parse_feature_collection(CITIES).unwrap();
+    let transform = wgs84_transformation(4326).unwrap();
+    assert_eq!(geometry_to_json(&features[2].geometry, &transform),
+               json!({"type": "Polygon",
+                      "coordinates": [[[6.1, 46.2], [6.9, 46.2], [6.9, 46.5], [6.1, 46.5], [6.1, 46.2]]]}));
+    let json = feature_geometry_to_json("cities", &features[0], &features[0].geometry, &transform)
+        .unwrap();
+    assert_eq!(json,
+               json!({"type": "Feature",
+                      "id": 1,
+                      "layer": "cities",
+                      "properties": {"name": "Bern", "pop": 121631, "capital": true},
+                      "geometry": {"type": "Point", "coordinates": [7.4474, 46.948]}}));
+}
+
+#[test]
+fn test_retrieve_features() {
+    let mut input = GeojsonInput::new(&[]);
+    input.add_collection("cities", parse_feature_collection(CITIES).unwrap());
+
+    let layers = input.detect_layers(false);
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].name, "cities");
+    assert_eq!(layers[0].geometry_type, Some("GEOMETRY".to_string()));
+    let cols: Vec<String> = input
+        .detect_data_columns(&layers[0], None)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(cols, vec!["area", "capital", "name", "pop", "tags"]);
+    let fields = input.detect_field_types(&layers[0], None).unwrap();
+    assert_eq!(fields,
+               vec![("area".to_string(), "Number".to_string()),
+                    ("capital".to_string(), "Boolean".to_string()),
+                    ("name".to_string(), "String".to_string()),
+                    ("pop".to_string(), "Number".to_string()),
+                    ("tags".to_string(), "String".to_string())]);
+    assert_eq!(format!("{:?}", input.retrieve_extent(&layers[0], &Grid::web_mercator())),
+               "Some(Extent { minx: 6.1, miny: 46.2, maxx: 8.5417, maxy: 47.3769 })");
+
+    let grid = Grid::web_mercator();
+    let mut layer = Layer::new("cities");
+    layer.table_name = Some("cities".to_string());
+    input.prepare_queries(&layer, &grid);
+    // Tile 8/133/90 (containing Bern)
+    let bern = grid.tile_extent_xyz(133, 90, 8);
+    let mut names = Vec::new();
+    input.retrieve_features(&layer, &bern, 8, &grid, |feat| {
+        for attr in feat.attributes() {
+            if attr.key == "name" {
+                names.push(format!("{:?}", attr.value));
+            }
+        }
+        assert!(feat.geometry().is_ok());
+    });
+    assert_eq!(names, vec!["String(\"Bern\")"]);
+
+    // Feature limit
+    let world = extent(-20037508.34, -20037508.34, 20037508.34, 20037508.34);
+    let mut count = 0;
+    input.retrieve_features(&layer, &world, 0, &grid, |_| count += 1);
+    assert_eq!(count, 3);
+    layer.query_limit = Some(1);
+    input.prepare_queries(&layer, &grid);
+    let mut count = 0;
+    input.retrieve_features(&layer, &world, 0, &grid, |_| count += 1);
+    assert_eq!(count, 1);
+}
@@ -0,0 +1,564 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use datasource::DatasourceInput;
+use datasource::geojson::{srid_transformation, map_points_checked};
+use rusqlite::{self, Connection, OpenFlags};
+use rusqlite::types::Value;
+use postgis::ewkb::EwkbRead;
+use core::feature::{Feature, FeatureAttr, FeatureAttrValType};
+use core::geom::*;
+use core::grid::Extent;
+use core::grid::Grid;
+use core::layer::Layer;
+use core::sqlite::SqlitePool;
+use core::Config;
+use toml;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+use std::path::Path;
+
+
+/// Decode GeoPackage geometry blob (GeoPackageBinary header followed by WKB).
+/// Returns Ok(None) for empty geometries.
+pub fn decode_gpkg_geometry(blob: &[u8]) -> Result<Option<GeometryType>, String> {
+    if blob.len() < 8 || &blob[0..2] != b"GP" {
+        return Err("Invalid GeoPackage geometry header".to_string());
+    }
+    let flags = blob[3];
+    if flags & 0x10 != 0 {
+        return Ok(None);
+    }
+    let envelope_len = match (flags >> 1) & 0x07 {
+        0 => 0,
+        1 => 32,
+        2 | 3 => 48,
+        4 => 64,
+        code => return Err(format!("Invalid envelope contents indicator {}", code)),
+    };
+    if blob.len() < 8 + envelope_len {
+        return Err("Invalid GeoPackage geometry header".to_string());
+    }
+    decode_wkb(&blob[8 + envelope_len..]).map(|geom| Some(geom))
+}
+
+/// Decode 2D WKB geometry
+pub fn decode_wkb(wkb: &[u8]) -> Result<GeometryType, String> {
+    if wkb.len() < 5 {
+        return Err("Invalid WKB geometry".to_string());
+    }
+    let type_id = if wkb[0] == 0 {
+        (wkb[1] as u32) << 24 | (wkb[2] as u32) << 16 | (wkb[3] as u32) << 8 | wkb[4] as u32
+    } else {
+        (wkb[4] as u32) << 24 | (wkb[3] as u32) << 16 | (wkb[2] as u32) << 8 | wkb[1] as u32
+    };
+    let mut raw = wkb;
+    let geom = match type_id {
+        1 => Point::read_ewkb(&mut raw).map(|g| GeometryType::Point(g)),
+        2 => LineString::read_ewkb(&mut raw).map(|g| GeometryType::LineString(g)),
+        3 => Polygon::read_ewkb(&mut raw).map(|g| GeometryType::Polygon(g)),
+        4 => MultiPoint::read_ewkb(&mut raw).map(|g| GeometryType::MultiPoint(g)),
+        5 => MultiLineString::read_ewkb(&mut raw).map(|g| GeometryType::MultiLineString(g)),
+        6 => MultiPolygon::read_ewkb(&mut raw).map(|g| GeometryType::MultiPolygon(g)),
+        7 => {
+            GeometryCollection::read_ewkb(&mut raw).map(|g| GeometryType::GeometryCollection(g))
+        }
+        _ => {
+            return Err(format!("Unsupported WKB geometry type {} (only 2D geometries are supported)",
+                               type_id))
+        }
+    };
+    geom.map_err(|err| format!("{}", err))
+}
+
+/// Name of RTree spatial index table of a geometry column
+pub fn rtree_table_name(table: &str, geometry_field: &str) -> String {
+    format!("rtree_{}_{}", table, geometry_field)
+}
+
+struct FeatureRow<'a> {
+    layer: &'a Layer,
+    columns: &'a [String],
+    values: Vec<Value>,
+    geom: Vec<u8>,
+    /// Transformation from layer SRID into grid SRID
+    transform: Option<fn(&Point) -> Point>,
+}
+
+/// Bounding box of transformed extent corners
+fn transform_extent(extent: &Extent, transform: fn(&Point) -> Point) -> Extent {
+    let min = transform(&Point::new(extent.minx, extent.miny, None));
+    let max = transform(&Point::new(extent.maxx, extent.maxy, None));
+    Extent {
+        minx: min.x,
+        miny: min.y,
+        maxx: max.x,
+        maxy: max.y,
+    }
+}
+
+impl<'a> Feature for FeatureRow<'a> {
+    fn fid(&self) -> Option<u64> {
+        self.layer
+            .fid_field
+            .as_ref()
+            .and_then(|fid_field| {
+                self.columns
+                    .iter()
+                    .position(|col| col == fid_field)
+                    .and_then(|idx| match self.values[idx] {
+                                  Value::Integer(fid) if fid >= 0 => Some(fid as u64),
+                                  _ => None,
+                              })
+            })
+    }
+    fn attributes(&self) -> Vec<FeatureAttr> {
+        let mut attrs = Vec::new();
+        for (col, val) in self.columns.iter().zip(self.values.iter()) {
+            if !self.layer.field_exposed(col) {
+                continue;
+            }
+            let value = match val {
+                &Value::Integer(v) => FeatureAttrValType::Int(v),
+                &Value::Real(v) => FeatureAttrValType::Double(v),
+                &Value::Text(ref v) => FeatureAttrValType::String(v.clone()),
+                // Skip NULL values and binary data
+                _ => continue,
+            };
+            attrs.push(FeatureAttr {
+                           key: col.clone(),
+                           value: value,
+                       });
+        }
+        attrs
+    }
+    fn geometry(&self) -> Result<GeometryType, String> {
+        match decode_gpkg_geometry(&self.geom) {
+            Ok(Some(geom)) => {
+                match self.transform {
+                    Some(transform) => {
+                        map_points_checked(&geom, &transform)
+                            .ok_or("Reprojection of geometry collections is not supported"
+                                       .to_string())
+                    }
+                    None => Ok(geom),
+                }
+            }
+            Ok(None) => Err("Empty geometry".to_string()),
+            Err(err) => {
+                error!("Layer '{}': {}", self.layer.name, err);
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Feature query of a GeoPackage layer
+struct GpkgQuery {
+    sql: String,
+    /// Query is filtered with the RTree index and takes bbox parameters
+    spatial_index: bool,
+}
+
+/// GeoPackage (SQLite) datasource
+pub struct GpkgInput {
+    pub path: String,
+    conn: Option<SqlitePool>,
+    queries: BTreeMap<String, GpkgQuery>,
+    // Layers with reported errors
+    layer_warnings: Mutex<HashSet<String>>,
+}
+
+impl GpkgInput {
+    pub fn new(path: &str) -> GpkgInput {
+        GpkgInput {
+            path: path.to_string(),
+            conn: None,
+            queries: BTreeMap::new(),
+            layer_warnings: Mutex::new(HashSet::new()),
+        }
+    }
+    pub fn connected(&self) -> GpkgInput {
+        self.try_connected().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn try_connected(&self) -> Result<GpkgInput, String> {
+        // Connection::open would create a new empty database
+        if !Path::new(&self.path).exists() {
+            return Err(format!("GeoPackage '{}' not found", self.path));
+        }
+        let pool = try!(SqlitePool::open(&self.path, OpenFlags::SQLITE_OPEN_READ_ONLY, "")
+                            .map_err(|e| format!("Error opening GeoPackage '{}': {}", self.path, e)));
+        Ok(GpkgInput {
+               path: self.path.clone(),
+               conn: Some(pool),
+               queries: BTreeMap::new(),
+               layer_warnings: Mutex::new(HashSet::new()),
+           })
+    }
+    /// Check that the GeoPackage is readable
+    pub fn ping(&self) -> Result<(), String> {
+        if self.conn.is_none() {
+            return Err(format!("GeoPackage '{}' not connected", self.path));
+        }
+        self.with_conn(|conn| conn.query_row("SELECT 1", &[], |row| row.get::<_, i64>(0)))
+            .map(|_| ())
+            .map_err(|e| format!("Error reading GeoPackage '{}': {}", self.path, e))
+    }
+    /// Database encoding with ST_AsMVT is not available
+    pub fn mvt_supported(&self) -> bool {
+        false
+    }
+    pub fn from_datasource_config(dscfg: &toml::Value,
+                                  _config: &toml::Value)
+                                  -> Result<GpkgInput, String> {
+        dscfg
+            .get("path")
+            .ok_or("Missing configuration entry 'datasource.path'".to_string())
+            .and_then(|val| {
+                          val.as_str()
+                              .ok_or("path entry is not a string".to_string())
+                      })
+            .and_then(|path| Ok(GpkgInput::new(path)))
+    }
+    fn with_conn<T, F>(&self, f: F) -> Result<T, rusqlite::Error>
+        where F: FnOnce(&Connection) -> Result<T, rusqlite::Error>
+    {
+        self.conn
+            .as_ref()
+            .expect("GeoPackage datasource not connected")
+            .with_connection(f)
+    }
+    /// Check that table and geometry column of a layer exist
+    pub fn check_layer(&self, layer: &Layer, _grid: &Grid) -> Result<(), String> {
+        let table = layer.table_name();
+        let column = layer
+            .geometry_field
+            .as_ref()
+            .map_or("*".to_string(), |field| format!("\"{}\"", field));
+        let sql = format!("SELECT {} FROM \"{}\" LIMIT 0", column, table);
+        self.with_conn(|conn| conn.prepare(&sql).map(|_| ()))
+            .map_err(|e| format!("{}", e))
+    }
+    /// SRID of the layer geometry column from `gpkg_geometry_columns`
+    pub fn detect_srid(&self, layer: &Layer) -> Option<i32> {
+        let table = layer.table_name();
+        let sql = "SELECT srs_id FROM gpkg_geometry_columns WHERE table_name = ?";
+        self.with_conn(|conn| conn.query_row(sql, &[table], |row| row.get::<_, i32>(0)))
+            .ok()
+            .and_then(|srid| if srid > 0 { Some(srid) } else { None })
+    }
+    pub fn detect_layers(&self, _detect_geometry_types: bool) -> Vec<Layer> {
+        info!("Detecting layers from gpkg_geometry_columns");
+        let sql = "SELECT g.table_name, g.column_name, g.geometry_type_name, g.srs_id \
+                   FROM gpkg_geometry_columns g \
+                   JOIN gpkg_contents c ON g.table_name = c.table_name \
+                   WHERE c.data_type = 'features' ORDER BY g.table_name DESC";
+        let layers = self.with_conn(|conn| {
+            let mut stmt = try!(conn.prepare(sql));
+            let rows = try!(stmt.query_map(&[], |row| {
+                let table_name: String = row.get(0);
+                let geometry_column: String = row.get(1);
+                let geomtype: String = row.get(2);
+                let srid: i32 = row.get(3);
+                let mut layer = Layer::new(&table_name);
+                layer.table_name = Some(table_name);
+                layer.geometry_field = Some(geometry_column);
+                layer.geometry_type = Some(geomtype.to_uppercase());
+                layer.srid = Some(srid);
+                layer
+            }));
+            rows.collect()
+        });
+        layers.unwrap_or_else(|e| {
+                                  error!("Error reading GeoPackage '{}': {}", self.path, e);
+                                  Vec::new()
+                              })
+    }
+    // Return column field names without geometry column. No type conversion needed.
+    pub fn detect_data_columns(&self,
+                               layer: &Layer,
+                               _sql: Option<&String>)
+                               -> Vec<(String, String)> {
+        let table = layer.table_name();
+        let sql = format!("PRAGMA table_info(\"{}\")", table);
+        let cols = self.with_conn(|conn| {
+            let mut stmt = try!(conn.prepare(&sql));
+            let rows = try!(stmt.query_map(&[], |row| row.get::<_, String>(1)));
+            rows.collect::<Result<Vec<String>, _>>()
+        });
+        match cols {
+            Ok(cols) => {
+                cols.into_iter()
+                    .filter(|col| Some(col) != layer.geometry_field.as_ref())
+                    .map(|col| (col, "".to_string()))
+                    .collect()
+            }
+            Err(e) => {
+                error!("Layer '{}': {}", layer.name, e);
+                vec![]
+            }
+        }
+    }
+    /// Attribute fields with types "String", "Number" or "Boolean" - without geometry column
+    pub fn detect_field_types(&self,
+                              layer: &Layer,
+                              _sql: Option<&String>)
+                              -> Result<Vec<(String, String)>, String> {
+        if self.conn.is_none() {
+            return Err("GeoPackage datasource not connected".to_string());
+        }
+        let table = layer.table_name();
+        let sql = format!("PRAGMA table_info(\"{}\")", table);
+        let cols = try!(self.with_conn(|conn| {
+                let mut stmt = try!(conn.prepare(&sql));
+                let rows = try!(stmt.query_map(&[], |row| {
+                    (row.get::<_, String>(1), row.get::<_, String>(2))
+                }));
+                rows.collect::<Result<Vec<(String, String)>, _>>()
+            })
+            .map_err(|e| format!("{}", e)));
+        Ok(cols.into_iter()
+               .filter(|&(ref col, _)| Some(col) != layer.geometry_field.as_ref())
+               .map(|(col, decltype)| {
+                        let decltype = decltype.to_uppercase();
+                        let field_type = if decltype.contains("INT") || decltype == "REAL" ||
+                                            decltype == "FLOAT" ||
+                                            decltype == "DOUBLE" {
+                            "Number"
+                        } else if decltype == "BOOLEAN" {
+                            "Boolean"
+                        } else {
+                            "String"
+                        };
+                        (col, field_type.to_string())
+                    })
+               .collect())
+    }
+    fn has_table(&self, name: &str) -> bool {
+        self.conn.is_some() &&
+        self.with_conn(|conn| {
+                           conn.query_row("SELECT count(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+                                          &[&name],
+                                          |row| row.get::<_, i64>(0))
+                       })
+            .map(|cnt| cnt > 0)
+            .unwrap_or(false)
+    }
+    /// Build feature query of a table layer
+    fn build_query(&self, layer: &Layer, spatial_index: bool) -> Option<GpkgQuery> {
+        let table = match layer.table_name {
+            Some(ref table) => table,
+            None => return None,
+        };
+        let geom = match layer.geometry_field {
+            Some(ref geom) => geom,
+            None => return None,
+        };
+        let sql = if spatial_index {
+            format!("SELECT * FROM \"{}\" WHERE rowid IN (SELECT id FROM \"{}\" WHERE minx <= ? AND maxx >= ? AND miny <= ? AND maxy >= ?) LIMIT ?",
+                    table,
+                    rtree_table_name(table, geom))
+        } else {
+            format!("SELECT * FROM \"{}\" LIMIT ?", table)
+        };
+        Some(GpkgQuery {
+                 sql: sql,
+                 spatial_index: spatial_index,
+             })
+    }
+    pub fn prepare_queries(&mut self, layer: &Layer, _grid: &Grid) {
+        if layer.query.iter().any(|q| q.sql.is_some()) || layer.point_on_surface {
+            warn!("Layer '{}': SQL queries and label points are not supported for GeoPackage datasources - layer skipped",
+                  layer.name);
+            return;
+        }
+        let spatial_index = match (layer.table_name.as_ref(), layer.geometry_field.as_ref()) {
+            (Some(table), Some(geom)) => self.has_table(&rtree_table_name(table, geom)),
+            _ => false,
+        };
+        if !spatial_index {
+            warn!("Layer '{}': No RTree spatial index found - reading all features",
+                  layer.name);
+        }
+        if let Some(query) = self.build_query(layer, spatial_index) {
+            debug!("Query for layer '{}': {}", layer.name, query.sql);
+            self.queries.insert(layer.name.clone(), query);
+        }
+    }
+    pub fn retrieve_mvt_layer(&self,
+                              _layer: &Layer,
+                              _extent: &Extent,
+                              _zoom: u8,
+                              _grid: &Grid)
+                              -> Option<Vec<u8>> {
+        None
+    }
+    /// WGS84 extent of layer data from gpkg_contents. Returns None for other SRS.
+    pub fn retrieve_extent(&self, layer: &Layer, _grid: &Grid) -> Option<Extent> {
+        let table = match layer.table_name {
+            Some(ref table) => table,
+            None => return None,
+        };
+        let ext = self.with_conn(|conn| {
+            conn.query_row("SELECT min_x, min_y, max_x, max_y, srs_id FROM gpkg_contents WHERE table_name = ?",
+                           &[table],
+                           |row| {
+                               (row.get::<_, Option<f64>>(0),
+                                row.get::<_, Option<f64>>(1),
+                                row.get::<_, Option<f64>>(2),
+                                row.get::<_, Option<f64>>(3),
+                                row.get::<_, Option<i32>>(4))
+                           })
+        });
+        match ext {
+            Ok((Some(minx), Some(miny), Some(maxx), Some(maxy), Some(4326))) => {
+                Some(Extent {
+                         minx: minx,
+                         miny: miny,
+                         maxx: maxx,
+                         maxy: maxy,
+                     })
+            }
+            Ok(_) => None,
+            Err(err) => {
+                warn!("Layer '{}': Couldn't read extent: {}", layer.name, err);
+                None
+            }
+        }
+    }
+    /// Report layer errors once per layer
+    fn warn_layer(&self, layer: &Layer, msg: &str) {
+        let mut warned = self.layer_warnings.lock().unwrap();
+        if warned.insert(layer.name.clone()) {
+            error!("Layer '{}': {}", layer.name, msg);
+        }
+    }
+}
+
+impl DatasourceInput for GpkgInput {
+    fn retrieve_features<F>(&self,
+                            layer: &Layer,
+                            extent: &Extent,
+                            zoom: u8,
+                            grid: &Grid,
+                            mut read: F)
+        where F: FnMut(&Feature)
+    {
+        let query = match self.queries.get(&layer.name) {
+            Some(query) => query,
+            None => return,
+        };
+        // Reproject layers with SRID differing from grid SRID
+        let layer_srid = layer.srid.unwrap_or(grid.srid);
+        let (transform, extent) = if layer_srid != grid.srid {
+            match (srid_transformation(layer_srid, grid.srid),
+                   srid_transformation(grid.srid, layer_srid)) {
+                (Ok(transform), Ok(inverse)) => (Some(transform), transform_extent(extent, inverse)),
+                (Err(err), _) |
+                (_, Err(err)) => {
+                    self.warn_layer(layer, &err);
+                    return;
+                }
+            }
+        } else {
+            (None, extent.clone())
+        };
+        let geom_field = layer.geometry_field.as_ref().unwrap();
+        // Negative LIMIT means no limit in SQLite
+        let limit = layer.query_limit(zoom).map_or(-1, |limit| limit as i64);
+        let res = self.with_conn(|conn| {
+            let mut stmt = try!(conn.prepare_cached(&query.sql));
+            let columns: Vec<String> = stmt.column_names()
+                .into_iter()
+                .map(|name| name.to_string())
+                .collect();
+            let geom_idx = columns.iter().position(|col| col == geom_field);
+            let rows = if query.spatial_index {
+                try!(stmt.query_map(&[&extent.maxx,
+                                      &extent.minx,
+                                      &extent.maxy,
+                                      &extent.miny,
+                                      &limit],
+                                    |row| {
+                                        (0..columns.len())
+                                            .map(|i| row.get::<_, Value>(i))
+                                            .collect::<Vec<Value>>()
+                                    }))
+                    .collect::<Result<Vec<_>, _>>()
+            } else {
+                try!(stmt.query_map(&[&limit], |row| {
+                        (0..columns.len())
+                            .map(|i| row.get::<_, Value>(i))
+                            .collect::<Vec<Value>>()
+                    }))
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            rows.map(|rows| (columns, geom_idx, rows))
+        });
+        let (columns, geom_idx, rows) = match res {
+            Ok(res) => res,
+            Err(err) => {
+                self.warn_layer(layer, &format!("{}", err));
+                return;
+            }
+        };
+        let geom_idx = match geom_idx {
+            Some(idx) => idx,
+            None => {
+                self.warn_layer(layer,
+                                &format!("Geometry column '{}' not found", geom_field));
+                return;
+            }
+        };
+        let attr_columns: Vec<String> = columns
+            .into_iter()
+            .enumerate()
+            .filter(|&(i, _)| i != geom_idx)
+            .map(|(_, col)| col)
+            .collect();
+        for mut values in rows {
+            let geom = match values.remove(geom_idx) {
+                Value::Blob(data) => data,
+                // Skip NULL geometries
+                _ => continue,
+            };
+            let feature = FeatureRow {
+                layer: layer,
+                columns: &attr_columns,
+                values: values,
+                geom: geom,
+                transform: transform,
+            };
+            read(&feature);
+        }
+    }
+}
+
+impl Config<GpkgInput> for GpkgInput {
+    fn from_config(config: &toml::Value) -> Result<Self, String> {
+        let dscfg = try!(config
+                             .get("datasource")
+                             .ok_or("Missing configuration entry 'datasource.path'".to_string()));
+        GpkgInput::from_datasource_config(dscfg, config)
+    }
+
+    fn gen_config() -> String {
+        let toml = r#"
+[datasource]
+type = "gpkg"
+# Path of GeoPackage file
+path = "data.gpkg"
+"#;
+        toml.to_string()
+    }
+    fn gen_runtime_config(&self) -> String {
+        format!(r#"
+[datasource]
+type = "gpkg"
+path = "{}"
+"#,
+                self.path)
+    }
+}
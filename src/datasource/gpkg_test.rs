@@ -0,0 +1,152 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use datasource::DatasourceInput;
+use datasource::gpkg::{GpkgInput, decode_gpkg_geometry, decode_wkb, rtree_table_name};
+use core::geom::GeometryType;
+use core::grid::Extent;
+use core::grid::Grid;
+use core::layer::Layer;
+use std::path::Path;
+
+
+/// Little endian WKB point
+fn wkb_point(x: f64, y: f64) -> Vec<u8> {
+    let mut wkb = vec![1u8, 1, 0, 0, 0];
+    for v in &[x, y] {
+        let bits: u64 = v.to_bits();
+        for i in 0..8 {
+            wkb.push((bits >> (8 * i)) as u8);
+        }
+    }
+    wkb
+}
+
+/// GeoPackage blob with little endian header, srs_id 3857 and envelope bytes
+fn gpkg_blob(flags: u8, envelope_len: usize, wkb: &[u8]) -> Vec<u8> {
+    let mut blob = vec![b'G', b'P', 0, flags, 0x11, 0x0f, 0, 0];
+    blob.extend(vec![0u8; envelope_len]);
+    blob.extend_from_slice(wkb);
+    blob
+}
+
+#[test]
+fn test_decode_wkb() {
+    let geom = decode_wkb(&wkb_point(960000.0, 6002729.0));
+    assert_eq!(&*format!("{:?}", geom),
+               "Ok(Point(Point { x: 960000.0, y: 6002729.0, srid: None }))");
+
+    // Big endian
+    let mut wkb = vec![0u8, 0, 0, 0, 1];
+    wkb.extend_from_slice(&[0x40, 0x24, 0, 0, 0, 0, 0, 0]); // 10.0
+    wkb.extend_from_slice(&[0x40, 0x34, 0, 0, 0, 0, 0, 0]); // 20.0
+    let geom = decode_wkb(&wkb);
+    assert_eq!(&*format!("{:?}", geom),
+               "Ok(Point(Point { x: 10.0, y: 20.0, srid: None }))");
+
+    // PointZ (ISO WKB)
+    let wkb = vec![1u8, 0xe9, 0x03, 0, 0];
+    assert_eq!(decode_wkb(&wkb).err().unwrap(),
+               "Unsupported WKB geometry type 1001 (only 2D geometries are supported)");
+
+    assert_eq!(decode_wkb(&[1u8]).err().unwrap(), "Invalid WKB geometry");
+}
+
+#[test]
+fn test_decode_gpkg_geometry() {
+    let wkb = wkb_point(960000.0, 6002729.0);
+    let geom = decode_gpkg_geometry(&gpkg_blob(0x01, 0, &wkb));
+    assert_eq!(&*format!("{:?}", geom),
+               "Ok(Some(Point(Point { x: 960000.0, y: 6002729.0, srid: None })))");
+
+    // XY envelope
+    let geom = decode_gpkg_geometry(&gpkg_blob(0x03, 32, &wkb));
+    assert_eq!(&*format!("{:?}", geom),
+               "Ok(Some(Point(Point { x: 960000.0, y: 6002729.0, srid: None })))");
+
+    // Empty geometry
+    let geom = decode_gpkg_geometry(&gpkg_blob(0x11, 0, &wkb));
+    assert!(geom.unwrap().is_none());
+
+    let geom = decode_gpkg_geometry(&wkb);
+    assert_eq!(geom.err().unwrap(), "Invalid GeoPackage geometry header");
+    let geom = decode_gpkg_geometry(&gpkg_blob(0x0b, 0, &wkb));
+    assert_eq!(geom.err().unwrap(), "Invalid envelope contents indicator 5");
+}
+
+#[test]
+fn test_rtree_table_name() {
+    assert_eq!(rtree_table_name("ne_10m_populated_places", "geom"),
+               "rtree_ne_10m_populated_places_geom");
+}
+
+#[test]
+#[ignore]
+fn test_retrieve_features() {
+    // Created with `make natural_earth.gpkg` in src/test
+    let path = "src/test/natural_earth.gpkg";
+    if !Path::new(path).exists() {
+        panic!("{} not found", path);
+    }
+    let mut gpkg = GpkgInput::new(path).connected();
+    let layers = gpkg.detect_layers(false);
+    let layer = layers
+        .iter()
+        .find(|layer| layer.name == "ne_10m_populated_places")
+        .unwrap();
+    assert_eq!(layer.geometry_type, Some("POINT".to_string()));
+    assert_eq!(layer.srid, Some(3857));
+    let cols = gpkg.detect_data_columns(&layer, None);
+    assert!(cols.iter().any(|&(ref name, _)| name == "name"));
+    assert!(!cols.iter().any(|&(ref name, _)| Some(name) == layer.geometry_field.as_ref()));
+
+    let grid = Grid::web_mercator();
+    let mut layer = layer.clone();
+    layer.fid_field = Some("fid".to_string());
+    gpkg.prepare_queries(&layer, &grid);
+    let extent = Extent {
+        minx: 821850.9,
+        miny: 5909499.5,
+        maxx: 860894.8,
+        maxy: 5939449.0,
+    };
+    let mut names = Vec::new();
+    gpkg.retrieve_features(&layer, &extent, 10, &grid, |feat| {
+        assert!(feat.fid().is_some());
+        assert!(feat.geometry().is_ok());
+        for attr in feat.attributes() {
+            if attr.key == "name" {
+                names.push(format!("{:?}", attr.value));
+            }
+        }
+    });
+    assert_eq!(names, vec!["String(\"Bern\")"]);
+    assert_eq!(gpkg.detect_srid(&layer), Some(3857));
+
+    // Reprojection to WGS84 grid
+    let grid = Grid::wgs84();
+    gpkg.prepare_queries(&layer, &grid);
+    let extent = Extent {
+        minx: 7.38,
+        miny: 46.9,
+        maxx: 7.73,
+        maxy: 47.08,
+    };
+    let mut points = Vec::new();
+    gpkg.retrieve_features(&layer, &extent, 10, &grid, |feat| {
+        if let Ok(GeometryType::Point(p)) = feat.geometry() {
+            points.push(p);
+        }
+    });
+    assert_eq!(points.len(), 1);
+    assert!((points[0].x - 7.47).abs() < 0.1 && (points[0].y - 46.95).abs() < 0.1);
+
+    let mut unknown = Layer::new("unknown");
+    unknown.table_name = Some("unknown".to_string());
+    unknown.geometry_field = Some("geom".to_string());
+    let mut count = 0;
+    gpkg.retrieve_features(&unknown, &extent, 10, &grid, |_| count += 1);
+    assert_eq!(count, 0);
+}
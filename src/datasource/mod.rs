@@ -5,18 +5,188 @@
 
 pub mod datasource;
 pub mod postgis;
+pub mod gpkg;
+pub mod geojson;
+pub mod datasources;
 #[cfg(test)]
 mod postgis_test;
+#[cfg(test)]
+mod gpkg_test;
+#[cfg(test)]
+mod geojson_test;
 
-pub use self::datasource::DatasourceInput;
+pub use self::datasource::{DatasourceInput, take_query_timeout, take_query_error,
+                           set_query_timeout, set_query_error};
 pub use self::postgis::PostgisInput;
+pub use self::gpkg::GpkgInput;
+pub use self::geojson::GeojsonInput;
+pub use self::datasources::Datasources;
 
 use core::Config;
+use core::feature::Feature;
+use core::grid::{Extent, Grid};
+use core::layer::Layer;
 use toml;
 
 
 pub enum Datasource {
     Postgis(PostgisInput),
+    Gpkg(GpkgInput),
+    Geojson(GeojsonInput),
+}
+
+impl Datasource {
+    pub fn from_datasource_config(dscfg: &toml::Value,
+                                  config: &toml::Value)
+                                  -> Result<Datasource, String> {
+        dscfg
+            .get("type")
+            .ok_or("Missing configuration entry 'datasource.type'".to_string())
+            .and_then(|val| {
+                          val.as_str()
+                              .ok_or("type entry is not a string".to_string())
+                      })
+            .and_then(|tn| match tn {
+                          "postgis" => {
+                              PostgisInput::from_datasource_config(dscfg, config)
+                                  .map(|pg| Datasource::Postgis(pg))
+                          }
+                          "gpkg" => {
+                              GpkgInput::from_datasource_config(dscfg, config)
+                                  .map(|gpkg| Datasource::Gpkg(gpkg))
+                          }
+                          "geojson" => {
+                              GeojsonInput::from_datasource_config(dscfg, config)
+                                  .map(|json| Datasource::Geojson(json))
+                          }
+                          _ => Err(format!("Unsupported datasource '{}'", tn)),
+                      })
+    }
+    pub fn connected(&self) -> Datasource {
+        match self {
+            &Datasource::Postgis(ref pg) => Datasource::Postgis(pg.connected()),
+            &Datasource::Gpkg(ref gpkg) => Datasource::Gpkg(gpkg.connected()),
+            &Datasource::Geojson(ref json) => Datasource::Geojson(json.connected()),
+        }
+    }
+    pub fn try_connected(&self) -> Result<Datasource, String> {
+        match self {
+            &Datasource::Postgis(ref pg) => pg.try_connected().map(|pg| Datasource::Postgis(pg)),
+            &Datasource::Gpkg(ref gpkg) => gpkg.try_connected().map(|gpkg| Datasource::Gpkg(gpkg)),
+            &Datasource::Geojson(ref json) => json.try_connected().map(|json| Datasource::Geojson(json)),
+        }
+    }
+    pub fn check_layer(&self, layer: &Layer, grid: &Grid) -> Result<(), String> {
+        match self {
+            &Datasource::Postgis(ref pg) => pg.check_layer(layer, grid),
+            &Datasource::Gpkg(ref gpkg) => gpkg.check_layer(layer, grid),
+            &Datasource::Geojson(ref json) => json.check_layer(layer, grid),
+        }
+    }
+    /// SRID of the layer geometry. None if unknown or reprojected by the datasource.
+    pub fn detect_srid(&self, layer: &Layer) -> Option<i32> {
+        match self {
+            &Datasource::Postgis(ref pg) => pg.detect_srid(layer),
+            &Datasource::Gpkg(ref gpkg) => gpkg.detect_srid(layer),
+            &Datasource::Geojson(_) => None,
+        }
+    }
+    pub fn detect_layers(&self, detect_geometry_types: bool) -> Vec<Layer> {
+        match self {
+            &Datasource::Postgis(ref pg) => pg.detect_layers(detect_geometry_types),
+            &Datasource::Gpkg(ref gpkg) => gpkg.detect_layers(detect_geometry_types),
+            &Datasource::Geojson(ref json) => json.detect_layers(detect_geometry_types),
+        }
+    }
+    /// Attribute fields with types "String", "Number" or "Boolean" exposed in tiles
+    pub fn detect_field_types(&self,
+                              layer: &Layer,
+                              sql: Option<&String>)
+                              -> Result<Vec<(String, String)>, String> {
+        let fields = match self {
+            &Datasource::Postgis(ref pg) => pg.detect_field_types(layer, sql),
+            &Datasource::Gpkg(ref gpkg) => gpkg.detect_field_types(layer, sql),
+            &Datasource::Geojson(ref json) => json.detect_field_types(layer, sql),
+        };
+        fields.map(|fields| {
+                       fields
+                           .into_iter()
+                           .filter(|&(ref name, _)| layer.field_exposed(name))
+                           .collect()
+                   })
+    }
+    /// Attribute columns exposed in tiles
+    pub fn detect_data_columns(&self,
+                               layer: &Layer,
+                               sql: Option<&String>)
+                               -> Vec<(String, String)> {
+        let cols = match self {
+            &Datasource::Postgis(ref pg) => pg.detect_data_columns(layer, sql),
+            &Datasource::Gpkg(ref gpkg) => gpkg.detect_data_columns(layer, sql),
+            &Datasource::Geojson(ref json) => json.detect_data_columns(layer, sql),
+        };
+        cols.into_iter()
+            .filter(|&(ref name, _)| layer.field_exposed(name))
+            .collect()
+    }
+    pub fn prepare_queries(&mut self, layer: &Layer, grid: &Grid) {
+        match self {
+            &mut Datasource::Postgis(ref mut pg) => pg.prepare_queries(layer, grid),
+            &mut Datasource::Gpkg(ref mut gpkg) => gpkg.prepare_queries(layer, grid),
+            &mut Datasource::Geojson(ref mut json) => json.prepare_queries(layer, grid),
+        }
+    }
+    /// Check that the datasource is reachable
+    pub fn ping(&self) -> Result<(), String> {
+        match self {
+            &Datasource::Postgis(ref pg) => pg.ping(),
+            &Datasource::Gpkg(ref gpkg) => gpkg.ping(),
+            &Datasource::Geojson(ref json) => json.ping(),
+        }
+    }
+    pub fn mvt_supported(&self) -> bool {
+        match self {
+            &Datasource::Postgis(ref pg) => pg.mvt_supported(),
+            &Datasource::Gpkg(ref gpkg) => gpkg.mvt_supported(),
+            &Datasource::Geojson(ref json) => json.mvt_supported(),
+        }
+    }
+    pub fn retrieve_mvt_layer(&self,
+                              layer: &Layer,
+                              extent: &Extent,
+                              zoom: u8,
+                              grid: &Grid)
+                              -> Option<Vec<u8>> {
+        match self {
+            &Datasource::Postgis(ref pg) => pg.retrieve_mvt_layer(layer, extent, zoom, grid),
+            &Datasource::Gpkg(ref gpkg) => gpkg.retrieve_mvt_layer(layer, extent, zoom, grid),
+            &Datasource::Geojson(ref json) => json.retrieve_mvt_layer(layer, extent, zoom, grid),
+        }
+    }
+    pub fn retrieve_extent(&self, layer: &Layer, grid: &Grid) -> Option<Extent> {
+        match self {
+            &Datasource::Postgis(ref pg) => pg.retrieve_extent(layer, grid),
+            &Datasource::Gpkg(ref gpkg) => gpkg.retrieve_extent(layer, grid),
+            &Datasource::Geojson(ref json) => json.retrieve_extent(layer, grid),
+        }
+    }
+}
+
+impl DatasourceInput for Datasource {
+    fn retrieve_features<F>(&self,
+                            layer: &Layer,
+                            extent: &Extent,
+                            zoom: u8,
+                            grid: &Grid,
+                            read: F)
+        where F: FnMut(&Feature)
+    {
+        match self {
+            &Datasource::Postgis(ref pg) => pg.retrieve_features(layer, extent, zoom, grid, read),
+            &Datasource::Gpkg(ref gpkg) => gpkg.retrieve_features(layer, extent, zoom, grid, read),
+            &Datasource::Geojson(ref json) => json.retrieve_features(layer, extent, zoom, grid, read),
+        }
+    }
 }
 
 impl Config<Datasource> for Datasource {
@@ -31,6 +201,8 @@ impl Config<Datasource> for Datasource {
                       })
             .and_then(|tn| match tn {
                           "postgis" => PostgisInput::from_config(config).and_then(|pg| Ok(Datasource::Postgis(pg))),
+                          "gpkg" => GpkgInput::from_config(config).and_then(|gpkg| Ok(Datasource::Gpkg(gpkg))),
+                          "geojson" => GeojsonInput::from_config(config).and_then(|json| Ok(Datasource::Geojson(json))),
                           _ => Err(format!("Unsupported datasource '{}'", tn)),
                       })
     }
@@ -40,6 +212,8 @@ impl Config<Datasource> for Datasource {
     fn gen_runtime_config(&self) -> String {
         match self {
             &Datasource::Postgis(ref pg) => pg.gen_runtime_config(),
+            &Datasource::Gpkg(ref gpkg) => gpkg.gen_runtime_config(),
+            &Datasource::Geojson(ref json) => json.gen_runtime_config(),
         }
     }
 }
@@ -62,9 +236,42 @@ fn test_datasource_from_config() {
         "#;
     let pg = match ds_from_config(toml).unwrap() {
         Datasource::Postgis(pg) => pg,
+        _ => panic!("PostGIS datasource expected"),
     };
     assert_eq!(pg.connection_url,
                "postgresql://pi@localhost/natural_earth_vectors");
+
+    let toml = r#"
+        [datasource]
+        type = "gpkg"
+        path = "natural_earth.gpkg"
+        "#;
+    let gpkg = match ds_from_config(toml).unwrap() {
+        Datasource::Gpkg(gpkg) => gpkg,
+        _ => panic!("GeoPackage datasource expected"),
+    };
+    assert_eq!(gpkg.path, "natural_earth.gpkg");
+
+    let toml = r#"
+        [datasource]
+        type = "geojson"
+        path = ["cities.geojson", "lakes.geojson"]
+        "#;
+    let json = match ds_from_config(toml).unwrap() {
+        Datasource::Geojson(json) => json,
+        _ => panic!("GeoJSON datasource expected"),
+    };
+    assert_eq!(json.paths, vec!["cities.geojson", "lakes.geojson"]);
+    assert!(json.gen_runtime_config()
+                .contains(r#"path = ["cities.geojson", "lakes.geojson"]"#));
+}
+
+#[cfg(test)]
+fn connection_url(ds: &Datasource) -> &str {
+    match ds {
+        &Datasource::Postgis(ref pg) => &pg.connection_url,
+        _ => "",
+    }
 }
 
 #[test]
@@ -90,4 +297,137 @@ fn test_datasource_config_errors() {
         "#;
     assert_eq!(ds_from_config(toml).err().unwrap(),
                "url entry is not a string");
+    let toml = r#"
+        [datasource]
+        type = "postgis"
+        url = "postgresql://pi@localhost/natural_earth_vectors"
+        pool_size = -1
+        "#;
+    assert_eq!(ds_from_config(toml).err().unwrap(),
+               "Invalid pool_size -1 (expected at least 1)");
+    let toml = r#"
+        [datasource]
+        type = "postgis"
+        url = "postgresql://pi@localhost/natural_earth_vectors"
+        pool_size = "4"
+        "#;
+    assert_eq!(ds_from_config(toml).err().unwrap(),
+               "pool_size entry is not an integer");
+}
+
+#[test]
+fn test_multiple_datasources() {
+    use core::parse_config;
+    use core::layer::Layer;
+
+    let toml = r#"
+        [[datasource]]
+        name = "osm"
+        type = "postgis"
+        url = "postgresql://pi@localhost/osm"
+
+        [[datasource]]
+        name = "ne"
+        type = "postgis"
+        url = "postgresql://pi@localhost/natural_earth_vectors"
+        pool_size = 4
+        sslmode = "require"
+
+        [[datasource]]
+        name = "gpkg"
+        type = "gpkg"
+        path = "natural_earth.gpkg"
+        "#;
+    let config = parse_config(toml.to_string(), "").unwrap();
+    let datasources = Datasources::from_config(&config).unwrap();
+    assert_eq!(datasources.default, Some("osm".to_string()));
+    assert_eq!(connection_url(datasources.get("ne").unwrap()),
+               "postgresql://pi@localhost/natural_earth_vectors");
+    match datasources.get("gpkg") {
+        Some(&Datasource::Gpkg(ref gpkg)) => assert_eq!(gpkg.path, "natural_earth.gpkg"),
+        _ => panic!("GeoPackage datasource expected"),
+    }
+
+    let mut layer = Layer::new("points");
+    assert_eq!(connection_url(datasources.for_layer(&layer).unwrap()),
+               "postgresql://pi@localhost/osm");
+    layer.datasource = Some("ne".to_string());
+    assert_eq!(connection_url(datasources.for_layer(&layer).unwrap()),
+               "postgresql://pi@localhost/natural_earth_vectors");
+    layer.datasource = Some("unknown".to_string());
+    assert!(datasources.for_layer(&layer).is_none());
+    assert_eq!(datasources.check_layers(&[&layer]).err().unwrap(),
+               "Unknown datasource 'unknown' in layer 'points'");
+
+    // Layers with the same name in the same datasource
+    let mut other = Layer::new("points");
+    other.datasource = Some("ne".to_string());
+    layer.datasource = Some("ne".to_string());
+    assert!(datasources.check_layers(&[&layer, &other]).is_ok());
+    other.table_name = Some("ne_10m_populated_places".to_string());
+    assert_eq!(datasources.check_layers(&[&layer, &other]).err().unwrap(),
+               "Layer 'points' of datasource 'ne' is defined more than once with different settings");
+    other.datasource = Some("osm".to_string());
+    assert!(datasources.check_layers(&[&layer, &other]).is_ok());
+
+    // Runtime config with all datasource settings
+    let config = parse_config(datasources.gen_runtime_config(), "").unwrap();
+    let regenerated = Datasources::from_config(&config).unwrap();
+    match regenerated.get("ne") {
+        Some(&Datasource::Postgis(ref pg)) => {
+            assert_eq!(pg.pool_size, Some(4));
+            assert_eq!(pg.sslmode, Some("require".to_string()));
+        }
+        _ => panic!("PostGIS datasource expected"),
+    }
+    assert_eq!(regenerated.default, Some("osm".to_string()));
+
+    // Single datasource
+    let toml = r#"
+        [datasource]
+        type = "postgis"
+        url = "postgresql://pi@localhost/natural_earth_vectors"
+        "#;
+    let config = parse_config(toml.to_string(), "").unwrap();
+    let datasources = Datasources::from_config(&config).unwrap();
+    assert_eq!(connection_url(datasources.default().unwrap()),
+               "postgresql://pi@localhost/natural_earth_vectors");
+
+    let toml = r#"
+        [[datasource]]
+        type = "postgis"
+        url = "postgresql://pi@localhost/osm"
+        "#;
+    let config = parse_config(toml.to_string(), "").unwrap();
+    assert_eq!(Datasources::from_config(&config).err().unwrap(),
+               "Missing configuration entry name in [[datasource]]");
+}
+
+#[test]
+fn test_detect_layers_per_datasource() {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+
+    let mut dir = env::temp_dir();
+    dir.push("t_rex_test_detect_datasources");
+    let _ = fs::remove_dir_all(&dir);
+    let mut datasources = Datasources::new();
+    for name in &["a", "b"] {
+        let dsdir = dir.join(name);
+        fs::create_dir_all(&dsdir).unwrap();
+        let path = format!("{}/places.geojson", dsdir.display());
+        let mut file = File::create(&path).unwrap();
+        file.write_all(br#"{"type": "FeatureCollection", "features": []}"#)
+            .unwrap();
+        datasources.add(name, Datasource::Geojson(GeojsonInput::new(&[path.as_str()])));
+    }
+    let datasources = datasources.connected();
+    let layers = datasources.detect_layers(false);
+    let names: Vec<(&str, Option<&str>)> = layers
+        .iter()
+        .map(|l| (l.name.as_str(), l.datasource.as_ref().map(|ds| ds.as_str())))
+        .collect();
+    assert_eq!(names, vec![("places", None), ("b_places", Some("b"))]);
+    assert_eq!(layers[1].table_name, Some("places".to_string()));
 }
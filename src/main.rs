@@ -3,6 +3,10 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
+// Rust 2015 idioms (`try!`, trait objects without `dyn`) are kept throughout the code base
+#![allow(deprecated, bare_trait_objects)]
+#![allow(clippy::style, clippy::complexity, clippy::perf)]
+
 #[macro_use]
 extern crate log;
 extern crate env_logger;
@@ -28,6 +32,21 @@ extern crate time;
 extern crate flate2;
 extern crate pbr;
 extern crate open;
+extern crate rusoto_core;
+extern crate rusoto_s3;
+extern crate lru_cache;
+extern crate redis;
+extern crate rusqlite;
+extern crate sha1;
+extern crate unicase;
+extern crate hyper_openssl;
+extern crate openssl;
+extern crate ctrlc;
+extern crate scoped_threadpool;
+#[cfg(unix)]
+extern crate libc;
+#[cfg(test)]
+extern crate filetime;
 
 pub mod core;
 mod datasource;
@@ -37,9 +56,15 @@ mod cache;
 mod webserver;
 
 use core::grid::Extent;
+use core::{Config, read_config};
+use cache::Tilecache;
+use datasource::Datasource;
+use service::mvt::MvtService;
 use clap::{App, SubCommand, ArgMatches, AppSettings};
 use std::env;
 use std::process;
+use std::fs::File;
+use std::io::BufReader;
 use log::{LogRecord, LogLevelFilter};
 use env_logger::LogBuilder;
 
@@ -70,29 +95,8 @@ fn init_logger() {
     builder.init().unwrap();
 }
 
-fn generate(args: &ArgMatches) {
-    let (mut service, config) = webserver::server::service_from_args(args);
-    let _ = config
-        .get("cache")
-        .and_then(|c| c.get("file"))
-        .and_then(|c| c.get("base"))
-        .ok_or("Missing configuration entry base in [cache.file]".to_string())
-        .unwrap_or_else(|err| {
-                            println!("Error reading configuration - {} ", err);
-                            process::exit(1)
-                        });
-    let tileset = args.value_of("tileset");
-    let minzoom = args.value_of("minzoom")
-        .map(|s| {
-                 s.parse::<u8>()
-                     .expect("Error parsing 'minzoom' as integer value")
-             });
-    let maxzoom = args.value_of("maxzoom")
-        .map(|s| {
-                 s.parse::<u8>()
-                     .expect("Error parsing 'maxzoom' as integer value")
-             });
-    let extent = args.value_of("extent")
+fn extent_from_args(args: &ArgMatches) -> Option<Extent> {
+    args.value_of("extent")
         .and_then(|numlist| {
             let arr: Vec<f64> = numlist
                 .split(",")
@@ -107,7 +111,51 @@ fn generate(args: &ArgMatches) {
                      maxx: arr[2],
                      maxy: arr[3],
                  })
-        });
+        })
+}
+
+/// One database connection per generator thread
+fn reserve_connections(service: &mut MvtService, threads: u8) {
+    let mut reconnect = false;
+    for input in service.datasources.datasources.values_mut() {
+        if let &mut Datasource::Postgis(ref mut pg) = input {
+            if pg.pool_size.unwrap_or(10) < threads as u32 {
+                pg.pool_size = Some(threads as u32);
+                reconnect = true;
+            }
+        }
+    }
+    if reconnect {
+        service.connect();
+    }
+}
+
+fn generate(args: &ArgMatches) {
+    let (mut service, _config) = webserver::server::service_from_args(args);
+    let tileset = args.value_of("tileset");
+    let uncached = service
+        .tilesets
+        .iter()
+        .filter(|set| tileset.map_or(true, |name| name == set.name))
+        .all(|set| match *service.tileset_cache(&set.name) {
+                 Tilecache::Nocache(_) => true,
+                 _ => false,
+             });
+    if uncached {
+        println!("Error reading configuration - Missing cache configuration entry [cache.*]");
+        process::exit(1)
+    }
+    let minzoom = args.value_of("minzoom")
+        .map(|s| {
+                 s.parse::<u8>()
+                     .expect("Error parsing 'minzoom' as integer value")
+             });
+    let maxzoom = args.value_of("maxzoom")
+        .map(|s| {
+                 s.parse::<u8>()
+                     .expect("Error parsing 'maxzoom' as integer value")
+             });
+    let extent = extent_from_args(args);
     let nodes = args.value_of("nodes")
         .map(|s| {
                  s.parse::<u8>()
@@ -118,13 +166,123 @@ fn generate(args: &ArgMatches) {
                  s.parse::<u8>()
                      .expect("Error parsing 'nodeno' as integer value")
              });
+    let threads = args.value_of("threads")
+        .map(|s| {
+                 s.parse::<u8>()
+                     .expect("Error parsing 'threads' as integer value")
+             });
     let progress = args.value_of("progress")
         .map_or(true, |s| {
             s.parse::<bool>()
                 .expect("Error parsing 'progress' as boolean value")
         });
+    if let Some(threads) = threads {
+        reserve_connections(&mut service, threads);
+    }
     service.prepare_feature_queries();
-    service.generate(tileset, minzoom, maxzoom, extent, nodes, nodeno, progress);
+    if let Some(path) = args.value_of("tile-list") {
+        let file = File::open(path).unwrap_or_else(|err| {
+                                                       println!("Error reading tile list '{}' - {}", path, err);
+                                                       process::exit(1)
+                                                   });
+        let tiles = service.read_tile_list(BufReader::new(file));
+        service.generate_tile_list(tileset, &tiles, threads, progress);
+    } else {
+        service.generate(tileset, minzoom, maxzoom, extent, nodes, nodeno, threads, progress);
+    }
+}
+
+fn export(args: &ArgMatches) {
+    let (mut service, _config) = webserver::server::service_from_args(args);
+    let format = args.value_of("format").unwrap_or("mbtiles");
+    if format != "mbtiles" && format != "pmtiles" {
+        println!("Unsupported export format '{}'", format);
+        process::exit(1)
+    }
+    let tileset = args.value_of("tileset").unwrap();
+    let out = args.value_of("out").unwrap();
+    let minzoom = args.value_of("minzoom")
+        .map(|s| {
+                 s.parse::<u8>()
+                     .expect("Error parsing 'minzoom' as integer value")
+             });
+    let maxzoom = args.value_of("maxzoom")
+        .map(|s| {
+                 s.parse::<u8>()
+                     .expect("Error parsing 'maxzoom' as integer value")
+             });
+    let extent = extent_from_args(args);
+    let threads = args.value_of("threads")
+        .map(|s| {
+                 s.parse::<u8>()
+                     .expect("Error parsing 'threads' as integer value")
+             });
+    let progress = args.value_of("progress")
+        .map_or(true, |s| {
+            s.parse::<bool>()
+                .expect("Error parsing 'progress' as boolean value")
+        });
+    if let Some(threads) = threads {
+        reserve_connections(&mut service, threads);
+    }
+    service.prepare_feature_queries();
+    let result = if format == "pmtiles" {
+        service.export_pmtiles(tileset, out, minzoom, maxzoom, extent, threads, progress)
+    } else {
+        service.export_mbtiles(tileset, out, minzoom, maxzoom, extent, threads, progress)
+    };
+    match result {
+        Ok(size) => println!("Exported tileset '{}' to {} ({} bytes)", tileset, out, size),
+        Err(err) => {
+            println!("Error exporting tileset - {}", err);
+            process::exit(1)
+        }
+    }
+}
+
+fn check(args: &ArgMatches) {
+    let path = args.value_of("config").unwrap();
+    let config = read_config(path).unwrap_or_else(|err| {
+                                                     println!("Error reading configuration - {}", err);
+                                                     process::exit(1)
+                                                 });
+    let mut service = MvtService::from_config(&config).unwrap_or_else(|err| {
+        println!("Error reading configuration - {}", err);
+        process::exit(1)
+    });
+    let errors = service.check();
+    if errors.is_empty() {
+        println!("Configuration '{}' is valid", path);
+    } else {
+        for err in &errors {
+            println!("Error: {}", err);
+        }
+        println!("{} problem(s) found in '{}'", errors.len(), path);
+        process::exit(1)
+    }
+}
+
+fn clearcache(args: &ArgMatches) {
+    let (service, _config) = webserver::server::service_from_args(args);
+    let tileset = args.value_of("tileset").unwrap();
+    let minzoom = args.value_of("minzoom")
+        .map(|s| {
+                 s.parse::<u8>()
+                     .expect("Error parsing 'minzoom' as integer value")
+             });
+    let maxzoom = args.value_of("maxzoom")
+        .map(|s| {
+                 s.parse::<u8>()
+                     .expect("Error parsing 'maxzoom' as integer value")
+             });
+    let extent = extent_from_args(args);
+    match service.purge_cache(tileset, minzoom, maxzoom, extent) {
+        Ok(count) => println!("Removed {} tiles", count),
+        Err(err) => {
+            println!("Error removing cached tiles - {}", err);
+            process::exit(1)
+        }
+    }
 }
 
 fn main() {
@@ -137,28 +295,57 @@ fn main() {
         .about("vector tile server specialized on publishing MVT tiles from a PostGIS database")
         .subcommand(SubCommand::with_name("serve")
                         .args_from_usage("--dbconn=[SPEC] 'PostGIS connection postgresql://USER@HOST/DBNAME'
+                                              --datasource=[FILE] 'GeoPackage or GeoJSON file'
+                                              --schema=[NAMES] 'Comma separated list of schemas for layer detection'
                                               --simplify=[true|false] 'Simplify geometries'
                                               --clip=[true|false] 'Clip geometries'
                                               --cache=[DIR] 'Use tile cache in DIR'
-                                              -c, --config=[FILE] 'Load from custom config file'
+                                              -c, --config=[FILE] 'Load from custom config file, URL or - for stdin'
                                               --openbrowser=[true|false] 'Open backend URL in browser'")
                         .about("Start web server and serve MVT vector tiles"))
         .subcommand(SubCommand::with_name("genconfig")
                         .args_from_usage("--dbconn=[SPEC] 'PostGIS connection postgresql://USER@HOST/DBNAME'
+                                              --datasource=[FILE] 'GeoPackage or GeoJSON file'
+                                              --schema=[NAMES] 'Comma separated list of schemas for layer detection'
                                               --simplify=[true|false] 'Simplify geometries'
                                               --clip=[true|false] 'Clip geometries'")
                         .about("Generate configuration template"))
         .subcommand(SubCommand::with_name("generate")
                         .setting(AppSettings::AllowLeadingHyphen)
-                        .args_from_usage("-c, --config=<FILE> 'Load from custom config file'
+                        .args_from_usage("-c, --config=<FILE> 'Load from custom config file, URL or - for stdin'
                                               --tileset=[NAME] 'Tileset name'
                                               --minzoom=[LEVEL] 'Minimum zoom level'
                                               --maxzoom=[LEVEL] 'Maximum zoom level'
                                               --extent=[minx,miny,maxx,maxy] 'Extent of tiles'
                                               --nodes=[NUM] 'Number of generator nodes'
                                               --nodeno=[NUM] 'Number of this nodes (0 <= n < nodes)'
+                                              --threads=[NUM] 'Number of generator threads'
+                                              --tile-list=[FILE] 'Generate tiles listed as z/x/y in FILE'
                                               --progress=[true|false] 'Show progress bar'")
-                        .about("Generate tiles for cache"));
+                        .about("Generate tiles for cache"))
+        .subcommand(SubCommand::with_name("export")
+                        .setting(AppSettings::AllowLeadingHyphen)
+                        .args_from_usage("-c, --config=<FILE> 'Load from custom config file, URL or - for stdin'
+                                              --tileset=<NAME> 'Tileset name'
+                                              --format=[FORMAT] 'Output format (mbtiles|pmtiles)'
+                                              --out=<FILE> 'Output file'
+                                              --minzoom=[LEVEL] 'Minimum zoom level'
+                                              --maxzoom=[LEVEL] 'Maximum zoom level'
+                                              --extent=[minx,miny,maxx,maxy] 'Extent of tiles'
+                                              --threads=[NUM] 'Number of generator threads'
+                                              --progress=[true|false] 'Show progress bar'")
+                        .about("Export tileset into a file"))
+        .subcommand(SubCommand::with_name("clearcache")
+                        .setting(AppSettings::AllowLeadingHyphen)
+                        .args_from_usage("-c, --config=<FILE> 'Load from custom config file, URL or - for stdin'
+                                              --tileset=<NAME> 'Tileset name'
+                                              --minzoom=[LEVEL] 'Minimum zoom level'
+                                              --maxzoom=[LEVEL] 'Maximum zoom level'
+                                              --extent=[minx,miny,maxx,maxy] 'Extent of tiles'")
+                        .about("Remove tiles from cache"))
+        .subcommand(SubCommand::with_name("check")
+                        .args_from_usage("-c, --config=<FILE> 'Load from custom config file, URL or - for stdin'")
+                        .about("Validate configuration and datasource layers"));
 
     match app.get_matches_from_safe_borrow(env::args()) { //app.get_matches() prohibits later call of app.print_help()
         Result::Err(e) => {
@@ -169,6 +356,9 @@ fn main() {
                 ("serve", Some(sub_m)) => webserver::server::webserver(sub_m),
                 ("genconfig", Some(sub_m)) => println!("{}", webserver::server::gen_config(sub_m)),
                 ("generate", Some(sub_m)) => generate(sub_m),
+                ("export", Some(sub_m)) => export(sub_m),
+                ("clearcache", Some(sub_m)) => clearcache(sub_m),
+                ("check", Some(sub_m)) => check(sub_m),
                 _ => {
                     let _ = app.print_help();
                     println!("");
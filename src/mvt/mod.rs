@@ -4,7 +4,8 @@
 //
 
 pub mod tile;
-#[cfg_attr(rustfmt, rustfmt_skip)]
+#[rustfmt::skip]
+#[allow(static_mut_refs)]
 pub mod vector_tile; // protoc --rust_out . vector_tile.proto
 pub mod geom_encoder;
 #[cfg(test)]
@@ -9,6 +9,7 @@ use core::grid::Extent;
 use core::geom::GeometryType;
 use core::geom;
 use core::screen;
+use core::clip::clip_geometry;
 use mvt::vector_tile;
 use mvt::geom_encoder::{EncodableGeom, CommandSequence};
 use protobuf::stream::CodedOutputStream;
@@ -22,6 +23,32 @@ use flate2::write::GzEncoder;
 use flate2::read::GzDecoder;
 
 
+/// Media type of Mapbox vector tiles
+pub const MVT_CONTENT_TYPE: &'static str = "application/vnd.mapbox-vector-tile";
+
+/// Media type of vector tiles in older t-rex versions
+pub const LEGACY_MVT_CONTENT_TYPE: &'static str = "application/x-protobuf";
+
+/// Default gzip compression level
+pub const DEFAULT_GZIP_LEVEL: u32 = 6;
+
+/// Gzip compression levels supported by flate2: none, fast, default and best
+pub const GZIP_LEVELS: [u32; 4] = [0, 1, 6, 9];
+
+/// Default size in bytes below which tiles are not compressed
+pub const DEFAULT_GZIP_MIN_BYTES: usize = 512;
+
+/// flate2 compression preset of a level in `GZIP_LEVELS`.
+/// flate2 0.2 has no numeric levels, other levels use the default preset.
+fn compression(level: u32) -> Compression {
+    match level {
+        0 => Compression::None,
+        1 => Compression::Fast,
+        9 => Compression::Best,
+        _ => Compression::Default,
+    }
+}
+
 pub struct Tile<'a> {
     pub mvt_tile: vector_tile::Tile,
     tile_size: u32,
@@ -150,7 +177,7 @@ impl ScreenGeom<geom::MultiPolygon> for screen::MultiPolygon {
 // --- Tile creation functions
 
 impl<'a> Tile<'a> {
-    pub fn new(extent: &Extent, tile_size: u32, reverse_y: bool) -> Tile {
+    pub fn new(extent: &Extent, tile_size: u32, reverse_y: bool) -> Tile<'_> {
         let mvt_tile = vector_tile::Tile::new();
         Tile {
             mvt_tile: mvt_tile,
@@ -164,32 +191,37 @@ impl<'a> Tile<'a> {
         let mut mvt_layer = vector_tile::Tile_Layer::new();
         mvt_layer.set_version(2);
         mvt_layer.set_name(layer.name.clone());
-        mvt_layer.set_extent(self.tile_size);
+        mvt_layer.set_extent(layer.mvt_extent(self.tile_size));
         mvt_layer
     }
 
     pub fn encode_geom(&self, geom: geom::GeometryType) -> CommandSequence {
+        self.encode_geom_extent(geom, self.tile_size)
+    }
+
+    /// Encode geometry in tile units of a layer with extent `tile_size`
+    pub fn encode_geom_extent(&self, geom: geom::GeometryType, tile_size: u32) -> CommandSequence {
         match geom {
             GeometryType::Point(ref g) => {
-                screen::Point::from_geom(&self.extent, self.reverse_y, self.tile_size, g).encode()
+                screen::Point::from_geom(&self.extent, self.reverse_y, tile_size, g).encode()
             }
             GeometryType::MultiPoint(ref g) => {
-                screen::MultiPoint::from_geom(&self.extent, self.reverse_y, self.tile_size, g)
+                screen::MultiPoint::from_geom(&self.extent, self.reverse_y, tile_size, g)
                     .encode()
             }
             GeometryType::LineString(ref g) => {
-                screen::LineString::from_geom(&self.extent, self.reverse_y, self.tile_size, g)
+                screen::LineString::from_geom(&self.extent, self.reverse_y, tile_size, g)
                     .encode()
             }
             GeometryType::MultiLineString(ref g) => {
-                screen::MultiLineString::from_geom(&self.extent, self.reverse_y, self.tile_size, g)
+                screen::MultiLineString::from_geom(&self.extent, self.reverse_y, tile_size, g)
                     .encode()
             }
             GeometryType::Polygon(ref g) => {
-                screen::Polygon::from_geom(&self.extent, self.reverse_y, self.tile_size, g).encode()
+                screen::Polygon::from_geom(&self.extent, self.reverse_y, tile_size, g).encode()
             }
             GeometryType::MultiPolygon(ref g) => {
-                screen::MultiPolygon::from_geom(&self.extent, self.reverse_y, self.tile_size, g)
+                screen::MultiPolygon::from_geom(&self.extent, self.reverse_y, tile_size, g)
                     .encode()
             }
             GeometryType::GeometryCollection(_) => panic!("GeometryCollection not supported"),
@@ -226,7 +258,41 @@ impl<'a> Tile<'a> {
         mvt_feature.mut_tags().push(validx as u32);
     }
 
-    pub fn add_feature(&self, mut mvt_layer: &mut vector_tile::Tile_Layer, feature: &Feature) {
+    pub fn add_feature(&self, mvt_layer: &mut vector_tile::Tile_Layer, feature: &Feature) {
+        if let Ok(geom) = feature.geometry() {
+            self.add_feature_geom(mvt_layer, feature, geom);
+        }
+    }
+
+    /// Add feature with geometry clipped to `clip_extent`. Features outside are skipped.
+    pub fn add_feature_clipped(&self,
+                               mvt_layer: &mut vector_tile::Tile_Layer,
+                               feature: &Feature,
+                               clip_extent: &Extent) {
+        let clipped = feature
+            .geometry()
+            .ok()
+            .and_then(|geom| clip_geometry(&geom, clip_extent));
+        if let Some(geom) = clipped {
+            self.add_feature_geom(mvt_layer, feature, geom);
+        }
+    }
+
+    /// Add feature with geometry `geom` instead of the feature geometry
+    pub fn add_feature_geom(&self,
+                        mut mvt_layer: &mut vector_tile::Tile_Layer,
+                        feature: &Feature,
+                        geom: GeometryType) {
+        if geom.is_empty() {
+            return;
+        }
+        if let GeometryType::GeometryCollection(_) = geom {
+            // MVT has no geometry collections
+            for part in geom.explode() {
+                self.add_feature_geom(&mut *mvt_layer, feature, part);
+            }
+            return;
+        }
         let mut mvt_feature = vector_tile::Tile_Feature::new();
         if let Some(fid) = feature.fid() {
             mvt_feature.set_id(fid);
@@ -261,13 +327,10 @@ impl<'a> Tile<'a> {
                                         attr.key.clone(),
                                         mvt_value);
         }
-        if let Ok(geom) = feature.geometry() {
-            if !geom.is_empty() {
-                mvt_feature.set_field_type(geom.mvt_field_type());
-                mvt_feature.set_geometry(self.encode_geom(geom).vec());
-                mvt_layer.mut_features().push(mvt_feature);
-            }
-        }
+        mvt_feature.set_field_type(geom.mvt_field_type());
+        let layer_extent = mvt_layer.get_extent();
+        mvt_feature.set_geometry(self.encode_geom_extent(geom, layer_extent).vec());
+        mvt_layer.mut_features().push(mvt_feature);
     }
 
     pub fn add_layer(&mut self, mvt_layer: vector_tile::Tile_Layer) {
@@ -290,6 +353,22 @@ impl<'a> Tile<'a> {
         let _ = gz.finish();
     }
 
+    /// Compress encoded tile data
+    pub fn gzip(data: &[u8]) -> Vec<u8> {
+        Self::gzip_level(data, DEFAULT_GZIP_LEVEL)
+    }
+
+    /// Compress encoded tile data with compression level 0, 1, 6 or 9
+    pub fn gzip_level(data: &[u8], level: u32) -> Vec<u8> {
+        let mut v = Vec::new();
+        {
+            let mut gz = GzEncoder::new(&mut v, compression(level));
+            let _ = gz.write_all(data);
+            let _ = gz.finish();
+        }
+        v
+    }
+
     pub fn read_from(fin: &mut Read) -> Result<vector_tile::Tile, ProtobufError> {
         let mut reader = BufReader::new(fin);
         parse_from_reader::<vector_tile::Tile>(&mut reader)
@@ -301,6 +380,54 @@ impl<'a> Tile<'a> {
         parse_from_reader::<vector_tile::Tile>(&mut reader)
     }
 
+    /// Check whether tile contains no features
+    pub fn is_empty(mvt_tile: &vector_tile::Tile) -> bool {
+        mvt_tile
+            .get_layers()
+            .iter()
+            .all(|layer| layer.get_features().is_empty())
+    }
+
+    /// Check whether encoded tile data contains no features
+    pub fn is_empty_data(data: &[u8], gzip: bool) -> bool {
+        let mvt_tile = if gzip {
+            Tile::read_gz_from(&mut &data[..])
+        } else {
+            Tile::read_from(&mut &data[..])
+        };
+        mvt_tile.map(|t| Tile::is_empty(&t)).unwrap_or(false)
+    }
+
+    /// Number of vertices of an encoded feature geometry
+    pub fn vertex_count(geometry: &[u32]) -> u64 {
+        let mut count = 0;
+        let mut i = 0;
+        while i < geometry.len() {
+            let cmd = geometry[i] & 0x7;
+            let n = (geometry[i] >> 3) as usize;
+            i += 1;
+            // MoveTo and LineTo have x/y parameters, ClosePath has none
+            if cmd == 1 || cmd == 2 {
+                count += n as u64;
+                i += 2 * n;
+            }
+        }
+        count
+    }
+
+    /// Decompress gzipped tile data
+    pub fn gunzip(data: &[u8]) -> Vec<u8> {
+        let mut gz = GzDecoder::new(data).unwrap();
+        let mut v = Vec::new();
+        let _ = gz.read_to_end(&mut v);
+        v
+    }
+
+    /// Check for gzip magic bytes. Encoded tiles start with a layer field (0x1a) or are empty.
+    pub fn is_gzip(data: &[u8]) -> bool {
+        data.len() >= 2 && data[0] == 0x1f && data[1] == 0x8b
+    }
+
     pub fn binary_tile(mvt_tile: &vector_tile::Tile) -> Vec<u8> {
         let mut v = Vec::new();
         Self::write_to(&mut v, mvt_tile);
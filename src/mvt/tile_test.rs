@@ -6,7 +6,7 @@
 use core::layer::Layer;
 use core::feature::FeatureAttrValType;
 use core::feature::{FeatureStruct, FeatureAttr};
-use core::grid::Extent;
+use core::grid::{Extent, Grid};
 use core::geom::GeometryType;
 use core::geom;
 use core::screen;
@@ -40,13 +40,13 @@ fn test_point_to_screen_coords() {
     assert_eq!(screen_pt,
                screen::Point {
                    x: 245,
-                   y: i32::MIN,
+                   y: i32::MAX,
                });
     let screen_pt = screen::Point::from_geom(&tile_extent, true, 4096, &point);
     assert_eq!(screen_pt,
                screen::Point {
                    x: 245,
-                   y: i32::MAX,
+                   y: 4096 - i32::MAX,
                });
 }
 
@@ -79,6 +79,237 @@ fn test_read_from_file() {
                &[9, 8236, 4926, 34, 9, 24, 37, 21, 10, 7, 4, 19, 15]);
 }
 
+#[test]
+fn test_tile_size_512() {
+    let grid256 = Grid::web_mercator();
+    let grid512 = Grid::web_mercator().with_tile_size(512);
+    // 512 pixel tiles cover the same area at the same zoom level
+    let extent = grid256.tile_extent(536, 665, 10);
+    assert_eq!(grid512.tile_extent(536, 665, 10), extent);
+
+    let layer = Layer::new("points");
+    let mut geometries = Vec::new();
+    for grid in &[grid256, grid512] {
+        let mut tile = Tile::new(&extent, grid.mvt_extent(), true);
+        let mut mvt_layer = tile.new_layer(&layer);
+        let feature = FeatureStruct {
+            fid: None,
+            attributes: Vec::new(),
+            geometry: GeometryType::Point(geom::Point::new(960000.0, 6002729.0, Some(3857))),
+        };
+        tile.add_feature(&mut mvt_layer, &feature);
+        tile.add_layer(mvt_layer);
+        let mvt_layer = &tile.mvt_tile.get_layers()[0];
+        assert_eq!(mvt_layer.get_extent(), grid.tile_size() * 16);
+        geometries.push(mvt_layer.get_features()[0].get_geometry().to_vec());
+    }
+    // Same position with double precision
+    assert_eq!(geometries[0], [9, 4340, 5062]); // (2170, 2531)
+    assert_eq!(geometries[1], [9, 8682, 10122]); // (4341, 5061)
+}
+
+#[test]
+fn test_clip_geometries() {
+    fn line(coords: &[(f64, f64)]) -> geom::LineString {
+        geom::LineString {
+            points: coords.iter().map(|&(x, y)| geom::Point::new(x, y, None)).collect(),
+            srid: None,
+        }
+    }
+    fn feature(geometry: GeometryType) -> FeatureStruct {
+        FeatureStruct {
+            fid: None,
+            attributes: Vec::new(),
+            geometry: geometry,
+        }
+    }
+
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 100.0,
+        maxy: 100.0,
+    };
+    let clip_extent = Extent {
+        minx: -10.0,
+        miny: -10.0,
+        maxx: 110.0,
+        maxy: 110.0,
+    };
+    let layer = Layer::new("clipped");
+    let mut tile = Tile::new(&extent, 4096, true);
+    let mut mvt_layer = tile.new_layer(&layer);
+    let features = vec![// Polygon crossing the tile corner
+                        GeometryType::Polygon(geom::Polygon {
+                                                  rings: vec![line(&[(50.0, 50.0),
+                                                                     (200.0, 50.0),
+                                                                     (200.0, 200.0),
+                                                                     (50.0, 200.0),
+                                                                     (50.0, 50.0)])],
+                                                  srid: None,
+                                              }),
+                        // Polygon touching the buffer boundary only
+                        GeometryType::Polygon(geom::Polygon {
+                                                  rings: vec![line(&[(110.0, 0.0),
+                                                                     (120.0, 0.0),
+                                                                     (120.0, 10.0),
+                                                                     (110.0, 10.0),
+                                                                     (110.0, 0.0)])],
+                                                  srid: None,
+                                              }),
+                        // Line leaving and re-entering the tile
+                        GeometryType::LineString(line(&[(50.0, 50.0),
+                                                        (200.0, 50.0),
+                                                        (200.0, 80.0),
+                                                        (50.0, 80.0)])),
+                        GeometryType::Point(geom::Point::new(105.0, 50.0, None)),
+                        GeometryType::Point(geom::Point::new(150.0, 50.0, None))];
+    for geometry in features {
+        tile.add_feature_clipped(&mut mvt_layer, &feature(geometry), &clip_extent);
+    }
+    tile.add_layer(mvt_layer);
+
+    let expected = vec![GeometryType::Polygon(geom::Polygon {
+                                                  rings: vec![line(&[(50.0, 110.0),
+                                                                     (50.0, 50.0),
+                                                                     (110.0, 50.0),
+                                                                     (110.0, 110.0),
+                                                                     (50.0, 110.0)])],
+                                                  srid: None,
+                                              }),
+                        GeometryType::MultiLineString(geom::MultiLineString {
+                                                          lines: vec![line(&[(50.0, 50.0),
+                                                                             (110.0, 50.0)]),
+                                                                      line(&[(110.0, 80.0),
+                                                                             (50.0, 80.0)])],
+                                                          srid: None,
+                                                      }),
+                        GeometryType::Point(geom::Point::new(105.0, 50.0, None))];
+    let mvt_features = tile.mvt_tile.get_layers()[0].get_features();
+    assert_eq!(mvt_features.len(), expected.len());
+    for (mvt_feature, geometry) in mvt_features.iter().zip(expected) {
+        assert_eq!(mvt_feature.get_field_type(), geometry.mvt_field_type());
+        assert_eq!(mvt_feature.get_geometry(), &tile.encode_geom(geometry).vec()[..]);
+    }
+}
+
+#[test]
+fn test_geometry_collection() {
+    use postgis::ewkb;
+
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 100.0,
+        maxy: 100.0,
+    };
+    let line = geom::LineString {
+        points: vec![geom::Point::new(10.0, 10.0, None), geom::Point::new(20.0, 20.0, None)],
+        srid: None,
+    };
+    let feature = FeatureStruct {
+        fid: Some(1),
+        attributes: Vec::new(),
+        geometry: GeometryType::GeometryCollection(geom::GeometryCollection {
+            geometries: vec![ewkb::GeometryT::Point(geom::Point::new(50.0, 50.0, None)),
+                             ewkb::GeometryT::LineString(line)],
+        }),
+    };
+    let layer = Layer::new("collection");
+    let mut tile = Tile::new(&extent, 4096, true);
+    let mut mvt_layer = tile.new_layer(&layer);
+    tile.add_feature(&mut mvt_layer, &feature);
+    tile.add_layer(mvt_layer);
+
+    // Components are encoded as separate features
+    let mvt_features = tile.mvt_tile.get_layers()[0].get_features();
+    assert_eq!(mvt_features.len(), 2);
+    assert_eq!(mvt_features[0].get_field_type(),
+               vector_tile::Tile_GeomType::POINT);
+    assert_eq!(mvt_features[1].get_field_type(),
+               vector_tile::Tile_GeomType::LINESTRING);
+    assert_eq!(mvt_features[1].get_id(), 1);
+}
+
+#[test]
+fn test_gunzip() {
+    let mut f = File::open("src/test/tile.pbf").unwrap();
+    let tile = Tile::read_from(&mut f).unwrap();
+    let mut tilegz = Vec::new();
+    Tile::write_gz_to(&mut tilegz, &tile);
+    assert_eq!(Tile::gunzip(&tilegz), Tile::binary_tile(&tile));
+    assert!(Tile::is_gzip(&tilegz));
+    assert!(!Tile::is_gzip(&Tile::binary_tile(&tile)));
+    assert!(!Tile::is_gzip(&[]));
+    assert_eq!(Tile::gunzip(&Tile::gzip(&Tile::binary_tile(&tile))),
+               Tile::binary_tile(&tile));
+}
+
+#[test]
+fn test_gzip_level() {
+    use mvt::tile::GZIP_LEVELS;
+
+    let mut f = File::open("src/test/tile.pbf").unwrap();
+    let data = Tile::binary_tile(&Tile::read_from(&mut f).unwrap());
+    let mut sizes = Vec::new();
+    for level in &GZIP_LEVELS {
+        let tilegz = Tile::gzip_level(&data, *level);
+        assert_eq!(Tile::gunzip(&tilegz), data);
+        sizes.push(tilegz.len());
+    }
+    assert!(sizes[0] > data.len());
+    assert!(sizes[1] < data.len());
+    assert!(sizes[3] <= sizes[2] && sizes[2] <= sizes[1]);
+}
+
+/// Run with `cargo test --release bench_gzip_level -- --ignored --nocapture`
+#[test]
+#[ignore]
+fn bench_gzip_level() {
+    use mvt::tile::GZIP_LEVELS;
+    use std::time::Instant;
+
+    let mut f = File::open("src/test/tile.pbf").unwrap();
+    let data = Tile::binary_tile(&Tile::read_from(&mut f).unwrap());
+    let rounds = 1000;
+    for level in &GZIP_LEVELS {
+        let start = Instant::now();
+        let mut size = 0;
+        for _ in 0..rounds {
+            size = Tile::gzip_level(&data, *level).len();
+        }
+        println!("gzip level {}: {} -> {} bytes, {:?}/tile",
+                 level,
+                 data.len(),
+                 size,
+                 start.elapsed() / rounds);
+    }
+}
+
+#[test]
+fn test_empty_tile() {
+    let mut f = File::open("src/test/tile.pbf").unwrap();
+    let tile = Tile::read_from(&mut f).unwrap();
+    assert!(!Tile::is_empty(&tile));
+    let mut tilegz = Vec::new();
+    Tile::write_gz_to(&mut tilegz, &tile);
+    assert!(!Tile::is_empty_data(&tilegz, true));
+
+    let extent = Extent {
+        minx: 958826.08,
+        miny: 5987771.04,
+        maxx: 978393.96,
+        maxy: 6007338.92,
+    };
+    let mut tile = Tile::new(&extent, 4096, true);
+    let layer = Layer::new("empty");
+    let mvt_layer = tile.new_layer(&layer);
+    tile.add_layer(mvt_layer);
+    assert!(Tile::is_empty(&tile.mvt_tile));
+    let data = Tile::binary_tile(&tile.mvt_tile);
+    assert!(Tile::is_empty_data(&data, false));
+}
+
 
 // https://github.com/mapbox/vector-tile-spec/tree/master/2.1#45-example
 #[cfg(test)]
@@ -86,13 +317,13 @@ const TILE_EXAMPLE: &'static str = r#"Tile {
     layers: [
         Tile_Layer {
             version: Some(
-                2
+                2,
             ),
             name: Some("points"),
             features: [
                 Tile_Feature {
                     id: Some(
-                        1
+                        1,
                     ),
                     tags: [
                         0,
@@ -100,53 +331,53 @@ const TILE_EXAMPLE: &'static str = r#"Tile {
                         1,
                         0,
                         2,
-                        1
+                        1,
                     ],
                     field_type: Some(
-                        POINT
+                        POINT,
                     ),
                     geometry: [
                         9,
                         490,
-                        6262
+                        6262,
                     ],
                     unknown_fields: UnknownFields {
-                        fields: None
+                        fields: None,
                     },
                     cached_size: Cell {
-                        value: 0
-                    }
+                        value: 0,
+                    },
                 },
                 Tile_Feature {
                     id: Some(
-                        2
+                        2,
                     ),
                     tags: [
                         0,
                         2,
                         2,
-                        3
+                        3,
                     ],
                     field_type: Some(
-                        POINT
+                        POINT,
                     ),
                     geometry: [
                         9,
                         490,
-                        6262
+                        6262,
                     ],
                     unknown_fields: UnknownFields {
-                        fields: None
+                        fields: None,
                     },
                     cached_size: Cell {
-                        value: 0
-                    }
-                }
+                        value: 0,
+                    },
+                },
             ],
             keys: [
                 "hello",
                 "h",
-                "count"
+                "count",
             ],
             values: [
                 Tile_Value {
@@ -158,28 +389,28 @@ const TILE_EXAMPLE: &'static str = r#"Tile {
                     sint_value: None,
                     bool_value: None,
                     unknown_fields: UnknownFields {
-                        fields: None
+                        fields: None,
                     },
                     cached_size: Cell {
-                        value: 0
-                    }
+                        value: 0,
+                    },
                 },
                 Tile_Value {
                     string_value: None,
                     float_value: None,
                     double_value: Some(
-                        1.23
+                        1.23,
                     ),
                     int_value: None,
                     uint_value: None,
                     sint_value: None,
                     bool_value: None,
                     unknown_fields: UnknownFields {
-                        fields: None
+                        fields: None,
                     },
                     cached_size: Cell {
-                        value: 0
-                    }
+                        value: 0,
+                    },
                 },
                 Tile_Value {
                     string_value: Some("again"),
@@ -190,47 +421,47 @@ const TILE_EXAMPLE: &'static str = r#"Tile {
                     sint_value: None,
                     bool_value: None,
                     unknown_fields: UnknownFields {
-                        fields: None
+                        fields: None,
                     },
                     cached_size: Cell {
-                        value: 0
-                    }
+                        value: 0,
+                    },
                 },
                 Tile_Value {
                     string_value: None,
                     float_value: None,
                     double_value: None,
                     int_value: Some(
-                        2
+                        2,
                     ),
                     uint_value: None,
                     sint_value: None,
                     bool_value: None,
                     unknown_fields: UnknownFields {
-                        fields: None
+                        fields: None,
                     },
                     cached_size: Cell {
-                        value: 0
-                    }
-                }
+                        value: 0,
+                    },
+                },
             ],
             extent: Some(
-                4096
+                4096,
             ),
             unknown_fields: UnknownFields {
-                fields: None
+                fields: None,
             },
             cached_size: Cell {
-                value: 0
-            }
-        }
+                value: 0,
+            },
+        },
     ],
     unknown_fields: UnknownFields {
-        fields: None
+        fields: None,
     },
     cached_size: Cell {
-        value: 0
-    }
+        value: 0,
+    },
 }"#;
 
 #[test]
@@ -350,3 +581,14 @@ fn test_build_mvt_with_helpers() {
     path.push("out.pbf");
     tile.to_file(&format!("{}", &path.display()));
 }
+
+#[test]
+fn test_vertex_count() {
+    // MoveTo(25,17)
+    assert_eq!(Tile::vertex_count(&[9, 50, 34]), 1);
+    // MoveTo(2,2) LineTo(2,10)(10,10)
+    assert_eq!(Tile::vertex_count(&[9, 4, 4, 18, 0, 16, 16, 0]), 3);
+    // Polygon: MoveTo(3,6) LineTo(8,12)(20,34) ClosePath
+    assert_eq!(Tile::vertex_count(&[9, 6, 12, 18, 10, 12, 24, 44, 15]), 3);
+    assert_eq!(Tile::vertex_count(&[]), 0);
+}
@@ -0,0 +1,119 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+
+/// Upper bounds of tile generation latency histogram buckets in seconds
+const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+#[derive(Default)]
+struct TilesetMetrics {
+    requests: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+    stale_served: u64,
+    revalidations: u64,
+    bytes_served: u64,
+    /// Cumulative bucket counts
+    latency_buckets: [u64; 11],
+    latency_sum: f64,
+    latency_count: u64,
+}
+
+/// Tile service counters exposed in Prometheus text format
+pub struct Metrics {
+    tilesets: Mutex<BTreeMap<String, TilesetMetrics>>,
+}
+
+impl Metrics {
+    pub fn new() -> Metrics {
+        Metrics { tilesets: Mutex::new(BTreeMap::new()) }
+    }
+    fn update<F>(&self, tileset: &str, update: F)
+        where F: FnOnce(&mut TilesetMetrics)
+    {
+        let mut tilesets = self.tilesets.lock().unwrap();
+        update(tilesets
+                   .entry(tileset.to_string())
+                   .or_insert(TilesetMetrics::default()));
+    }
+    pub fn inc_requests(&self, tileset: &str) {
+        self.update(tileset, |m| m.requests += 1);
+    }
+    pub fn inc_cache_hits(&self, tileset: &str) {
+        self.update(tileset, |m| m.cache_hits += 1);
+    }
+    pub fn inc_cache_misses(&self, tileset: &str) {
+        self.update(tileset, |m| m.cache_misses += 1);
+    }
+    pub fn inc_stale_served(&self, tileset: &str) {
+        self.update(tileset, |m| m.stale_served += 1);
+    }
+    pub fn inc_revalidations(&self, tileset: &str) {
+        self.update(tileset, |m| m.revalidations += 1);
+    }
+    pub fn add_bytes_served(&self, tileset: &str, bytes: u64) {
+        self.update(tileset, |m| m.bytes_served += bytes);
+    }
+    /// Record tile generation latency in seconds
+    pub fn observe_generation(&self, tileset: &str, seconds: f64) {
+        self.update(tileset, |m| {
+            for (i, le) in LATENCY_BUCKETS.iter().enumerate() {
+                if seconds <= *le {
+                    m.latency_buckets[i] += 1;
+                }
+            }
+            m.latency_sum += seconds;
+            m.latency_count += 1;
+        });
+    }
+    /// Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let tilesets = self.tilesets.lock().unwrap();
+        let mut out = String::new();
+        let counters: [(&str, &str, fn(&TilesetMetrics) -> u64); 6] =
+            [("trex_tile_requests_total", "Total number of tile requests", |m| m.requests),
+             ("trex_cache_hits_total", "Number of tiles read from cache", |m| m.cache_hits),
+             ("trex_cache_misses_total", "Number of tiles not found in cache", |m| m.cache_misses),
+             ("trex_cache_stale_served_total", "Number of expired tiles served from cache", |m| m.stale_served),
+             ("trex_cache_revalidations_total", "Number of expired tiles regenerated in the background", |m| m.revalidations),
+             ("trex_bytes_served_total", "Number of tile bytes sent", |m| m.bytes_served)];
+        for &(name, help, value) in counters.iter() {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            for (tileset, m) in tilesets.iter() {
+                let _ = writeln!(out, "{}{{tileset=\"{}\"}} {}", name, tileset, value(m));
+            }
+        }
+        let name = "trex_tile_generation_seconds";
+        let _ = writeln!(out, "# HELP {} Tile generation latency (query and encoding)", name);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        for (tileset, m) in tilesets.iter() {
+            for (i, le) in LATENCY_BUCKETS.iter().enumerate() {
+                let _ = writeln!(out,
+                                 "{}_bucket{{tileset=\"{}\",le=\"{}\"}} {}",
+                                 name,
+                                 tileset,
+                                 le,
+                                 m.latency_buckets[i]);
+            }
+            let _ = writeln!(out,
+                             "{}_bucket{{tileset=\"{}\",le=\"+Inf\"}} {}",
+                             name,
+                             tileset,
+                             m.latency_count);
+            let _ = writeln!(out, "{}_sum{{tileset=\"{}\"}} {}", name, tileset, m.latency_sum);
+            let _ = writeln!(out,
+                             "{}_count{{tileset=\"{}\"}} {}",
+                             name,
+                             tileset,
+                             m.latency_count);
+        }
+        out
+    }
+}
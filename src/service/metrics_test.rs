@@ -0,0 +1,36 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use service::metrics::Metrics;
+
+
+#[test]
+fn test_metrics() {
+    let metrics = Metrics::new();
+    metrics.inc_requests("osm");
+    metrics.inc_requests("osm");
+    metrics.inc_cache_hits("osm");
+    metrics.inc_cache_misses("osm");
+    metrics.add_bytes_served("osm", 1024);
+    metrics.inc_stale_served("osm");
+    metrics.observe_generation("osm", 0.03);
+    metrics.inc_requests("admin");
+
+    let text = metrics.to_prometheus();
+    assert!(text.contains("# TYPE trex_tile_requests_total counter\n"));
+    assert!(text.contains("trex_tile_requests_total{tileset=\"osm\"} 2\n"));
+    assert!(text.contains("trex_tile_requests_total{tileset=\"admin\"} 1\n"));
+    assert!(text.contains("trex_cache_hits_total{tileset=\"osm\"} 1\n"));
+    assert!(text.contains("trex_cache_misses_total{tileset=\"osm\"} 1\n"));
+    assert!(text.contains("trex_bytes_served_total{tileset=\"osm\"} 1024\n"));
+    assert!(text.contains("trex_cache_stale_served_total{tileset=\"osm\"} 1\n"));
+    assert!(text.contains("trex_cache_revalidations_total{tileset=\"osm\"} 0\n"));
+    assert!(text.contains("# TYPE trex_tile_generation_seconds histogram\n"));
+    assert!(text.contains("trex_tile_generation_seconds_bucket{tileset=\"osm\",le=\"0.025\"} 0\n"));
+    assert!(text.contains("trex_tile_generation_seconds_bucket{tileset=\"osm\",le=\"0.05\"} 1\n"));
+    assert!(text.contains("trex_tile_generation_seconds_bucket{tileset=\"osm\",le=\"+Inf\"} 1\n"));
+    assert!(text.contains("trex_tile_generation_seconds_count{tileset=\"osm\"} 1\n"));
+    assert!(text.contains("trex_tile_generation_seconds_count{tileset=\"admin\"} 0\n"));
+}
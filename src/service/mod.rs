@@ -5,8 +5,26 @@
 
 pub mod mvt;
 pub mod glstyle_converter;
+pub mod metrics;
+pub mod pmtiles;
+pub mod revalidate;
+pub mod singleflight;
+pub mod utfgrid;
+pub mod wmts;
 
 #[cfg(test)]
-mod mvt_test;
+pub mod mvt_test;
 #[cfg(test)]
 mod glstyle_converter_test;
+#[cfg(test)]
+mod metrics_test;
+#[cfg(test)]
+mod pmtiles_test;
+#[cfg(test)]
+mod revalidate_test;
+#[cfg(test)]
+mod singleflight_test;
+#[cfg(test)]
+mod utfgrid_test;
+#[cfg(test)]
+mod wmts_test;
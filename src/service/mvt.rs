@@ -3,41 +3,215 @@
 // Licensed under the MIT License. See LICENSE file in the project root for full license information.
 //
 
-use datasource::{Datasource, DatasourceInput};
-use datasource::PostgisInput;
+use datasource::DatasourceInput;
+use datasource::{Datasource, Datasources};
+use datasource::geojson::{feature_geometry_to_json, wgs84_inverse_transformation};
+use datasource::{take_query_timeout, take_query_error};
+use core::clip::clip_geometry;
 use core::grid::{Grid, Extent, ExtentInt};
+use core::geom::GeometryType;
+use core::feature::{Feature, TaggedFeature};
 use core::layer::Layer;
 use core::Config;
-use mvt::tile::Tile;
+use mvt::tile::{Tile, DEFAULT_GZIP_LEVEL, DEFAULT_GZIP_MIN_BYTES, GZIP_LEVELS};
 use mvt::vector_tile;
-use cache::{Cache, Tilecache};
+use cache::{Cache, Tilecache, Nocache, Mbtilescache};
+use service::metrics::Metrics;
+use service::pmtiles::{PmtilesWriter, zxy_to_tile_id};
+use service::revalidate::{Revalidator, DEFAULT_REVALIDATION_THREADS};
+use service::singleflight::SingleFlight;
+use service::utfgrid::{self, UtfGrid};
+use rusqlite::Connection;
 use std::path::Path;
 use std::fs::{self, File};
 use toml;
 use serde_json;
 use pbr::ProgressBar;
-use std::io::Stdout;
+use std::io::{self, BufRead, Stdout, Write};
+use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::any::Any;
+use std::cmp;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::collections::BTreeMap;
+use scoped_threadpool::Pool;
 
 
 /// Collection of layers in one MVT
 pub struct Tileset {
     pub name: String,
     pub layers: Vec<Layer>,
+    /// Extent of tileset data in WGS84 coordinates (configured or computed from data)
+    pub extent: Option<Extent>,
+    /// Show tileset in built-in viewer (global `viewer` setting if None)
+    pub viewer: Option<bool>,
+    /// TileJSON attribution (HTML)
+    pub attribution: Option<String>,
+    /// TileJSON description (tileset name if None)
+    pub description: Option<String>,
+    /// Tile URLs in TMS adressing scheme (`scheme = "tms"`)
+    pub tms: bool,
+    /// Cache backend of this tileset (service cache if None). Nocache for `cache = false`.
+    pub cache: Option<Tilecache>,
+}
+
+/// Origin of a tile returned by `MvtService::tile_cached_or_stale`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TileSource {
+    /// Valid tile from cache
+    Cache,
+    /// Newly created tile
+    Generated,
+    /// Expired tile from cache served after a query timeout (`serve_stale_on_timeout`)
+    StaleOnTimeout,
+    /// Expired tile from cache, which is regenerated in the background (`stale_while_revalidate`)
+    StaleRevalidate,
+    /// Newly created tile with empty layers after a query timeout, which is not cached
+    Incomplete,
+}
+
+impl TileSource {
+    pub fn is_stale(&self) -> bool {
+        *self == TileSource::StaleOnTimeout || *self == TileSource::StaleRevalidate
+    }
+}
+
+/// Failed tile generation
+#[derive(Clone, PartialEq, Debug)]
+pub enum TileError {
+    /// Datasource query failed
+    Datasource(String),
+    /// Tile encoding failed
+    Generation(String),
+}
+
+impl TileError {
+    pub fn http_status(&self) -> u16 {
+        match *self {
+            TileError::Datasource(_) => 502,
+            TileError::Generation(_) => 500,
+        }
+    }
+}
+
+impl fmt::Display for TileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TileError::Datasource(ref err) |
+            TileError::Generation(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// Message of a caught panic
+pub fn panic_message(cause: &(Any + Send)) -> String {
+    if let Some(msg) = cause.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = cause.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "Tile generation panicked".to_string()
+    }
 }
 
 /// Mapbox Vector Tile Service
 pub struct MvtService {
-    pub input: PostgisInput,
+    pub datasources: Datasources,
     pub grid: Grid,
     pub tilesets: Vec<Tileset>,
     pub cache: Tilecache,
+    /// Write tiles without features into cache
+    pub cache_empty_tiles: bool,
+    /// Compute tileset extents without configured extent from data
+    pub compute_extent: bool,
+    /// Request and cache statistics (disabled if None)
+    pub metrics: Option<Metrics>,
+    /// Compression level of generated tiles (0, 1, 6 or 9)
+    pub gzip_level: u32,
+    /// Generated tiles smaller than this size are cached and sent uncompressed
+    pub gzip_min_bytes: usize,
+    /// Datasource queries are prepared
+    pub prepared: bool,
+    /// Serve expired cached tiles when tile queries exceed `query_timeout_ms`
+    pub serve_stale_on_timeout: bool,
+    /// Background regeneration of expired cached tiles (stale-while-revalidate)
+    pub revalidator: Option<Revalidator>,
+    /// Running tile generations, shared by concurrent requests for the same tile
+    pub inflight: SingleFlight<Result<(Vec<u8>, TileSource), TileError>>,
+    /// Built-in viewer enabled for tilesets without `viewer` setting
+    pub viewer: bool,
 }
 
+/// Version of index.json structure
+const METADATA_VERSION: u32 = 2;
+
+const MBTILES_GRID_ERROR: &'static str = "MBTiles requires a grid with 2^z x 2^z tiles at zoom level z (e.g. web_mercator)";
+
 type JsonResult = Result<serde_json::Value, serde_json::error::Error>;
 
 impl MvtService {
+    /// Service without cache and with default settings
+    pub fn new(datasources: Datasources, grid: Grid, tilesets: Vec<Tileset>) -> MvtService {
+        MvtService {
+            datasources: datasources,
+            grid: grid,
+            tilesets: tilesets,
+            cache: Tilecache::Nocache(Nocache),
+            cache_empty_tiles: false,
+            compute_extent: false,
+            metrics: None,
+            gzip_level: DEFAULT_GZIP_LEVEL,
+            gzip_min_bytes: 0,
+            prepared: false,
+            serve_stale_on_timeout: false,
+            revalidator: None,
+            inflight: SingleFlight::new(),
+            viewer: true,
+        }
+    }
     pub fn connect(&mut self) {
-        self.input = self.input.connected();
+        self.datasources = self.datasources.connected();
+    }
+    /// Connect datasources and check layer tables and queries. Returns all problems found.
+    pub fn check(&mut self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut datasources = Datasources::new();
+        for (name, input) in &self.datasources.datasources {
+            match input.try_connected() {
+                Ok(input) => datasources.add(name, input),
+                Err(err) => errors.push(format!("Datasource '{}': {}", name, err)),
+            }
+        }
+        datasources.default = self.datasources.default.clone();
+        self.datasources = datasources;
+        errors.extend(self.check_layers());
+        errors
+    }
+    /// Check layer tables, geometry columns and queries of connected datasources
+    pub fn check_layers(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for tileset in &self.tilesets {
+            for layer in tileset.layers.iter().flat_map(|l| l.input_layers()) {
+                // Layers of datasources with connection errors are skipped
+                if let Some(input) = self.datasources.for_layer(&layer) {
+                    if let Err(err) = input.check_layer(&layer, &self.grid) {
+                        errors.push(format!("Tileset '{}', layer '{}': {}",
+                                            tileset.name,
+                                            layer.name,
+                                            err));
+                    }
+                }
+            }
+        }
+        errors
+    }
+    /// Datasource of layer. Layer datasources are checked when reading the configuration.
+    fn input(&self, layer: &Layer) -> &Datasource {
+        self.datasources
+            .for_layer(layer)
+            .expect("Layer datasource not found")
     }
     fn get_tileset(&self, name: &str) -> Vec<&Layer> {
         let tileset = self.tilesets.iter().find(|t| t.name == name);
@@ -46,10 +220,89 @@ impl MvtService {
             None => Vec::new(),
         }
     }
+    /// Retrieve features of a layer. Features of combined layers are tagged with their source.
+    fn retrieve_features<F>(&self, layer: &Layer, extent: &Extent, zoom: u8, mut read: F)
+        where F: FnMut(&Feature)
+    {
+        for input_layer in layer.input_layers() {
+            // Tables of other zoom levels
+            if !input_layer.in_zoom_range(zoom) {
+                continue;
+            }
+            let tag = input_layer.source_tag();
+            self.input(&input_layer)
+                .retrieve_features(&input_layer, extent, zoom, &self.grid, |feat| match tag {
+                    Some(ref tag) => {
+                        read(&TaggedFeature {
+                                  feature: feat,
+                                  tag: tag,
+                              })
+                    }
+                    None => read(feat),
+                });
+        }
+    }
+    /// Attribute fields with types of a layer. Combined layers have the fields of all sources.
+    fn detect_field_types(&self, layer: &Layer) -> Result<BTreeMap<String, String>, String> {
+        let mut fields = BTreeMap::new();
+        for input_layer in layer.input_layers() {
+            let query = input_layer.query(input_layer.maxzoom());
+            let types = try!(self.input(&input_layer).detect_field_types(&input_layer, query));
+            for (field, field_type) in types {
+                fields.entry(field).or_insert(field_type);
+            }
+        }
+        if !layer.source.is_empty() {
+            fields.insert(layer.source_field().to_string(), "String".to_string());
+        }
+        Ok(fields)
+    }
+    /// Attribute columns of a layer. Combined layers have the columns of all sources.
+    fn detect_data_columns(&self, layer: &Layer) -> Vec<String> {
+        let mut cols = Vec::new();
+        for input_layer in layer.input_layers() {
+            let query = input_layer.query(input_layer.maxzoom());
+            for (col, _) in self.input(&input_layer).detect_data_columns(&input_layer, query) {
+                if !cols.contains(&col) {
+                    cols.push(col);
+                }
+            }
+        }
+        if !layer.source.is_empty() {
+            cols.push(layer.source_field().to_string());
+        }
+        cols
+    }
+    /// Built-in viewer enabled for tileset
+    pub fn has_viewer(&self, tileset: &Tileset) -> bool {
+        tileset.viewer.unwrap_or(self.viewer)
+    }
+    /// Built-in viewer enabled for any tileset
+    pub fn viewer_enabled(&self) -> bool {
+        self.tilesets.iter().any(|set| self.has_viewer(set))
+    }
+    /// Check that queries are prepared and all datasources are reachable
+    pub fn readiness(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        if !self.prepared {
+            errors.push("Feature queries not prepared".to_string());
+        }
+        for (name, ds) in &self.datasources.datasources {
+            if let Err(err) = ds.ping() {
+                errors.push(format!("Datasource '{}': {}", name, err));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
     /// Service metadata for backend web application
     pub fn get_mvt_metadata(&self) -> JsonResult {
         #[derive(Serialize)]
         struct MvtInfo {
+            version: u32,
             tilesets: Vec<TilesetInfo>,
         }
         #[derive(Serialize)]
@@ -59,11 +312,15 @@ impl MvtService {
             tileurl: String,
             layers: Vec<LayerInfo>,
             supported: bool,
+            viewer: bool,
         }
         #[derive(Serialize)]
         struct LayerInfo {
             name: String,
             geometry_type: Option<String>,
+            minzoom: u8,
+            maxzoom: u8,
+            fields: BTreeMap<String, String>,
         }
 
         let mut tileset_infos: Vec<TilesetInfo> = self.tilesets
@@ -72,9 +329,17 @@ impl MvtService {
                 let layerinfos = set.layers
                     .iter()
                     .map(|l| {
+                             let fields = self.detect_field_types(l)
+                                 .unwrap_or_else(|err| {
+                                                     warn!("Layer '{}': {}", l.name, err);
+                                                     BTreeMap::new()
+                                                 });
                              LayerInfo {
                                  name: l.name.clone(),
                                  geometry_type: l.geometry_type.clone(),
+                                 minzoom: l.minzoom(),
+                                 maxzoom: l.maxzoom(),
+                                 fields: fields,
                              }
                          })
                     .collect();
@@ -91,28 +356,59 @@ impl MvtService {
                     tileurl: format!("/{}/{{z}}/{{x}}/{{y}}.pbf", set.name),
                     layers: layerinfos,
                     supported: supported,
+                    viewer: self.has_viewer(set),
                 }
             })
             .collect();
         tileset_infos.sort_by_key(|ti| ti.name.clone());
-        let mvt_info = MvtInfo { tilesets: tileset_infos };
+        let mvt_info = MvtInfo {
+            version: METADATA_VERSION,
+            tilesets: tileset_infos,
+        };
         serde_json::to_value(mvt_info)
     }
     fn get_tilejson_metadata(&self, tileset: &str) -> JsonResult {
-        Ok(json!({
+        let set = self.tilesets.iter().find(|t| t.name == tileset);
+        let data_extent = set.and_then(|t| t.extent.as_ref());
+        let description = set.and_then(|t| t.description.clone())
+            .unwrap_or(tileset.to_string());
+        let attribution = set.and_then(|t| t.attribution.clone())
+            .unwrap_or("".to_string());
+        let scheme = if set.map_or(false, |t| t.tms) {
+            "tms"
+        } else {
+            "xyz"
+        };
+        let (bounds, center) = match data_extent.or(self.grid.bounds.as_ref()) {
+            Some(b) => {
+                (json!([b.minx, b.miny, b.maxx, b.maxy]),
+                 json!([(b.minx + b.maxx) / 2.0, (b.miny + b.maxy) / 2.0, extent_zoom(b)]))
+            }
+            None => (json!([-180.0,-90.0,180.0,90.0]), json!([0.0, 0.0, 2])),
+        };
+        let mut metadata = json!({
             "id": tileset,
             "name": tileset,
-            "description": tileset,
-            "attribution": "",
+            "description": description,
+            "attribution": attribution,
             "format": "pbf",
             "version": "2.0.0",
-            "scheme": "xyz",
-            "bounds": [-180.0,-90.0,180.0,90.0], //TODO: bbox from data
+            "scheme": scheme,
+            "bounds": bounds,
             "minzoom": 0,  //TODO: make configurable
             "maxzoom": 14,  //TODO: make configurable
-            "center": [0.0, 0.0, 2], //TODO: make configurable
+            "center": center,
             "basename": tileset
-        }))
+        });
+        if self.grid.srid != 3857 {
+            // Non-standard extension for clients supporting other projections
+            metadata["crs"] = json!(format!("EPSG:{}", self.grid.srid));
+        }
+        if self.grid.tile_size() != 256 {
+            // Tiles cover the same area as 256 pixel tiles with zoom level reduced by one
+            metadata["tileSize"] = json!(self.grid.tile_size());
+        }
+        Ok(metadata)
     }
     fn get_tilejson_layers(&self, tileset: &str) -> JsonResult {
         let layers = self.get_tileset(tileset);
@@ -120,7 +416,6 @@ impl MvtService {
             .iter()
             .map(|layer| {
                 let meta = layer.metadata();
-                let query = layer.query(layer.maxzoom());
                 let mut meta_json = json!({
                 "id": meta.get("id").unwrap(),
                 "name": meta.get("name").unwrap(),
@@ -133,9 +428,11 @@ impl MvtService {
                 },
                 "fields": {}
             });
+                if self.grid.srid != 3857 {
+                    meta_json["srs"] = json!(format!("+init=epsg:{}", self.grid.srid));
+                }
                 //insert fields
-                let fields = self.input.detect_data_columns(&layer, query);
-                for (ref field, _) in fields {
+                for field in self.detect_data_columns(layer) {
                     meta_json["fields"]
                         .as_object_mut()
                         .unwrap()
@@ -152,7 +449,6 @@ impl MvtService {
             .iter()
             .map(|layer| {
                 let meta = layer.metadata();
-                let query = layer.query(layer.maxzoom());
                 let mut layer_json = json!({
                 "id": meta.get("id").unwrap(),
                 "description": meta.get("description").unwrap(),
@@ -161,13 +457,15 @@ impl MvtService {
                 "fields": {}
             });
                 //insert fields
-                let fields = self.input.detect_data_columns(&layer, query);
-                for (ref field, _) in fields {
+                for field in self.detect_data_columns(layer) {
                     layer_json["fields"]
                         .as_object_mut()
                         .unwrap()
                         .insert(field.clone(), json!(""));
                 }
+                if let Some(ref fid_field) = layer.fid_field {
+                    layer_json["fid_field"] = json!(fid_field);
+                }
                 layer_json
             })
             .collect();
@@ -180,11 +478,37 @@ impl MvtService {
         let url = json!([
             format!("{}/{}/{{z}}/{{x}}/{{y}}.pbf", baseurl, tileset)
         ]);
-        let mut obj = metadata.as_object_mut().unwrap();
+        let obj = metadata.as_object_mut().unwrap();
         obj.insert("tiles".to_string(), url);
         obj.insert("vector_layers".to_string(), vector_layers);
         Ok(json!(obj))
     }
+    /// Error record with available tilesets for an unknown tileset name, `None` if the tileset exists
+    pub fn unknown_tileset(&self, tileset: &str) -> Option<serde_json::Value> {
+        if self.tilesets.iter().any(|set| set.name == tileset) {
+            return None;
+        }
+        let mut tilesets: Vec<&str> = self.tilesets.iter().map(|set| set.name.as_str()).collect();
+        tilesets.sort();
+        Some(json!({
+            "error": format!("Unknown tileset '{}'", tileset),
+            "tilesets": tilesets
+        }))
+    }
+    /// TileJSON and style URLs of all tilesets
+    pub fn get_tileset_index(&self, baseurl: &str) -> JsonResult {
+        let index: Vec<serde_json::Value> = self.tilesets
+            .iter()
+            .map(|set| {
+                json!({
+                    "name": set.name,
+                    "tilejson_url": format!("{}/{}.json", baseurl, set.name),
+                    "style_url": format!("{}/{}.style.json", baseurl, set.name)
+                })
+            })
+            .collect();
+        Ok(json!(index))
+    }
     /// MapboxGL Style JSON (https://www.mapbox.com/mapbox-gl-style-spec/)
     pub fn get_stylejson(&self, baseurl: &str, tileset: &str) -> JsonResult {
         let mut stylejson = json!({
@@ -211,6 +535,13 @@ impl MvtService {
             "background-color": "rgba(255, 255, 255, 1)"
           }
         }); // TODO: from global style
+        let attribution = self.tilesets
+            .iter()
+            .find(|t| t.name == tileset)
+            .and_then(|t| t.attribution.clone());
+        if let Some(attribution) = attribution {
+            stylejson["sources"][tileset]["attribution"] = json!(attribution);
+        }
         let layers = self.get_tileset(tileset);
         let mut layer_styles: Vec<serde_json::Value> = layers
             .iter()
@@ -248,13 +579,28 @@ impl MvtService {
                     .unwrap()
                     .entry("type".to_string())
                     .or_insert(json!(default_type));
+                if let Some(minzoom) = layer.minzoom {
+                    layerjson
+                        .as_object_mut()
+                        .unwrap()
+                        .entry("minzoom".to_string())
+                        .or_insert(json!(minzoom));
+                }
+                if let Some(maxzoom) = layer.maxzoom {
+                    // Style maxzoom is exclusive
+                    layerjson
+                        .as_object_mut()
+                        .unwrap()
+                        .entry("maxzoom".to_string())
+                        .or_insert(json!(maxzoom + 1));
+                }
 
                 layerjson
             })
             .collect();
         layer_styles.insert(0, background_layer);
         // Insert layers in stylejson
-        let mut obj = stylejson.as_object_mut().unwrap();
+        let obj = stylejson.as_object_mut().unwrap();
         obj.insert("layers".to_string(), json!(layer_styles));
         Ok(json!(obj))
     }
@@ -270,84 +616,554 @@ impl MvtService {
             "Layer": layers,
             "vector_layers": vector_layers
         });
-        let mut obj = metadata.as_object_mut().unwrap();
+        let obj = metadata.as_object_mut().unwrap();
         obj.insert("json".to_string(),
                    json!(metadata_vector_layers.to_string()));
         Ok(json!(obj))
     }
+    /// Row of tile URL in XYZ adressing scheme
+    pub fn url_ytile_xyz(&self, tileset: &str, ytile: u32, zoom: u8) -> u32 {
+        match self.tilesets.iter().find(|t| t.name == tileset) {
+            Some(set) if set.tms => self.grid.ytile_from_tms(ytile, zoom),
+            _ => ytile,
+        }
+    }
     /// Prepare datasource queries. Must be called before requesting tiles.
     pub fn prepare_feature_queries(&mut self) {
+        self.detect_layer_srids();
         for tileset in &self.tilesets {
-            for layer in &tileset.layers {
-                self.input.prepare_queries(&layer, self.grid.srid);
+            for layer in tileset.layers.iter().flat_map(|l| l.input_layers()) {
+                if let Some(input) = self.datasources.for_layer_mut(&layer) {
+                    input.prepare_queries(&layer, &self.grid);
+                }
+            }
+        }
+        if self.compute_extent {
+            self.compute_tileset_extents();
+        }
+        self.prepared = true;
+    }
+    /// Set SRID of layers without configured `srid` from datasource metadata.
+    /// Layers with unknown SRID are assumed to be in the grid SRID.
+    fn detect_layer_srids(&mut self) {
+        for tileset in &mut self.tilesets {
+            for layer in &mut tileset.layers {
+                if layer.srid.is_some() {
+                    continue;
+                }
+                // Combined layers use the SRID of their first source
+                let input_layer = layer.input_layers().remove(0);
+                let srid = self.datasources
+                    .for_layer(&input_layer)
+                    .and_then(|input| input.detect_srid(&input_layer));
+                if let Some(srid) = srid {
+                    if srid != self.grid.srid {
+                        info!("Layer '{}': Reprojecting from detected SRID {} to grid SRID {}",
+                              layer.name,
+                              srid,
+                              self.grid.srid);
+                    }
+                    layer.srid = Some(srid);
+                }
+            }
+        }
+    }
+    /// Set extent of tilesets without configured extent from layer data
+    fn compute_tileset_extents(&mut self) {
+        let datasources = &self.datasources;
+        let grid = &self.grid;
+        for tileset in &mut self.tilesets {
+            if tileset.extent.is_some() {
+                continue;
+            }
+            let mut extent: Option<Extent> = None;
+            for layer in tileset.layers.iter().flat_map(|l| l.input_layers()) {
+                let layer_extent = datasources
+                    .for_layer(&layer)
+                    .and_then(|input| input.retrieve_extent(&layer, grid));
+                if let Some(lext) = layer_extent {
+                    extent = Some(match extent {
+                                      Some(ext) => {
+                                          Extent {
+                                              minx: ext.minx.min(lext.minx),
+                                              miny: ext.miny.min(lext.miny),
+                                              maxx: ext.maxx.max(lext.maxx),
+                                              maxy: ext.maxy.max(lext.maxy),
+                                          }
+                                      }
+                                      None => lext,
+                                  });
+                }
+            }
+            if let Some(ref ext) = extent {
+                info!("Tileset '{}': data extent {:?}", tileset.name, ext);
+            }
+            tileset.extent = extent;
+        }
+    }
+    /// Retrieve features of `layer` in tile at x, y, z in TMS adressing scheme and call
+    /// `add` with each geometry to encode. Geometry collections are exploded or skipped,
+    /// geometries below the minimal size are removed and the others clipped if enabled.
+    fn retrieve_tile_features<F>(&self,
+                                 layer: &Layer,
+                                 xtile: u32,
+                                 ytile: u32,
+                                 zoom: u8,
+                                 mut add: F)
+        where F: FnMut(&Feature, GeometryType)
+    {
+        let extent = self.grid.tile_extent(xtile, ytile, zoom);
+        let clip_extent = if layer.clip_geometries_enabled() {
+            let buffer = self.grid.pixel_width(zoom) * layer.buffer_size(zoom) as f64;
+            Some(Extent {
+                     minx: extent.minx - buffer,
+                     miny: extent.miny - buffer,
+                     maxx: extent.maxx + buffer,
+                     maxy: extent.maxy + buffer,
+                 })
+        } else {
+            None
+        };
+        let pixel_width = self.grid.pixel_width(zoom);
+        let explode = layer.explode_collections();
+        let mut skipped_collections = 0;
+        self.retrieve_features(layer, &extent, zoom, |feat| {
+            let geom = match feat.geometry() {
+                Ok(geom) => geom,
+                Err(_) => return,
+            };
+            let geoms = match geom {
+                GeometryType::GeometryCollection(_) if !explode => {
+                    skipped_collections += 1;
+                    return;
+                }
+                // Components of geometry collections are encoded as separate features
+                geom => geom.explode(),
+            };
+            for geom in geoms {
+                // Skip features collapsed to sub-pixel size by simplification
+                if layer.below_min_size(&geom, pixel_width) {
+                    continue;
+                }
+                let geom = match clip_extent {
+                    Some(ref clip_extent) => clip_geometry(&geom, clip_extent),
+                    None => Some(geom),
+                };
+                if let Some(geom) = geom {
+                    add(feat, geom);
+                }
             }
+        });
+        if skipped_collections > 0 {
+            warn!("Layer '{}': skipped {} geometry collections in tile {}/{}/{}",
+                  layer.name,
+                  skipped_collections,
+                  zoom,
+                  xtile,
+                  ytile);
         }
     }
     /// Create vector tile from input at x, y, z in TMS adressing scheme
     pub fn tile(&self, tileset: &str, xtile: u32, ytile: u32, zoom: u8) -> vector_tile::Tile {
         let extent = self.grid.tile_extent(xtile, ytile, zoom);
         debug!("MVT tile request {:?}", extent);
-        let mut tile = Tile::new(&extent, 4096, true);
+        let mut tile = Tile::new(&extent, self.grid.mvt_extent(), true);
         for layer in self.get_tileset(tileset) {
+            if !layer.in_zoom_range(zoom) {
+                continue;
+            }
             let mut mvt_layer = tile.new_layer(layer);
-            self.input
-                .retrieve_features(&layer,
-                                   &extent,
-                                   zoom,
-                                   &self.grid,
-                                   |feat| { tile.add_feature(&mut mvt_layer, feat); });
+            self.retrieve_tile_features(layer, xtile, ytile, zoom, |feat, geom| {
+                tile.add_feature_geom(&mut mvt_layer, feat, geom);
+            });
             tile.add_layer(mvt_layer);
         }
         tile.mvt_tile
     }
-    /// Fetch or create vector tile from input at x, y, z
+    /// Features of tile at x, y, z in XYZ adressing scheme as GeoJSON FeatureCollection
+    /// with WGS84 coordinates. Features are processed like in vector tiles.
+    pub fn tile_geojson(&self,
+                        tileset: &str,
+                        xtile: u32,
+                        ytile: u32,
+                        zoom: u8)
+                        -> Result<serde_json::Value, String> {
+        let transform = try!(wgs84_inverse_transformation(self.grid.srid));
+        let y = self.grid.ytile_from_xyz(ytile, zoom);
+        debug!("GeoJSON tile request {:?}", self.grid.tile_extent(xtile, y, zoom));
+        let mut features = Vec::new();
+        for layer in self.get_tileset(tileset) {
+            if !layer.in_zoom_range(zoom) {
+                continue;
+            }
+            self.retrieve_tile_features(layer, xtile, y, zoom, |feat, geom| {
+                if let Some(json) = feature_geometry_to_json(&layer.name, feat, &geom, &transform) {
+                    features.push(json);
+                }
+            });
+        }
+        Ok(json!({
+            "type": "FeatureCollection",
+            "features": features
+        }))
+    }
+    /// UTFGrid of tile at x, y, z in XYZ adressing scheme from layers with `utfgrid_fields`
+    pub fn tile_utfgrid(&self,
+                        tileset: &str,
+                        xtile: u32,
+                        ytile: u32,
+                        zoom: u8)
+                        -> Result<serde_json::Value, String> {
+        let layers: Vec<&Layer> = self.get_tileset(tileset)
+            .into_iter()
+            .filter(|layer| layer.utfgrid_fields.is_some())
+            .collect();
+        if layers.is_empty() {
+            return Err(format!("Tileset '{}' has no UTFGrid layers", tileset));
+        }
+        let y = self.grid.ytile_from_xyz(ytile, zoom);
+        let extent = self.grid.tile_extent(xtile, y, zoom);
+        debug!("UTFGrid tile request {:?}", extent);
+        let mut grid = UtfGrid::new(self.grid.tile_size() / utfgrid::RESOLUTION, &extent);
+        for layer in layers {
+            if !layer.in_zoom_range(zoom) {
+                continue;
+            }
+            let fields = layer.utfgrid_fields.as_ref().unwrap();
+            self.retrieve_features(layer, &extent, zoom, |feat| {
+                grid.add_feature(feat, fields);
+            });
+        }
+        Ok(grid.to_json())
+    }
+    /// Tile encoded by the database with ST_AsMVT.
+    /// Returns None unless all layers of the tileset support database encoding.
+    fn tile_data_from_db(&self,
+                         tileset: &str,
+                         xtile: u32,
+                         ytile: u32,
+                         zoom: u8)
+                         -> Option<Vec<u8>> {
+        let layers: Vec<&Layer> = self.get_tileset(tileset)
+            .into_iter()
+            .filter(|layer| layer.in_zoom_range(zoom))
+            .collect();
+        // Sources of combined layers are tagged when encoding the tile
+        if !layers
+                .iter()
+                .all(|layer| !layer.is_combined() && self.input(layer).mvt_supported()) {
+            return None;
+        }
+        let extent = self.grid.tile_extent(xtile, ytile, zoom);
+        let mut data = Vec::new();
+        for layer in layers {
+            // Encoded layers are concatenated into a tile
+            match self.input(layer)
+                      .retrieve_mvt_layer(layer, &extent, zoom, &self.grid) {
+                Some(layer_data) => data.extend(layer_data),
+                None => return None,
+            }
+        }
+        Some(data)
+    }
+    /// Create gzip compressed tile at x, y, z in TMS adressing scheme.
+    /// Tiles smaller than `gzip_min_bytes` are not compressed, tiles without features are empty.
+    /// Returns tile data and whether the tile has no features.
+    fn encoded_tile(&self, tileset: &str, xtile: u32, ytile: u32, zoom: u8) -> (Vec<u8>, bool) {
+        let (data, empty) = match self.tile_data_from_db(tileset, xtile, ytile, zoom) {
+            Some(data) => {
+                let empty = Tile::is_empty_data(&data, false);
+                (data, empty)
+            }
+            None => {
+                let mvt_tile = self.tile(tileset, xtile, ytile, zoom);
+                (Tile::binary_tile(&mvt_tile), Tile::is_empty(&mvt_tile))
+            }
+        };
+        if empty {
+            // Zero bytes are a valid tile without layers and are detected without decoding
+            (Vec::new(), true)
+        } else if data.len() < self.gzip_min_bytes {
+            (data, false)
+        } else {
+            (Tile::gzip_level(&data, self.gzip_level), false)
+        }
+    }
+    /// Cache backend of tileset
+    pub fn tileset_cache(&self, tileset: &str) -> &Tilecache {
+        self.tilesets
+            .iter()
+            .find(|t| t.name == tileset)
+            .and_then(|set| set.cache.as_ref())
+            .unwrap_or(&self.cache)
+    }
+    /// Caching disabled with `cache = false`. Clients shouldn't store tiles either.
+    pub fn cache_disabled(&self, tileset: &str) -> bool {
+        match self.tilesets.iter().find(|t| t.name == tileset) {
+            Some(&Tileset { cache: Some(Tilecache::Nocache(_)), .. }) => true,
+            _ => false,
+        }
+    }
+    /// Size and number of features and vertices per layer of the tile at x, y, z
+    /// in XYZ adressing scheme. The tile is generated and not cached.
+    pub fn tile_stats(&self,
+                      tileset: &str,
+                      xtile: u32,
+                      ytile: u32,
+                      zoom: u8)
+                      -> Result<serde_json::Value, String> {
+        let y = self.grid.ytile_from_xyz(ytile, zoom);
+        let (tile, _) = self.encoded_tile(tileset, xtile, y, zoom);
+        let (data, tilegz) = if Tile::is_gzip(&tile) {
+            (Tile::gunzip(&tile), tile)
+        } else {
+            let tilegz = Tile::gzip_level(&tile, self.gzip_level);
+            (tile, tilegz)
+        };
+        let mvt_tile = try!(Tile::read_from(&mut &data[..]).map_err(|e| format!("{}", e)));
+        let layers: Vec<serde_json::Value> = mvt_tile
+            .get_layers()
+            .iter()
+            .map(|layer| {
+                let vertices: u64 = layer
+                    .get_features()
+                    .iter()
+                    .map(|feature| Tile::vertex_count(feature.get_geometry()))
+                    .sum();
+                json!({
+                    "name": layer.get_name(),
+                    "feature_count": layer.get_features().len(),
+                    "vertex_count": vertices
+                })
+            })
+            .collect();
+        Ok(json!({
+            "bytes": data.len(),
+            "gzipped_bytes": tilegz.len(),
+            "layers": layers
+        }))
+    }
+    /// Write encoded tile into cache. Empty tiles are skipped unless `cache_empty_tiles` is set.
+    fn write_cache(&self, tileset: &str, path: &str, empty: bool, tilegz: &[u8]) {
+        if !self.cache_empty_tiles && empty {
+            debug!("Skip caching of empty tile {}", path);
+            return;
+        }
+        if let Err(err) = self.tileset_cache(tileset).write(path, tilegz) {
+            error!("Writing {} into cache failed: {}", path, err);
+        }
+    }
+    /// Read cached tile including expired tiles
+    fn read_stale(&self, tileset: &str, path: &str) -> Option<Vec<u8>> {
+        match self.tileset_cache(tileset).read_tile_stale(path) {
+            Ok(tile) => tile,
+            Err(err) => {
+                error!("Reading stale {} from cache failed: {}", path, err);
+                None
+            }
+        }
+    }
+    /// Fetch or create vector tile from input at x, y, z.
+    /// Panics if the generation failed.
+    #[cfg(test)]
     pub fn tile_cached(&self,
                        tileset: &str,
                        xtile: u32,
                        ytile: u32,
                        zoom: u8,
-                       _gzip: bool)
+                       gzip: bool)
                        -> Vec<u8> {
-        // Reverse y for XYZ scheme (TODO: protocol instead of CRS dependent?)
-        let y = if self.grid.srid == 3857 {
-            self.grid.ytile_from_xyz(ytile, zoom)
-        } else {
-            ytile
-        };
-        let path = format!("{}/{}/{}/{}.pbf", tileset, zoom, xtile, ytile);
-
-        let mut tile: Option<Vec<u8>> = None;
-        self.cache
-            .read(&path, |mut f| {
-                let mut data = Vec::new();
-                let _ = f.read_to_end(&mut data);
-                tile = Some(data);
-            });
-        if tile.is_some() {
-            //TODO: unzip if gzip == false
-            return tile.unwrap();
+        match self.tile_cached_or_stale(tileset, xtile, ytile, zoom, gzip) {
+            Ok((tile, _)) => tile,
+            Err(err) => panic!("{}", err),
         }
+    }
+    /// Fetch or create vector tile from input at x, y, z.
+    /// Returns tile data and where it comes from.
+    /// Concurrent requests for a tile which is not cached wait for a single generation.
+    pub fn tile_cached_or_stale(&self,
+                                tileset: &str,
+                                xtile: u32,
+                                ytile: u32,
+                                zoom: u8,
+                                gzip: bool)
+                                -> Result<(Vec<u8>, TileSource), TileError> {
+        // Tiles are cached gzip compressed, except tiles smaller than `gzip_min_bytes`
+        let path = format!("{}/{}/{}/{}.pbf.gz", tileset, zoom, xtile, ytile);
 
-        let mvt_tile = self.tile(tileset, xtile, y, zoom);
-
-        let mut tilegz = Vec::new();
-        Tile::write_gz_to(&mut tilegz, &mvt_tile);
-        let _ = self.cache.write(&path, &tilegz);
+        let tile = match self.tileset_cache(tileset).read_tile(&path) {
+            Ok(tile) => tile,
+            Err(err) => {
+                // Regenerate tile on read failures
+                error!("Reading {} from cache failed: {}", path, err);
+                None
+            }
+        };
+        let (tilegz, source) = match tile {
+            Some(tilegz) => {
+                if let Some(ref metrics) = self.metrics {
+                    metrics.inc_cache_hits(tileset);
+                }
+                (tilegz, TileSource::Cache)
+            }
+            None => {
+                // Expired tiles are served immediately and regenerated in the background
+                let stale_tile = if self.revalidator.is_some() {
+                    self.read_stale(tileset, &path)
+                } else {
+                    None
+                };
+                match stale_tile {
+                    Some(stale_tile) => {
+                        if let Some(ref metrics) = self.metrics {
+                            metrics.inc_stale_served(tileset);
+                        }
+                        (stale_tile, TileSource::StaleRevalidate)
+                    }
+                    None => {
+                        // Waiters get an error if the generating request panicked
+                        let generated = self.inflight
+                            .run(&path,
+                                 || self.generate_uncached(tileset, xtile, ytile, zoom, &path));
+                        try!(try!(generated.map_err(TileError::Generation)))
+                    }
+                }
+            }
+        };
 
-        //TODO: return unzipped if gzip == false
-        tilegz
+        if !gzip && Tile::is_gzip(&tilegz) {
+            // Client doesn't accept gzip encoding
+            Ok((Tile::gunzip(&tilegz), source))
+        } else {
+            Ok((tilegz, source))
+        }
+    }
+    /// Fetch tile like `tile_cached_or_stale` and queue the regeneration of expired tiles
+    pub fn tile_cached_revalidate(service: &Arc<MvtService>,
+                                  tileset: &str,
+                                  xtile: u32,
+                                  ytile: u32,
+                                  zoom: u8,
+                                  gzip: bool)
+                                  -> Result<(Vec<u8>, TileSource), TileError> {
+        let (tile, source) = try!(service.tile_cached_or_stale(tileset, xtile, ytile, zoom, gzip));
+        if source == TileSource::StaleRevalidate {
+            if let Some(ref revalidator) = service.revalidator {
+                revalidator.enqueue(service.clone(), tileset, xtile, ytile, zoom);
+            }
+        }
+        Ok((tile, source))
+    }
+    /// Create tile at x, y, z in XYZ adressing scheme and write it into the cache.
+    /// Tiles with failed or timed out queries are not cached.
+    fn generate_uncached(&self,
+                         tileset: &str,
+                         xtile: u32,
+                         ytile: u32,
+                         zoom: u8,
+                         path: &str)
+                         -> Result<(Vec<u8>, TileSource), TileError> {
+        // Reverse y for XYZ scheme (grids with top-left origin are unchanged)
+        let y = self.grid.ytile_from_xyz(ytile, zoom);
+        let start = self.metrics.as_ref().map(|_| Instant::now());
+        // Reset flags of previous requests
+        take_query_timeout();
+        take_query_error();
+        let encoded =
+            panic::catch_unwind(AssertUnwindSafe(|| self.encoded_tile(tileset, xtile, y, zoom)));
+        let (tilegz, empty) = match encoded {
+            Ok(encoded) => encoded,
+            Err(cause) => return Err(TileError::Generation(panic_message(&*cause))),
+        };
+        if let Some(err) = take_query_error() {
+            return Err(TileError::Datasource(err));
+        }
+        let timed_out = take_query_timeout();
+        if let (Some(metrics), Some(start)) = (self.metrics.as_ref(), start) {
+            let elapsed = start.elapsed();
+            metrics.inc_cache_misses(tileset);
+            metrics.observe_generation(tileset,
+                                       elapsed.as_secs() as f64 +
+                                       elapsed.subsec_nanos() as f64 * 1e-9);
+        }
+        let stale_tile = if timed_out && self.serve_stale_on_timeout {
+            self.read_stale(tileset, path)
+        } else {
+            None
+        };
+        match stale_tile {
+            Some(stale_tile) => {
+                warn!("Serving stale tile {} after query timeout", path);
+                if let Some(ref metrics) = self.metrics {
+                    metrics.inc_stale_served(tileset);
+                }
+                Ok((stale_tile, TileSource::StaleOnTimeout))
+            }
+            None if timed_out => {
+                warn!("Query timeout for tile {} - not caching incomplete tile", path);
+                Ok((tilegz, TileSource::Incomplete))
+            }
+            None => {
+                self.write_cache(tileset, path, empty, &tilegz);
+                Ok((tilegz, TileSource::Generated))
+            }
+        }
+    }
+    /// Replace expired cached tile at x, y, z in XYZ adressing scheme.
+    /// The stale tile is kept if a query times out.
+    pub fn regenerate_tile(&self, tileset: &str, xtile: u32, ytile: u32, zoom: u8) {
+        let y = self.grid.ytile_from_xyz(ytile, zoom);
+        let path = format!("{}/{}/{}/{}.pbf.gz", tileset, zoom, xtile, ytile);
+        debug!("Regenerating {}", path);
+        take_query_timeout();
+        take_query_error();
+        let (tilegz, _empty) = self.encoded_tile(tileset, xtile, y, zoom);
+        if take_query_timeout() {
+            warn!("Regeneration of {} timed out - keeping stale tile", path);
+            return;
+        }
+        if let Some(err) = take_query_error() {
+            warn!("Regeneration of {} failed - keeping stale tile: {}", path, err);
+            return;
+        }
+        if let Some(ref metrics) = self.metrics {
+            metrics.inc_revalidations(tileset);
+        }
+        // Empty tiles are written as well to replace the stale tile
+        if let Err(err) = self.tileset_cache(tileset).write(&path, &tilegz) {
+            error!("Writing {} into cache failed: {}", path, err);
+        }
     }
     fn progress_bar(&self, msg: &str, limits: &ExtentInt) -> ProgressBar<Stdout> {
-        let tiles = (limits.maxx as u64 - limits.minx as u64) *
-                    (limits.maxy as u64 - limits.miny as u64);
-        let mut pb = ProgressBar::new(tiles);
+        let mut pb = ProgressBar::new(tile_count(limits));
         pb.message(msg);
         //pb.set_max_refresh_rate(Some(Duration::from_millis(200)));
-        pb.show_speed = false;
+        pb.show_speed = true;
         pb.show_percent = false;
         pb.show_time_left = false;
         pb
     }
+    /// Tile limits of zoom levels `minzoom` to `maxzoom` within `extent` (grid SRS)
+    fn zoom_limits(&self,
+                   minzoom: Option<u8>,
+                   maxzoom: Option<u8>,
+                   extent: Option<Extent>)
+                   -> Vec<(u8, ExtentInt)> {
+        let minzoom = minzoom.unwrap_or(0);
+        let maxzoom = maxzoom.unwrap_or(self.grid.maxzoom());
+        if maxzoom > self.grid.maxzoom() {
+            warn!("Zoom level exceeds maximal zoom level of grid ({}) - skipping", self.grid.maxzoom());
+        }
+        let extent = extent.unwrap_or(self.grid.tile_extent(0, 0, 0));
+        debug!("tile limits: {:?}", extent);
+        self.grid
+            .tile_limits(extent, 0)
+            .into_iter()
+            .enumerate()
+            .map(|(zoom, limit)| (zoom as u8, limit))
+            .filter(|&(zoom, _)| zoom >= minzoom && zoom <= maxzoom)
+            .collect()
+    }
     /// Populate tile cache
     pub fn generate(&self,
                     tileset_name: Option<&str>,
@@ -356,83 +1172,545 @@ impl MvtService {
                     extent: Option<Extent>,
                     nodes: Option<u8>,
                     nodeno: Option<u8>,
+                    threads: Option<u8>,
                     progress: bool) {
         self.init_cache();
-        let minzoom = minzoom.unwrap_or(0);
-        let maxzoom = maxzoom.unwrap_or(self.grid.maxzoom());
-        let extent = extent.unwrap_or(self.grid.tile_extent(0, 0, 0));
         let nodes = nodes.unwrap_or(1) as u64;
         let nodeno = nodeno.unwrap_or(0) as u64;
+        let threads = threads.unwrap_or(1).max(1) as u64;
+        let mut pool = Pool::new(threads as u32);
         let mut tileno: u64 = 0;
-        debug!("tile limits: {:?}", extent);
-        let limits = self.grid.tile_limits(extent, 0);
+        let skipped = AtomicUsize::new(0);
+        let zoom_limits = self.zoom_limits(minzoom, maxzoom, extent);
         for tileset in &self.tilesets {
             if tileset_name.is_some() && tileset_name.unwrap() != &tileset.name {
                 continue;
             }
+            if self.cache_disabled(&tileset.name) {
+                continue;
+            }
             if progress {
                 println!("Generating tileset '{}'...", tileset.name);
             }
-            for zoom in minzoom..(maxzoom + 1) {
-                if zoom > self.grid.maxzoom() {
-                    warn!("Zoom level exceeds maximal zoom level of grid ({}) - skipping", self.grid.maxzoom());
-                    continue;
-                }
-                let ref limit = limits[zoom as usize];
+            for &(zoom, ref limit) in &zoom_limits {
                 debug!("level {}: {:?}", zoom, limit);
-                let mut pb = self.progress_bar(&format!("Level {}: ", zoom), &limit);
+                let pb = Mutex::new(self.progress_bar(&format!("Level {}: ", zoom), &limit));
                 if progress {
-                    pb.tick();
-                }
-                for xtile in limit.minx..limit.maxx {
-                    for ytile in limit.miny..limit.maxy {
-                        let skip = tileno % nodes != nodeno;
-                        tileno += 1;
-                        if skip {
-                            continue;
-                        }
-
-                        // store in xyz schema. TODO: make configurable
-                        let y = self.grid.ytile_from_xyz(ytile, zoom);
-                        let path = format!("{}/{}/{}/{}.pbf", &tileset.name, zoom, xtile, y);
-
-                        if !self.cache.exists(&path) {
-                            // Entry doesn't exist, so generate it
-                            let mvt_tile =
-                                self.tile(&tileset.name, xtile as u32, ytile as u32, zoom);
-                            let mut tilegz = Vec::new();
-                            Tile::write_gz_to(&mut tilegz, &mvt_tile);
-                            let _ = self.cache.write(&path, &tilegz);
-                        }
-
-                        if progress {
-                            pb.inc();
-                        }
+                    pb.lock().unwrap().tick();
+                }
+                let level_start = tileno;
+                let level_height = (limit.maxy - limit.miny) as u64;
+                pool.scoped(|scope| {
+                    for threadno in 0..threads {
+                        let pb = &pb;
+                        let skipped = &skipped;
+                        scope.execute(move || {
+                            for xtile in limit.minx..limit.maxx {
+                                for ytile in limit.miny..limit.maxy {
+                                    let tileno = level_start +
+                                                 (xtile - limit.minx) as u64 * level_height +
+                                                 (ytile - limit.miny) as u64;
+                                    // Distribute tiles over nodes and threads
+                                    if tileno % nodes != nodeno ||
+                                       (tileno / nodes) % threads != threadno {
+                                        continue;
+                                    }
+                                    if !self.generate_tile(&tileset.name, xtile, ytile, zoom) {
+                                        skipped.fetch_add(1, Ordering::Relaxed);
+                                    }
+                                    if progress {
+                                        pb.lock().unwrap().inc();
+                                    }
+                                }
+                            }
+                        });
+                    }
+                });
+                tileno += (limit.maxx - limit.minx) as u64 * level_height;
+            }
+        }
+        if progress {
+            println!("");
+        }
+        log_skipped_tiles(skipped.load(Ordering::Relaxed) as u64);
+    }
+    /// Populate tile cache with tiles of a tileset within `extent` (grid SRS).
+    /// `progress` is called with zoom level, number of seeded tiles and total number of tiles
+    /// after each tile and cancels seeding by returning false.
+    /// Returns the number of seeded tiles.
+    pub fn seed<F>(&self,
+                   tileset_name: &str,
+                   minzoom: Option<u8>,
+                   maxzoom: Option<u8>,
+                   extent: Option<Extent>,
+                   mut progress: F)
+                   -> Result<u64, String>
+        where F: FnMut(u8, u64, u64) -> bool
+    {
+        if !self.tilesets.iter().any(|set| set.name == tileset_name) {
+            return Err(format!("Unknown tileset '{}'", tileset_name));
+        }
+        let zoom_limits = self.zoom_limits(minzoom, maxzoom, extent);
+        let total: u64 = zoom_limits.iter().map(|&(_, ref limit)| tile_count(limit)).sum();
+        info!("Seeding {} tiles of tileset '{}'", total, tileset_name);
+        let mut tiles: u64 = 0;
+        let mut skipped: u64 = 0;
+        for &(zoom, ref limit) in &zoom_limits {
+            for xtile in limit.minx..limit.maxx {
+                for ytile in limit.miny..limit.maxy {
+                    if !self.generate_tile(tileset_name, xtile, ytile, zoom) {
+                        skipped += 1;
                     }
+                    tiles += 1;
+                    if !progress(zoom, tiles, total) {
+                        info!("Seeding of tileset '{}' cancelled after {} tiles", tileset_name, tiles);
+                        log_skipped_tiles(skipped);
+                        return Ok(tiles);
+                    }
+                }
+            }
+        }
+        log_skipped_tiles(skipped);
+        Ok(tiles)
+    }
+    /// Generate tiles of a tileset into a new MBTiles file. Returns the file size in bytes.
+    pub fn export_mbtiles(&mut self,
+                          tileset_name: &str,
+                          path: &str,
+                          minzoom: Option<u8>,
+                          maxzoom: Option<u8>,
+                          extent: Option<Extent>,
+                          threads: Option<u8>,
+                          progress: bool)
+                          -> Result<u64, String> {
+        if !self.tilesets.iter().any(|t| t.name == tileset_name) {
+            return Err(format!("Unknown tileset '{}'", tileset_name));
+        }
+        if Path::new(path).exists() {
+            try!(fs::remove_file(path).map_err(|e| format!("Error removing '{}': {}", path, e)));
+        }
+        if !self.grid.is_quadtree() {
+            return Err(MBTILES_GRID_ERROR.to_string());
+        }
+        self.cache = Tilecache::Mbtilescache(Mbtilescache::new(path));
+        // Exported tiles are always gzip compressed
+        self.gzip_min_bytes = 0;
+        for tileset in &mut self.tilesets {
+            tileset.cache = None;
+        }
+        let minzoom = minzoom.unwrap_or(0);
+        let maxzoom = maxzoom.unwrap_or(self.grid.maxzoom()).min(self.grid.maxzoom());
+        self.generate(Some(tileset_name),
+                      Some(minzoom),
+                      Some(maxzoom),
+                      extent,
+                      None,
+                      None,
+                      threads,
+                      progress);
+        // Metadata with exported zoom range
+        let mut metadata = try!(self.get_mbtiles_metadata(tileset_name)
+                                    .map_err(|e| format!("{}", e)));
+        metadata["minzoom"] = json!(minzoom);
+        metadata["maxzoom"] = json!(maxzoom);
+        // MBTiles spec: comma-separated bounds without brackets
+        let bounds = metadata["bounds"]
+            .as_str()
+            .unwrap_or("")
+            .trim_matches(|c| c == '[' || c == ']')
+            .to_string();
+        metadata["bounds"] = json!(bounds);
+        if let Tilecache::Mbtilescache(ref mbtiles) = self.cache {
+            try!(mbtiles
+                     .write_metadata(tileset_name, &metadata)
+                     .map_err(|e| format!("Error writing MBTiles metadata: {}", e)));
+        }
+        fs::metadata(path)
+            .map(|meta| meta.len())
+            .map_err(|e| format!("Error reading '{}': {}", path, e))
+    }
+    /// Generate tiles of a tileset into a new PMTiles archive. Returns the file size in bytes.
+    pub fn export_pmtiles(&mut self,
+                          tileset_name: &str,
+                          path: &str,
+                          minzoom: Option<u8>,
+                          maxzoom: Option<u8>,
+                          extent: Option<Extent>,
+                          threads: Option<u8>,
+                          progress: bool)
+                          -> Result<u64, String> {
+        // Tiles are generated into a temporary MBTiles file first
+        let tmp_path = format!("{}.mbtiles-tmp", path);
+        let data_path = format!("{}.data-tmp", path);
+        let result = self.export_mbtiles(tileset_name,
+                                         &tmp_path,
+                                         minzoom,
+                                         maxzoom,
+                                         extent,
+                                         threads,
+                                         progress)
+            .and_then(|_| {
+                          self.write_pmtiles(tileset_name, path, &tmp_path, &data_path, minzoom, maxzoom)
+                      });
+        let _ = fs::remove_file(&tmp_path);
+        let _ = fs::remove_file(&data_path);
+        result
+    }
+    /// Write PMTiles archive from tiles in MBTiles file `mbtiles_path`.
+    /// The tile data section is collected in the temporary file `data_path`.
+    fn write_pmtiles(&mut self,
+                     tileset_name: &str,
+                     path: &str,
+                     mbtiles_path: &str,
+                     data_path: &str,
+                     minzoom: Option<u8>,
+                     maxzoom: Option<u8>)
+                     -> Result<u64, String> {
+        self.cache = Tilecache::Nocache(Nocache);
+        let minzoom = minzoom.unwrap_or(0);
+        let maxzoom = maxzoom.unwrap_or(self.grid.maxzoom()).min(self.grid.maxzoom());
+        let tilejson = try!(self.get_tilejson_metadata(tileset_name)
+                                .map_err(|e| format!("{}", e)));
+        let bounds = Extent {
+            minx: tilejson["bounds"][0].as_f64().unwrap_or(-180.0),
+            miny: tilejson["bounds"][1].as_f64().unwrap_or(-90.0),
+            maxx: tilejson["bounds"][2].as_f64().unwrap_or(180.0),
+            maxy: tilejson["bounds"][3].as_f64().unwrap_or(90.0),
+        };
+        let mut writer = PmtilesWriter::new(minzoom, maxzoom, bounds);
+        let center_zoom = tilejson["center"][2].as_u64().unwrap_or(0) as u8;
+        writer.center.2 = cmp::max(minzoom, cmp::min(maxzoom, center_zoom));
+        writer.metadata = tilejson;
+        writer.metadata["minzoom"] = json!(minzoom);
+        writer.metadata["maxzoom"] = json!(maxzoom);
+        writer.metadata["vector_layers"] = try!(self.get_tilejson_vector_layers(tileset_name)
+                                                    .map_err(|e| format!("{}", e)));
+        let mut data = io::BufWriter::new(try!(File::create(data_path)
+                                                   .map_err(|e| format!("Error creating '{}': {}", data_path, e))));
+        {
+            let conn = try!(Connection::open(mbtiles_path).map_err(|e| format!("{}", e)));
+            // Tile ids of all rows, sorted for reading the tile data in tile id order
+            let mut tiles: Vec<(u64, i64)> = Vec::new();
+            {
+                let mut stmt = try!(conn.prepare("SELECT rowid, zoom_level, tile_column, tile_row FROM tiles")
+                                        .map_err(|e| format!("{}", e)));
+                let rows = try!(stmt.query_map(&[], |row| {
+                                         (row.get::<_, i64>(0),
+                                          row.get::<_, i64>(1),
+                                          row.get::<_, i64>(2),
+                                          row.get::<_, i64>(3))
+                                     })
+                                    .map_err(|e| format!("{}", e)));
+                for row in rows {
+                    let (rowid, zoom, xtile, tms_row) = try!(row.map_err(|e| format!("{}", e)));
+                    // MBTiles rows are in TMS scheme
+                    let ytile = (1i64 << zoom) - 1 - tms_row;
+                    tiles.push((zxy_to_tile_id(zoom as u8, xtile as u32, ytile as u32), rowid));
+                }
+            }
+            tiles.sort();
+            let mut stmt = try!(conn.prepare("SELECT tile_data FROM tiles WHERE rowid = ?")
+                                    .map_err(|e| format!("{}", e)));
+            for &(tile_id, rowid) in &tiles {
+                let tilegz = try!(stmt.query_row(&[&rowid], |row| row.get::<_, Vec<u8>>(0))
+                                      .map_err(|e| format!("{}", e)));
+                // Tiles without features are missing tiles in PMTiles
+                if tilegz.is_empty() {
+                    continue;
+                }
+                try!(writer
+                         .add_tile(&mut data, tile_id, &tilegz)
+                         .map_err(|e| format!("Error writing '{}': {}", data_path, e)));
+            }
+        }
+        try!(data.flush()
+                 .map_err(|e| format!("Error writing '{}': {}", data_path, e)));
+        drop(data);
+        if Path::new(path).exists() {
+            try!(fs::remove_file(path).map_err(|e| format!("Error removing '{}': {}", path, e)));
+        }
+        let mut data = try!(File::open(data_path)
+                                .map_err(|e| format!("Error reading '{}': {}", data_path, e)));
+        let mut file = io::BufWriter::new(try!(File::create(path)
+                                                   .map_err(|e| format!("Error creating '{}': {}", path, e))));
+        let size = try!(writer
+                            .write(&mut file, &mut data)
+                            .and_then(|size| file.flush().map(|_| size))
+                            .map_err(|e| format!("Error writing '{}': {}", path, e)));
+        Ok(size)
+    }
+    /// Read newline separated z/x/y tile list in XYZ adressing scheme.
+    /// Invalid lines are reported and skipped.
+    pub fn read_tile_list<R: BufRead>(&self, reader: R) -> Vec<(u8, u32, u32)> {
+        let mut tiles = Vec::new();
+        for (lineno, line) in reader.lines().enumerate() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    warn!("Tile list line {}: {}", lineno + 1, err);
+                    continue;
                 }
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let coords: Vec<&str> = line.split('/').collect();
+            let tile = if coords.len() == 3 {
+                self.tile_coords(&coords)
+            } else {
+                None
+            };
+            match tile {
+                Some(tile) => tiles.push(tile),
+                None => warn!("Tile list line {}: invalid tile '{}' - skipping", lineno + 1, line),
+            }
+        }
+        tiles
+    }
+    /// Tile z, x, y in range of the grid
+    fn tile_coords(&self, coords: &[&str]) -> Option<(u8, u32, u32)> {
+        match (coords[0].parse::<u8>(), coords[1].parse::<u32>(), coords[2].parse::<u32>()) {
+            (Ok(z), Ok(x), Ok(y)) if self.grid.tile_in_range(x, y, z) => Some((z, x, y)),
+            _ => None,
+        }
+    }
+    /// Read newline separated tileset/z/x/y tile list in XYZ adressing scheme.
+    /// Invalid lines and unknown tilesets are reported and skipped.
+    pub fn read_warm_manifest<R: BufRead>(&self, reader: R) -> Vec<(String, u8, u32, u32)> {
+        let mut tiles = Vec::new();
+        for (lineno, line) in reader.lines().enumerate() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => {
+                    warn!("Cache warming manifest line {}: {}", lineno + 1, err);
+                    continue;
+                }
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let parts: Vec<&str> = line.split('/').collect();
+            let tile = if parts.len() == 4 && self.tilesets.iter().any(|t| t.name == parts[0]) {
+                self.tile_coords(&parts[1..])
+                    .map(|(z, x, y)| (parts[0].to_string(), z, x, y))
+            } else {
+                None
+            };
+            match tile {
+                Some(tile) => tiles.push(tile),
+                None => {
+                    warn!("Cache warming manifest line {}: invalid tile '{}' - skipping",
+                          lineno + 1,
+                          line)
+                }
+            }
+        }
+        tiles
+    }
+    /// Generate and cache tiles given as tileset/z/x/y in XYZ adressing scheme,
+    /// unless they are already cached. Returns the number of warmed and failed tiles.
+    pub fn warm_cache(&self, tiles: &[(String, u8, u32, u32)]) -> (u64, u64) {
+        let mut warmed = 0;
+        let mut failed = 0;
+        for &(ref tileset, zoom, xtile, ytile) in tiles {
+            match self.tile_cached_or_stale(tileset, xtile, ytile, zoom, true) {
+                Ok(_) => warmed += 1,
+                Err(err) => {
+                    error!("Cache warming of {}/{}/{}/{} failed: {}",
+                           tileset,
+                           zoom,
+                           xtile,
+                           ytile,
+                           err);
+                    failed += 1;
+                }
+            }
+        }
+        (warmed, failed)
+    }
+    /// Populate tile cache with tiles given as z/x/y in XYZ adressing scheme
+    pub fn generate_tile_list(&self,
+                              tileset_name: Option<&str>,
+                              tiles: &[(u8, u32, u32)],
+                              threads: Option<u8>,
+                              progress: bool) {
+        self.init_cache();
+        let threads = threads.unwrap_or(1).max(1) as usize;
+        let mut pool = Pool::new(threads as u32);
+        let skipped = AtomicUsize::new(0);
+        for tileset in &self.tilesets {
+            if tileset_name.is_some() && tileset_name.unwrap() != &tileset.name {
+                continue;
             }
+            if self.cache_disabled(&tileset.name) {
+                continue;
+            }
+            if progress {
+                println!("Generating tileset '{}'...", tileset.name);
+            }
+            let mut pb = ProgressBar::new(tiles.len() as u64);
+            pb.message("Tiles: ");
+            pb.show_percent = false;
+            pb.show_time_left = false;
+            let pb = Mutex::new(pb);
+            pool.scoped(|scope| {
+                for threadno in 0..threads {
+                    let pb = &pb;
+                    let skipped = &skipped;
+                    scope.execute(move || {
+                        let thread_tiles = tiles
+                            .iter()
+                            .enumerate()
+                            .filter(|&(i, _)| i % threads == threadno);
+                        for (_, &(zoom, xtile, ytile)) in thread_tiles {
+                            let y = self.grid.ytile_from_xyz(ytile, zoom);
+                            if !self.generate_tile(&tileset.name, xtile, y, zoom) {
+                                skipped.fetch_add(1, Ordering::Relaxed);
+                            }
+                            if progress {
+                                pb.lock().unwrap().inc();
+                            }
+                        }
+                    });
+                }
+            });
         }
         if progress {
             println!("");
         }
+        log_skipped_tiles(skipped.load(Ordering::Relaxed) as u64);
+    }
+    /// Generate tile at x, y, z in TMS adressing scheme and write it into the cache,
+    /// unless it is already cached.
+    /// Returns false if the tile was not cached because a query failed or timed out.
+    fn generate_tile(&self, tileset: &str, xtile: u32, ytile: u32, zoom: u8) -> bool {
+        // store in xyz schema. TODO: make configurable
+        let y = self.grid.ytile_from_xyz(ytile, zoom);
+        let path = format!("{}/{}/{}/{}.pbf.gz", tileset, zoom, xtile, y);
+
+        if self.tileset_cache(tileset).exists(&path) {
+            return true;
+        }
+        // Entry doesn't exist, so generate it
+        take_query_timeout();
+        take_query_error();
+        let (tilegz, empty) = self.encoded_tile(tileset, xtile, ytile, zoom);
+        if let Some(err) = take_query_error() {
+            warn!("Generating tile {} failed - not caching incomplete tile: {}", path, err);
+            return false;
+        }
+        if take_query_timeout() {
+            warn!("Query timeout for tile {} - not caching incomplete tile", path);
+            return false;
+        }
+        self.write_cache(tileset, &path, empty, &tilegz);
+        true
+    }
+    /// Remove cached tiles
+    pub fn purge_cache(&self,
+                       tileset_name: &str,
+                       minzoom: Option<u8>,
+                       maxzoom: Option<u8>,
+                       extent: Option<Extent>)
+                       -> Result<u64, io::Error> {
+        let minzoom = minzoom.unwrap_or(0);
+        let maxzoom = maxzoom.unwrap_or(self.grid.maxzoom());
+        let limits = extent.map(|extent| {
+            // Convert tile limits to XYZ scheme used in cache paths
+            self.grid
+                .tile_limits(extent, 0)
+                .iter()
+                .enumerate()
+                .map(|(zoom, limit)| {
+                    let zoom = zoom as u8;
+                    if limit.maxy <= limit.miny {
+                        return ExtentInt { minx: 0, miny: 0, maxx: 0, maxy: 0 };
+                    }
+                    let y1 = self.grid.ytile_from_xyz(limit.miny, zoom);
+                    let y2 = self.grid.ytile_from_xyz(limit.maxy - 1, zoom);
+                    ExtentInt {
+                        minx: limit.minx,
+                        miny: cmp::min(y1, y2),
+                        maxx: limit.maxx,
+                        maxy: cmp::max(y1, y2) + 1,
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+        self.tileset_cache(tileset_name)
+            .purge(tileset_name, minzoom, maxzoom, limits.as_ref().map(|l| &l[..]))
     }
     pub fn init_cache(&self) {
-        if let Tilecache::Filecache(ref fc) = self.cache {
-            info!("Tile cache directory: {}", fc.basepath);
-            // Write metadata.json for each tileset
-            for tileset in &self.tilesets {
+        // Write metadata for each tileset into its cache
+        for tileset in &self.tilesets {
+            let cache = self.tileset_cache(&tileset.name);
+            if let &Tilecache::Filecache(ref fc) = cache {
+                info!("Tile cache directory: {}", fc.basepath);
                 let path = Path::new(&fc.basepath).join(&tileset.name);
                 fs::create_dir_all(&path).unwrap();
                 let f = File::create(&path.join("metadata.json")).unwrap();
                 let _ = serde_json::to_writer(f,
                                               &self.get_mbtiles_metadata(&tileset.name).unwrap());
             }
+            if let &Tilecache::Mbtilescache(ref mbtiles) = cache {
+                info!("Tile cache MBTiles file: {}", mbtiles.tileset_path(&tileset.name));
+                let metadata = self.get_mbtiles_metadata(&tileset.name).unwrap();
+                if let Err(err) = mbtiles.write_metadata(&tileset.name, &metadata) {
+                    error!("Writing MBTiles metadata failed: {}", err);
+                }
+            }
+            if let &Tilecache::S3cache(ref s3) = cache {
+                info!("Tile cache S3 bucket: {}", s3.bucket);
+                let path = format!("{}/metadata.json", &tileset.name);
+                let metadata = serde_json::to_vec(&self.get_mbtiles_metadata(&tileset.name)
+                                                      .unwrap())
+                        .unwrap();
+                if let Err(err) = cache.write(&path, &metadata) {
+                    error!("Writing {} failed: {}", path, err);
+                }
+            }
         }
     }
 }
 
+/// Report tiles which were not cached because of failed or timed out queries
+fn log_skipped_tiles(skipped: u64) {
+    if skipped > 0 {
+        warn!("{} tiles not cached because of failed or timed out queries", skipped);
+    }
+}
+
+/// Number of tiles within tile limits
+fn tile_count(limits: &ExtentInt) -> u64 {
+    (limits.maxx as u64 - limits.minx as u64) * (limits.maxy as u64 - limits.miny as u64)
+}
+
+/// Zoom level showing the whole extent (WGS84), limited to 0-14
+fn extent_zoom(extent: &Extent) -> u8 {
+    let span = (extent.maxx - extent.minx).max(extent.maxy - extent.miny);
+    if span <= 0.0 {
+        return 14;
+    }
+    (360.0 / span).log2().floor().max(0.0).min(14.0) as u8
+}
+
 
 impl Tileset {
+    /// Tileset with default settings
+    pub fn new(name: &str, layers: Vec<Layer>) -> Tileset {
+        Tileset {
+            name: name.to_string(),
+            layers: layers,
+            extent: None,
+            viewer: None,
+            attribution: None,
+            description: None,
+            tms: false,
+            cache: None,
+        }
+    }
     pub fn tilesets_from_config(config: &toml::Value) -> Result<Vec<Self>, String> {
         config
             .get("tileset")
@@ -442,16 +1720,22 @@ impl Tileset {
                               .ok_or("Array type for [[tileset]] entry expected".to_string())
                       })
             .and_then(|tilesets| {
-                          Ok(tilesets
-                                 .iter()
-                                 .map(|tileset| Tileset::from_config(tileset).unwrap())
-                                 .collect())
+                          tilesets
+                              .iter()
+                              .map(|tileset| Tileset::from_config(tileset))
+                              .collect()
                       })
     }
-    pub fn gen_runtime_config_from_input(&self, input: &PostgisInput) -> String {
+    pub fn gen_runtime_config_from_input(&self, datasources: &Datasources) -> String {
         let mut config = String::new();
         for layer in &self.layers {
-            config.push_str(&layer.gen_runtime_config_from_input(input));
+            match datasources.for_layer(layer) {
+                Some(&Datasource::Postgis(ref pg)) => {
+                    config.push_str(&layer.gen_runtime_config_from_input(pg));
+                }
+                Some(_) => config.push_str(&layer.gen_runtime_config()),
+                None => {}
+            }
         }
         config
     }
@@ -467,12 +1751,65 @@ impl Config<Tileset> for Tileset {
                               .ok_or("tileset.name entry is not a string".to_string())
                       })
             .map(|v| v.to_string());
-        let layers = try!(Layer::layers_from_config(config));
+        let mut layers = try!(Layer::layers_from_config(config));
+        if let Some(val) = config.get("mvt_extent") {
+            let mvt_extent = try!(val.as_integer()
+                                      .ok_or("tileset.mvt_extent entry is not an integer"
+                                                 .to_string()));
+            // Default for layers of the tileset
+            for layer in layers.iter_mut().filter(|l| l.mvt_extent.is_none()) {
+                layer.mvt_extent = Some(mvt_extent as u32);
+            }
+        }
+        let label_layers: Vec<Layer> = layers.iter().filter_map(|l| l.label_layer()).collect();
+        layers.extend(label_layers);
+        let extent = match config.get("extent") {
+            Some(ext) => {
+                Some(try!(ext.clone()
+                              .try_into::<Extent>()
+                              .map_err(|e| format!("Error reading tileset extent - {}", e))))
+            }
+            None => None,
+        };
+        let viewer = config.get("viewer").and_then(|val| val.as_bool());
+        let attribution = config
+            .get("attribution")
+            .and_then(|val| val.as_str())
+            .map(|v| v.to_string());
+        let description = config
+            .get("description")
+            .and_then(|val| val.as_str())
+            .map(|v| v.to_string());
+        let tms = match config.get("scheme").and_then(|val| val.as_str()) {
+            Some("tms") => true,
+            Some("xyz") | None => false,
+            Some(scheme) => return Err(format!("Invalid tileset scheme '{}'", scheme)),
+        };
+        // `[tileset.cache.*]` overrides the service cache, `cache = false` disables caching
+        let cache = match config.get("cache") {
+            Some(&toml::Value::Boolean(false)) => Some(Tilecache::Nocache(Nocache)),
+            Some(&toml::Value::Boolean(true)) |
+            None => None,
+            Some(&toml::Value::Table(_)) => Some(try!(Tilecache::from_config(config))),
+            Some(_) => return Err("tileset.cache entry is not a boolean or table".to_string()),
+        };
         name.and_then(|n| {
-                          Ok(Tileset {
-                                 name: n,
-                                 layers: layers,
-                             })
+                          // Layer names are the public MVT layer ids
+                          for (i, layer) in layers.iter().enumerate() {
+                              if layers[..i].iter().any(|l| l.name == layer.name) {
+                                  return Err(format!("Duplicate layer '{}' in tileset '{}'",
+                                                     layer.name,
+                                                     n));
+                              }
+                          }
+                          let mut tileset = Tileset::new(&n, layers);
+                          tileset.extent = extent;
+                          tileset.viewer = viewer;
+                          tileset.attribution = attribution;
+                          tileset.description = description;
+                          tileset.tms = tms;
+                          tileset.cache = cache;
+                          Ok(tileset)
                       })
     }
     fn gen_config() -> String {
@@ -491,21 +1828,101 @@ impl Config<Tileset> for Tileset {
 
 impl Config<MvtService> for MvtService {
     fn from_config(config: &toml::Value) -> Result<Self, String> {
-        let pg = try!(PostgisInput::from_config(config));
+        let datasources = try!(Datasources::from_config(config));
         let grid = try!(Grid::from_config(config));
         let tilesets = try!(Tileset::tilesets_from_config(config));
+        {
+            let layers: Vec<&Layer> = tilesets.iter().flat_map(|ts| ts.layers.iter()).collect();
+            try!(datasources.check_layers(&layers));
+            for layer in layers {
+                try!(layer.validate());
+            }
+        }
         let cache = try!(Tilecache::from_config(config));
-        Ok(MvtService {
-               input: pg,
-               grid: grid,
-               tilesets: tilesets,
-               cache: cache,
-           })
+        if !grid.is_quadtree() {
+            let caches = Some(&cache)
+                .into_iter()
+                .chain(tilesets.iter().filter_map(|ts| ts.cache.as_ref()));
+            for cache in caches {
+                if let &Tilecache::Mbtilescache(_) = cache {
+                    return Err(MBTILES_GRID_ERROR.to_string());
+                }
+            }
+        }
+        let cache_empty_tiles = config
+            .get("service")
+            .and_then(|s| s.get("mvt"))
+            .and_then(|mvt| mvt.get("cache_empty_tiles"))
+            .map_or(false, |val| val.as_bool().unwrap_or(false));
+        let compute_extent = config
+            .get("service")
+            .and_then(|s| s.get("mvt"))
+            .and_then(|mvt| mvt.get("compute_extent"))
+            .map_or(false, |val| val.as_bool().unwrap_or(false));
+        let serve_stale_on_timeout = config
+            .get("service")
+            .and_then(|s| s.get("mvt"))
+            .and_then(|mvt| mvt.get("serve_stale_on_timeout"))
+            .map_or(false, |val| val.as_bool().unwrap_or(false));
+        let stale_while_revalidate = config
+            .get("service")
+            .and_then(|s| s.get("mvt"))
+            .and_then(|mvt| mvt.get("stale_while_revalidate"))
+            .map_or(false, |val| val.as_bool().unwrap_or(false));
+        let revalidation_threads = config
+            .get("service")
+            .and_then(|s| s.get("mvt"))
+            .and_then(|mvt| mvt.get("revalidation_threads"))
+            .map_or(DEFAULT_REVALIDATION_THREADS as i64,
+                    |val| val.as_integer().unwrap_or(DEFAULT_REVALIDATION_THREADS as i64));
+        if revalidation_threads < 1 {
+            return Err("revalidation_threads must be at least 1".to_string());
+        }
+        let revalidator = if stale_while_revalidate {
+            Some(Revalidator::new(revalidation_threads as usize))
+        } else {
+            None
+        };
+        let viewer = config
+            .get("service")
+            .and_then(|s| s.get("mvt"))
+            .and_then(|mvt| mvt.get("viewer"))
+            .map_or(true, |val| val.as_bool().unwrap_or(true));
+        let gzip_level = match config.get("webserver").and_then(|ws| ws.get("gzip_level")) {
+            Some(val) => {
+                try!(val.as_integer()
+                         .ok_or("webserver.gzip_level entry is not an integer".to_string()))
+            }
+            None => DEFAULT_GZIP_LEVEL as i64,
+        };
+        if !GZIP_LEVELS.iter().any(|&level| level as i64 == gzip_level) {
+            return Err(format!("Invalid gzip_level {} (expected 0, 1, 6 or 9)", gzip_level));
+        }
+        let gzip_min_bytes = match config.get("webserver").and_then(|ws| ws.get("gzip_min_bytes")) {
+            Some(val) => {
+                try!(val.as_integer()
+                         .ok_or("webserver.gzip_min_bytes entry is not an integer".to_string()))
+            }
+            None => DEFAULT_GZIP_MIN_BYTES as i64,
+        };
+        if gzip_min_bytes < 0 {
+            return Err(format!("Invalid gzip_min_bytes {}", gzip_min_bytes));
+        }
+        let mut service = MvtService::new(datasources, grid, tilesets);
+        service.cache = cache;
+        service.cache_empty_tiles = cache_empty_tiles;
+        service.compute_extent = compute_extent;
+        service.gzip_level = gzip_level as u32;
+        service.gzip_min_bytes = gzip_min_bytes as usize;
+        service.serve_stale_on_timeout = serve_stale_on_timeout;
+        service.revalidator = revalidator;
+        service.viewer = viewer;
+        Ok(service)
     }
     fn gen_config() -> String {
         let mut config = String::new();
         config.push_str(TOML_SERVICES);
-        config.push_str(&Datasource::gen_config());
+        config.push_str(&Datasources::gen_config());
         config.push_str(&Grid::gen_config());
         config.push_str(&Tileset::gen_config());
         config.push_str(&Tilecache::gen_config());
@@ -514,10 +1931,10 @@ impl Config<MvtService> for MvtService {
     fn gen_runtime_config(&self) -> String {
         let mut config = String::new();
         config.push_str(TOML_SERVICES);
-        config.push_str(&self.input.gen_runtime_config());
+        config.push_str(&self.datasources.gen_runtime_config());
         config.push_str(&self.grid.gen_runtime_config());
         for tileset in &self.tilesets {
-            config.push_str(&tileset.gen_runtime_config_from_input(&self.input));
+            config.push_str(&tileset.gen_runtime_config_from_input(&self.datasources));
         }
         config.push_str(&self.cache.gen_runtime_config());
         config
@@ -0,0 +1,277 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Writer for single-file tile archives in PMTiles v3 format
+//! (https://github.com/protomaps/PMTiles/blob/main/spec/v3/spec.md)
+
+use core::grid::Extent;
+use mvt::tile::Tile;
+use serde_json;
+use sha1::Sha1;
+use std::collections::HashMap;
+use std::cmp;
+use std::io::{self, Read, Write};
+
+
+const HEADER_LEN: usize = 127;
+/// Header and root directory have to fit into the first 16 KiB
+const MAX_ROOT_LEN: usize = 16384 - HEADER_LEN;
+
+const COMPRESSION_GZIP: u8 = 2;
+const TILE_TYPE_MVT: u8 = 1;
+
+/// Rotate quadrant of Hilbert curve.
+/// Only the bits below `n` are used afterwards, so wrapping is harmless.
+fn rotate(n: u64, x: &mut u64, y: &mut u64, rx: u64, ry: u64) {
+    if ry == 0 {
+        if rx == 1 {
+            *x = (n - 1).wrapping_sub(*x);
+            *y = (n - 1).wrapping_sub(*y);
+        }
+        ::std::mem::swap(x, y);
+    }
+}
+
+/// PMTiles tile id: Position on Hilbert curve of zoom level plus number of tiles of lower levels
+pub fn zxy_to_tile_id(z: u8, x: u32, y: u32) -> u64 {
+    // Sum of 4^i for i < z
+    let acc = ((1u64 << (2 * z as u64)) - 1) / 3;
+    let (mut x, mut y) = (x as u64, y as u64);
+    let mut d = 0;
+    let mut s = (1u64 << z) / 2;
+    while s > 0 {
+        let rx = if x & s > 0 { 1 } else { 0 };
+        let ry = if y & s > 0 { 1 } else { 0 };
+        d += s * s * ((3 * rx) ^ ry);
+        rotate(s, &mut x, &mut y, rx, ry);
+        s /= 2;
+    }
+    acc + d
+}
+
+/// Directory entry. Entries with `run_length` 0 point to leaf directories.
+#[derive(PartialEq, Debug)]
+pub struct Entry {
+    pub tile_id: u64,
+    pub offset: u64,
+    pub length: u32,
+    pub run_length: u32,
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Serialize and gzip compress directory entries sorted by tile id
+pub fn serialize_directory(entries: &[Entry]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_varint(&mut buf, entries.len() as u64);
+    let mut last_id = 0;
+    for entry in entries {
+        write_varint(&mut buf, entry.tile_id - last_id);
+        last_id = entry.tile_id;
+    }
+    for entry in entries {
+        write_varint(&mut buf, entry.run_length as u64);
+    }
+    for entry in entries {
+        write_varint(&mut buf, entry.length as u64);
+    }
+    for (i, entry) in entries.iter().enumerate() {
+        // Offset 0 means contiguous to the previous entry
+        if i > 0 && entry.offset == entries[i - 1].offset + entries[i - 1].length as u64 {
+            write_varint(&mut buf, 0);
+        } else {
+            write_varint(&mut buf, entry.offset + 1);
+        }
+    }
+    Tile::gzip(&buf)
+}
+
+/// Split entries into leaf directories of `leaf_size` entries
+fn build_roots_leaves(entries: &[Entry], leaf_size: usize) -> (Vec<u8>, Vec<u8>) {
+    let mut root_entries = Vec::new();
+    let mut leaves = Vec::new();
+    for chunk in entries.chunks(leaf_size) {
+        let leaf = serialize_directory(chunk);
+        root_entries.push(Entry {
+                              tile_id: chunk[0].tile_id,
+                              offset: leaves.len() as u64,
+                              length: leaf.len() as u32,
+                              run_length: 0,
+                          });
+        leaves.extend_from_slice(&leaf);
+    }
+    (serialize_directory(&root_entries), leaves)
+}
+
+/// Root directory and leaf directories section
+pub fn build_directories(entries: &[Entry]) -> (Vec<u8>, Vec<u8>) {
+    let root = serialize_directory(entries);
+    if root.len() <= MAX_ROOT_LEN {
+        return (root, Vec::new());
+    }
+    let mut leaf_size = cmp::max(4096, entries.len() / 3500);
+    loop {
+        let (root, leaves) = build_roots_leaves(entries, leaf_size);
+        if root.len() <= MAX_ROOT_LEN {
+            return (root, leaves);
+        }
+        leaf_size *= 2;
+    }
+}
+
+fn coord_e7(value: f64) -> i32 {
+    (value * 10_000_000.0).round() as i32
+}
+
+/// PMTiles archive with gzip compressed MVT tiles.
+/// Tiles have to be added in tile id order. Their data is written to a separate
+/// tile data section, only the directory entries are kept in memory.
+pub struct PmtilesWriter {
+    pub minzoom: u8,
+    pub maxzoom: u8,
+    /// Bounds in WGS84 coordinates
+    pub bounds: Extent,
+    /// Center longitude, latitude and zoom level
+    pub center: (f64, f64, u8),
+    pub metadata: serde_json::Value,
+    entries: Vec<Entry>,
+    /// Offset of tile contents by SHA1 digest, for storing identical tiles only once
+    contents: HashMap<[u8; 20], u64>,
+    addressed_tiles: u64,
+    data_len: u64,
+}
+
+impl PmtilesWriter {
+    pub fn new(minzoom: u8, maxzoom: u8, bounds: Extent) -> PmtilesWriter {
+        let center = ((bounds.minx + bounds.maxx) / 2.0, (bounds.miny + bounds.maxy) / 2.0, minzoom);
+        PmtilesWriter {
+            minzoom: minzoom,
+            maxzoom: maxzoom,
+            bounds: bounds,
+            center: center,
+            metadata: json!({}),
+            entries: Vec::new(),
+            contents: HashMap::new(),
+            addressed_tiles: 0,
+            data_len: 0,
+        }
+    }
+    /// Add gzip compressed tile with `tile_id` (see `zxy_to_tile_id`) and write its
+    /// data to the tile data section `data` unless an identical tile was added before.
+    pub fn add_tile<W: Write>(&mut self,
+                              data: &mut W,
+                              tile_id: u64,
+                              tilegz: &[u8])
+                              -> Result<(), io::Error> {
+        if let Some(last) = self.entries.last() {
+            if tile_id < last.tile_id + last.run_length as u64 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                          format!("Tile {} added after tile {}",
+                                                  tile_id,
+                                                  last.tile_id)));
+            }
+        }
+        let mut hash = Sha1::new();
+        hash.update(tilegz);
+        let digest = hash.digest().bytes();
+        let offset = match self.contents.get(&digest).cloned() {
+            Some(offset) => offset,
+            None => {
+                let offset = self.data_len;
+                try!(data.write_all(tilegz));
+                self.data_len += tilegz.len() as u64;
+                self.contents.insert(digest, offset);
+                offset
+            }
+        };
+        self.addressed_tiles += 1;
+        // Consecutive identical tiles are stored as run
+        if let Some(last) = self.entries.last_mut() {
+            if last.offset == offset && last.tile_id + last.run_length as u64 == tile_id {
+                last.run_length += 1;
+                return Ok(());
+            }
+        }
+        self.entries.push(Entry {
+                              tile_id: tile_id,
+                              offset: offset,
+                              length: tilegz.len() as u32,
+                              run_length: 1,
+                          });
+        Ok(())
+    }
+    /// Write archive with the tile data section written by `add_tile`.
+    /// Returns the number of bytes written.
+    pub fn write<W: Write, R: Read>(&self, out: &mut W, data: &mut R) -> Result<u64, io::Error> {
+        let (root, leaves) = build_directories(&self.entries);
+        let metadata = Tile::gzip(self.metadata.to_string().as_bytes());
+
+        let root_offset = HEADER_LEN as u64;
+        let metadata_offset = root_offset + root.len() as u64;
+        let leaves_offset = metadata_offset + metadata.len() as u64;
+        let data_offset = leaves_offset + leaves.len() as u64;
+
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(b"PMTiles");
+        header.push(3);
+        for value in &[root_offset,
+                       root.len() as u64,
+                       metadata_offset,
+                       metadata.len() as u64,
+                       leaves_offset,
+                       leaves.len() as u64,
+                       data_offset,
+                       self.data_len,
+                       self.addressed_tiles,
+                       self.entries.len() as u64,
+                       self.contents.len() as u64] {
+            header.extend_from_slice(&u64_le(*value));
+        }
+        header.push(1); // clustered
+        header.push(COMPRESSION_GZIP); // internal compression
+        header.push(COMPRESSION_GZIP); // tile compression
+        header.push(TILE_TYPE_MVT);
+        header.push(self.minzoom);
+        header.push(self.maxzoom);
+        for value in &[self.bounds.minx, self.bounds.miny, self.bounds.maxx, self.bounds.maxy] {
+            header.extend_from_slice(&i32_le(coord_e7(*value)));
+        }
+        header.push(self.center.2);
+        header.extend_from_slice(&i32_le(coord_e7(self.center.0)));
+        header.extend_from_slice(&i32_le(coord_e7(self.center.1)));
+
+        try!(out.write_all(&header));
+        try!(out.write_all(&root));
+        try!(out.write_all(&metadata));
+        try!(out.write_all(&leaves));
+        let copied = try!(io::copy(data, out));
+        if copied != self.data_len {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                      format!("Tile data section has {} bytes instead of {}",
+                                              copied,
+                                              self.data_len)));
+        }
+        Ok(data_offset + self.data_len)
+    }
+}
+
+fn u64_le(value: u64) -> [u8; 8] {
+    let mut bytes = [0; 8];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = (value >> (8 * i)) as u8;
+    }
+    bytes
+}
+
+fn i32_le(value: i32) -> [u8; 4] {
+    let value = value as u32;
+    [value as u8, (value >> 8) as u8, (value >> 16) as u8, (value >> 24) as u8]
+}
@@ -0,0 +1,134 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use service::pmtiles::{PmtilesWriter, Entry, zxy_to_tile_id, serialize_directory, build_directories};
+use core::grid::Extent;
+use mvt::tile::Tile;
+use std::io::Cursor;
+
+
+fn read_u64(data: &[u8], pos: usize) -> u64 {
+    (0..8).fold(0, |acc, i| acc | (data[pos + i] as u64) << (8 * i))
+}
+
+fn read_i32(data: &[u8], pos: usize) -> i32 {
+    (0..4).fold(0u32, |acc, i| acc | (data[pos + i] as u32) << (8 * i)) as i32
+}
+
+#[test]
+fn test_tile_id() {
+    assert_eq!(zxy_to_tile_id(0, 0, 0), 0);
+    assert_eq!(zxy_to_tile_id(1, 0, 0), 1);
+    assert_eq!(zxy_to_tile_id(1, 0, 1), 2);
+    assert_eq!(zxy_to_tile_id(1, 1, 1), 3);
+    assert_eq!(zxy_to_tile_id(1, 1, 0), 4);
+    assert_eq!(zxy_to_tile_id(2, 0, 0), 5);
+    assert_eq!(zxy_to_tile_id(12, 3423, 1763), 19078479);
+    assert_eq!(zxy_to_tile_id(20, 0, 0), 366503875925);
+}
+
+#[test]
+fn test_directory() {
+    let entries = vec![Entry {
+                           tile_id: 1,
+                           offset: 0,
+                           length: 1,
+                           run_length: 2,
+                       },
+                       Entry {
+                           tile_id: 3,
+                           offset: 1,
+                           length: 1,
+                           run_length: 1,
+                       },
+                       Entry {
+                           tile_id: 300,
+                           offset: 0,
+                           length: 1,
+                           run_length: 1,
+                       }];
+    let dir = Tile::gunzip(&serialize_directory(&entries));
+    // count, tile id deltas, run lengths, lengths, offsets (0 = contiguous)
+    assert_eq!(dir, vec![3, 1, 2, 169, 2, 2, 1, 1, 1, 1, 1, 1, 0, 1]);
+
+    let (root, leaves) = build_directories(&entries);
+    assert_eq!(root, serialize_directory(&entries));
+    assert!(leaves.is_empty());
+}
+
+#[test]
+fn test_leaf_directories() {
+    // Pseudo-random offsets to prevent compressing the root directory below 16 KiB
+    let mut seed: u64 = 1;
+    let entries: Vec<Entry> = (0..40000)
+        .map(|i| {
+                 seed = (seed * 1103515245 + 12345) % (1 << 31);
+                 Entry {
+                     tile_id: i * 3,
+                     offset: seed,
+                     length: 500 + (seed % 1000) as u32,
+                     run_length: 1,
+                 }
+             })
+        .collect();
+    let (root, leaves) = build_directories(&entries);
+    assert!(root.len() <= 16384 - 127);
+    assert!(!leaves.is_empty());
+    // Root entries point to leaf directories
+    let root = Tile::gunzip(&root);
+    assert_eq!(root[0], 10); // 40000 entries in leaves of 4096 entries
+}
+
+#[test]
+fn test_write_archive() {
+    let bounds = Extent {
+        minx: -180.0,
+        miny: -85.0511287798066,
+        maxx: 180.0,
+        maxy: 85.0511287798066,
+    };
+    let mut writer = PmtilesWriter::new(0, 1, bounds);
+    writer.metadata = json!({"name": "test"});
+    let mut data = Vec::new();
+    writer.add_tile(&mut data, zxy_to_tile_id(1, 0, 0), b"a").unwrap();
+    writer.add_tile(&mut data, zxy_to_tile_id(1, 0, 1), b"a").unwrap();
+    writer.add_tile(&mut data, zxy_to_tile_id(1, 1, 1), b"b").unwrap();
+    writer.add_tile(&mut data, zxy_to_tile_id(1, 1, 0), b"a").unwrap();
+    // Tiles have to be added in tile id order
+    assert!(writer.add_tile(&mut data, zxy_to_tile_id(1, 0, 1), b"c").is_err());
+    assert_eq!(data, b"ab");
+
+    let mut out = Vec::new();
+    let size = writer.write(&mut out, &mut Cursor::new(data)).unwrap();
+    assert_eq!(size, out.len() as u64);
+    assert_eq!(&out[0..7], b"PMTiles");
+    assert_eq!(out[7], 3);
+
+    let root_offset = read_u64(&out, 8) as usize;
+    let root_len = read_u64(&out, 16) as usize;
+    let metadata_offset = read_u64(&out, 24) as usize;
+    let metadata_len = read_u64(&out, 32) as usize;
+    assert_eq!(root_offset, 127);
+    assert_eq!(metadata_offset, root_offset + root_len);
+    assert_eq!(read_u64(&out, 48), 0); // no leaf directories
+    let data_offset = read_u64(&out, 56) as usize;
+    assert_eq!(read_u64(&out, 64), 2);
+    assert_eq!(&out[data_offset..], b"ab");
+    assert_eq!(read_u64(&out, 72), 4); // addressed tiles
+    assert_eq!(read_u64(&out, 80), 3); // tile entries
+    assert_eq!(read_u64(&out, 88), 2); // tile contents
+    assert_eq!(&out[96..102], &[1, 2, 2, 1, 0, 1]);
+    assert_eq!(read_i32(&out, 102), -1800000000);
+    assert_eq!(read_i32(&out, 106), -850511288);
+    assert_eq!(read_i32(&out, 110), 1800000000);
+    assert_eq!(read_i32(&out, 114), 850511288);
+
+    // (1,0,0) and (1,0,1) are stored as run of identical tiles
+    let root = Tile::gunzip(&out[root_offset..root_offset + root_len]);
+    assert_eq!(root, vec![3, 1, 2, 1, 2, 1, 1, 1, 1, 1, 1, 0, 1]);
+
+    let metadata = Tile::gunzip(&out[metadata_offset..metadata_offset + metadata_len]);
+    assert_eq!(String::from_utf8(metadata).unwrap(), r#"{"name":"test"}"#);
+}
@@ -0,0 +1,139 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use service::mvt::{MvtService, panic_message};
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::thread;
+
+
+/// Default number of background regeneration threads
+pub const DEFAULT_REVALIDATION_THREADS: usize = 2;
+/// Maximal number of queued regenerations. Further stale tiles are regenerated on a later request.
+const REVALIDATION_QUEUE_SIZE: usize = 1000;
+
+struct RevalidationJob {
+    service: Arc<MvtService>,
+    tileset: String,
+    xtile: u32,
+    ytile: u32,
+    zoom: u8,
+    key: String,
+}
+
+/// Bounded worker pool regenerating expired cached tiles in the background
+pub struct Revalidator {
+    sender: Mutex<SyncSender<RevalidationJob>>,
+    /// Shared with the workers. Keeps the queue open without worker threads.
+    _receiver: Arc<Mutex<Receiver<RevalidationJob>>>,
+    /// Tiles queued or in regeneration
+    pending: Arc<Mutex<HashSet<String>>>,
+}
+
+/// Removes a tile from the pending tiles when its regeneration is finished or panicked
+struct PendingGuard<'a> {
+    pending: &'a Mutex<HashSet<String>>,
+    key: &'a str,
+}
+
+impl<'a> Drop for PendingGuard<'a> {
+    fn drop(&mut self) {
+        // Lock may be poisoned by a panic of another worker
+        let mut pending = match self.pending.lock() {
+            Ok(pending) => pending,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        pending.remove(self.key);
+    }
+}
+
+fn run_worker(receiver: Arc<Mutex<Receiver<RevalidationJob>>>,
+              pending: Arc<Mutex<HashSet<String>>>) {
+    loop {
+        // Lock is released before the regeneration starts
+        let job = receiver.lock().unwrap().recv();
+        match job {
+            Ok(job) => {
+                let _guard = PendingGuard {
+                    pending: &pending,
+                    key: &job.key,
+                };
+                // Keep the worker alive if the regeneration panics
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    job.service
+                        .regenerate_tile(&job.tileset, job.xtile, job.ytile, job.zoom)
+                }));
+                if let Err(cause) = result {
+                    error!("Regeneration of {} panicked - keeping stale tile: {}",
+                           job.key,
+                           panic_message(&*cause));
+                }
+            }
+            // All senders dropped with the service
+            Err(_) => break,
+        }
+    }
+}
+
+impl Revalidator {
+    pub fn new(threads: usize) -> Revalidator {
+        Revalidator::with_queue_size(threads, REVALIDATION_QUEUE_SIZE)
+    }
+    pub fn with_queue_size(threads: usize, queue_size: usize) -> Revalidator {
+        let (sender, receiver) = sync_channel(queue_size);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let pending = Arc::new(Mutex::new(HashSet::new()));
+        for _ in 0..threads {
+            let receiver = receiver.clone();
+            let pending = pending.clone();
+            thread::spawn(move || run_worker(receiver, pending));
+        }
+        Revalidator {
+            sender: Mutex::new(sender),
+            _receiver: receiver,
+            pending: pending,
+        }
+    }
+    /// Queue regeneration of tile at x, y, z in XYZ adressing scheme.
+    /// Returns false if the tile is already queued or the queue is full.
+    pub fn enqueue(&self,
+                   service: Arc<MvtService>,
+                   tileset: &str,
+                   xtile: u32,
+                   ytile: u32,
+                   zoom: u8)
+                   -> bool {
+        let key = format!("{}/{}/{}/{}", tileset, zoom, xtile, ytile);
+        if !self.pending.lock().unwrap().insert(key.clone()) {
+            debug!("Regeneration of {} already queued", key);
+            return false;
+        }
+        let job = RevalidationJob {
+            service: service,
+            tileset: tileset.to_string(),
+            xtile: xtile,
+            ytile: ytile,
+            zoom: zoom,
+            key: key.clone(),
+        };
+        let sent = self.sender.lock().unwrap().try_send(job);
+        match sent {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) |
+            Err(TrySendError::Disconnected(_)) => {
+                debug!("Regeneration queue full - skipping {}", key);
+                self.pending.lock().unwrap().remove(&key);
+                false
+            }
+        }
+    }
+    /// Number of tiles queued or in regeneration
+    #[cfg(test)]
+    pub fn pending(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+}
@@ -0,0 +1,51 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use service::mvt_test::geojson_service;
+use service::revalidate::Revalidator;
+use std::sync::Arc;
+
+
+#[test]
+fn test_revalidation_queue() {
+    let service = Arc::new(geojson_service());
+    // Without workers, jobs stay in the queue
+    let revalidator = Revalidator::with_queue_size(0, 2);
+    assert!(revalidator.enqueue(service.clone(), "places", 133, 90, 8));
+    // Concurrent revalidations of the same tile are deduplicated
+    assert!(!revalidator.enqueue(service.clone(), "places", 133, 90, 8));
+    assert!(revalidator.enqueue(service.clone(), "places", 134, 90, 8));
+    assert_eq!(revalidator.pending(), 2);
+    // Queue is full
+    assert!(!revalidator.enqueue(service.clone(), "places", 135, 90, 8));
+    assert_eq!(revalidator.pending(), 2);
+}
+
+#[test]
+fn test_revalidation_panic() {
+    use std::thread;
+    use std::time::Duration;
+
+    let service = Arc::new(geojson_service());
+    let revalidator = Revalidator::with_queue_size(1, 10);
+    // Zoom level outside of grid panics
+    assert!(revalidator.enqueue(service.clone(), "places", 0, 0, 50));
+    for _ in 0..100 {
+        if revalidator.pending() == 0 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(revalidator.pending(), 0);
+    // Worker is still running
+    assert!(revalidator.enqueue(service.clone(), "places", 0, 0, 51));
+    for _ in 0..100 {
+        if revalidator.pending() == 0 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(revalidator.pending(), 0);
+}
@@ -0,0 +1,88 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+
+
+struct Flight<T> {
+    result: Mutex<Option<Result<T, String>>>,
+    done: Condvar,
+}
+
+/// Coalesces concurrent calls with the same key into a single execution
+pub struct SingleFlight<T> {
+    flights: Mutex<HashMap<String, Arc<Flight<T>>>>,
+}
+
+/// Completes the flight of the running call. Waiters get an error if the call panicked.
+struct FlightGuard<'a, T: 'a + Clone> {
+    group: &'a SingleFlight<T>,
+    key: &'a str,
+    flight: Arc<Flight<T>>,
+    result: Option<Result<T, String>>,
+}
+
+impl<'a, T: Clone> Drop for FlightGuard<'a, T> {
+    fn drop(&mut self) {
+        let result = self.result
+            .take()
+            .unwrap_or_else(|| Err(format!("Concurrent generation of {} failed", self.key)));
+        // Later callers start a new flight
+        self.group.flights.lock().unwrap().remove(self.key);
+        *self.flight.result.lock().unwrap() = Some(result);
+        self.flight.done.notify_all();
+    }
+}
+
+impl<T: Clone> SingleFlight<T> {
+    pub fn new() -> SingleFlight<T> {
+        SingleFlight { flights: Mutex::new(HashMap::new()) }
+    }
+    /// Call `f` unless a call with the same `key` is already running.
+    /// In this case, wait for the running call and return a copy of its result.
+    pub fn run<F>(&self, key: &str, f: F) -> Result<T, String>
+        where F: FnOnce() -> T
+    {
+        let (flight, leader) = {
+            let mut flights = self.flights.lock().unwrap();
+            let running = flights.get(key).cloned();
+            match running {
+                Some(flight) => (flight, false),
+                None => {
+                    let flight = Arc::new(Flight {
+                                              result: Mutex::new(None),
+                                              done: Condvar::new(),
+                                          });
+                    flights.insert(key.to_string(), flight.clone());
+                    (flight, true)
+                }
+            }
+        };
+        if leader {
+            let mut guard = FlightGuard {
+                group: self,
+                key: key,
+                flight: flight,
+                result: None,
+            };
+            let result = f();
+            guard.result = Some(Ok(result.clone()));
+            Ok(result)
+        } else {
+            debug!("Waiting for concurrent generation of {}", key);
+            let mut result = flight.result.lock().unwrap();
+            while result.is_none() {
+                result = flight.done.wait(result).unwrap();
+            }
+            result.clone().unwrap()
+        }
+    }
+    /// Number of running calls
+    #[cfg(test)]
+    pub fn running(&self) -> usize {
+        self.flights.lock().unwrap().len()
+    }
+}
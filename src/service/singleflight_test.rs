@@ -0,0 +1,68 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use service::singleflight::SingleFlight;
+use std::sync::{Arc, Barrier};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+
+#[test]
+fn test_single_flight() {
+    let group = Arc::new(SingleFlight::new());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let barrier = Arc::new(Barrier::new(16));
+    let threads: Vec<_> = (0..16)
+        .map(|_| {
+            let group = group.clone();
+            let calls = calls.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                              barrier.wait();
+                              group.run("osm/6/33/22.pbf.gz", || {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(200));
+                    vec![1u8, 2, 3]
+                })
+                          })
+        })
+        .collect();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), Ok(vec![1u8, 2, 3]));
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(group.running(), 0);
+
+    // Completed calls are not reused
+    let result = group.run("osm/6/33/22.pbf.gz", || {
+        calls.fetch_add(1, Ordering::SeqCst);
+        vec![4u8]
+    });
+    assert_eq!(result, Ok(vec![4u8]));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_single_flight_error() {
+    let group: Arc<SingleFlight<Vec<u8>>> = Arc::new(SingleFlight::new());
+    let leader = {
+        let group = group.clone();
+        thread::spawn(move || {
+                          group.run("osm/6/33/22.pbf.gz", || {
+                thread::sleep(Duration::from_millis(200));
+                panic!("query failed");
+            })
+                      })
+    };
+    while group.running() == 0 {
+        thread::sleep(Duration::from_millis(1));
+    }
+    // Waiting callers get the error of the running call
+    assert_eq!(group.run("osm/6/33/22.pbf.gz", || vec![]),
+               Err("Concurrent generation of osm/6/33/22.pbf.gz failed".to_string()));
+    assert!(leader.join().is_err());
+    assert_eq!(group.running(), 0);
+}
@@ -0,0 +1,189 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! UTFGrid interaction grids (https://github.com/mapbox/utfgrid-spec)
+
+use core::feature::Feature;
+use core::geom::{GeometryType, LineString};
+use core::grid::Extent;
+use core::feature::json_attr_value;
+use serde_json;
+use std::char;
+use std::collections::HashMap;
+
+
+/// Pixels per grid cell
+pub const RESOLUTION: u32 = 4;
+
+/// Grid with feature keys rasterized from feature geometries
+pub struct UtfGrid {
+    size: u32,
+    extent: Extent,
+    /// Index into `keys` for each cell (0: no feature)
+    cells: Vec<u32>,
+    keys: Vec<String>,
+    /// Index of each key in `keys`
+    key_index: HashMap<String, u32>,
+    data: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Cell coordinates of a point
+type Coord = (f64, f64);
+
+impl UtfGrid {
+    /// Grid of `size` x `size` cells covering `extent`
+    pub fn new(size: u32, extent: &Extent) -> UtfGrid {
+        UtfGrid {
+            size: size,
+            extent: extent.clone(),
+            cells: vec![0; (size * size) as usize],
+            keys: vec!["".to_string()],
+            key_index: HashMap::new(),
+            data: serde_json::Map::new(),
+        }
+    }
+    /// Rasterize feature with attributes `fields`. Features added later cover previous features.
+    /// Features with the same fid share their key, features without fid get a key `_{index}`,
+    /// which can't collide with a fid.
+    pub fn add_feature(&mut self, feature: &Feature, fields: &[String]) {
+        let geom = match feature.geometry() {
+            Ok(geom) => geom,
+            Err(_) => return,
+        };
+        let key = match feature.fid() {
+            Some(fid) => fid.to_string(),
+            None => format!("_{}", self.keys.len()),
+        };
+        let mut attrs = serde_json::Map::new();
+        for attr in feature.attributes() {
+            if fields.contains(&attr.key) {
+                attrs.insert(attr.key, json_attr_value(&attr.value));
+            }
+        }
+        let next_index = self.keys.len() as u32;
+        let index = *self.key_index.entry(key.clone()).or_insert(next_index);
+        if index == next_index {
+            self.keys.push(key.clone());
+        }
+        self.data.insert(key, serde_json::Value::Object(attrs));
+        self.rasterize(&geom, index);
+    }
+    fn cell_coord(&self, x: f64, y: f64) -> Coord {
+        let size = self.size as f64;
+        ((x - self.extent.minx) / (self.extent.maxx - self.extent.minx) * size,
+         (self.extent.maxy - y) / (self.extent.maxy - self.extent.miny) * size)
+    }
+    fn set_cell(&mut self, col: i64, row: i64, index: u32) {
+        let size = self.size as i64;
+        if col >= 0 && col < size && row >= 0 && row < size {
+            self.cells[(row * size + col) as usize] = index;
+        }
+    }
+    fn rasterize(&mut self, geom: &GeometryType, index: u32) {
+        match geom {
+            &GeometryType::Point(ref p) => self.rasterize_point(p.x, p.y, index),
+            &GeometryType::MultiPoint(ref mp) => {
+                for p in &mp.points {
+                    self.rasterize_point(p.x, p.y, index);
+                }
+            }
+            &GeometryType::LineString(ref l) => self.rasterize_line(l, index),
+            &GeometryType::MultiLineString(ref ml) => {
+                for l in &ml.lines {
+                    self.rasterize_line(l, index);
+                }
+            }
+            &GeometryType::Polygon(ref p) => self.rasterize_rings(&p.rings, index),
+            &GeometryType::MultiPolygon(ref mp) => {
+                for p in &mp.polygons {
+                    self.rasterize_rings(&p.rings, index);
+                }
+            }
+            &GeometryType::GeometryCollection(_) => {}
+        }
+    }
+    /// Points cover the cell containing the point and its neighbours
+    fn rasterize_point(&mut self, x: f64, y: f64, index: u32) {
+        let (cx, cy) = self.cell_coord(x, y);
+        let (col, row) = (cx.floor() as i64, cy.floor() as i64);
+        for r in row - 1..row + 2 {
+            for c in col - 1..col + 2 {
+                self.set_cell(c, r, index);
+            }
+        }
+    }
+    fn rasterize_line(&mut self, line: &LineString, index: u32) {
+        let coords: Vec<Coord> = line.points
+            .iter()
+            .map(|p| self.cell_coord(p.x, p.y))
+            .collect();
+        for segment in coords.windows(2) {
+            let (p0, p1) = (segment[0], segment[1]);
+            // Sample segment in half cell steps
+            let len = ((p1.0 - p0.0).powi(2) + (p1.1 - p0.1).powi(2)).sqrt();
+            let steps = (len * 2.0).ceil().max(1.0).min(4.0 * self.size as f64) as u32;
+            for i in 0..steps + 1 {
+                let t = i as f64 / steps as f64;
+                let (cx, cy) = (p0.0 + t * (p1.0 - p0.0), p0.1 + t * (p1.1 - p0.1));
+                self.set_cell(cx.floor() as i64, cy.floor() as i64, index);
+            }
+        }
+    }
+    /// Cells with center inside polygon (even-odd rule)
+    fn rasterize_rings(&mut self, rings: &[LineString], index: u32) {
+        let rings: Vec<Vec<Coord>> = rings
+            .iter()
+            .map(|ring| ring.points.iter().map(|p| self.cell_coord(p.x, p.y)).collect())
+            .collect();
+        for row in 0..self.size {
+            let cy = row as f64 + 0.5;
+            // Crossings of ring edges with the row center line
+            let mut crossings = Vec::new();
+            for ring in &rings {
+                for edge in ring.windows(2) {
+                    let (p0, p1) = (edge[0], edge[1]);
+                    if (p0.1 > cy) != (p1.1 > cy) {
+                        crossings.push(p0.0 + (cy - p0.1) / (p1.1 - p0.1) * (p1.0 - p0.0));
+                    }
+                }
+            }
+            crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for span in crossings.chunks(2) {
+                if span.len() < 2 {
+                    continue;
+                }
+                let first = (span[0] - 0.5).ceil().max(0.0) as i64;
+                let last = (span[1] - 0.5).floor().min(self.size as f64 - 1.0) as i64;
+                for col in first..last + 1 {
+                    self.set_cell(col, row as i64, index);
+                }
+            }
+        }
+    }
+    /// UTFGrid JSON with `grid`, `keys` and `data`
+    pub fn to_json(&self) -> serde_json::Value {
+        let grid: Vec<String> = self.cells
+            .chunks(self.size as usize)
+            .map(|row| row.iter().map(|&index| encode_id(index)).collect())
+            .collect();
+        json!({
+            "grid": grid,
+            "keys": self.keys,
+            "data": self.data
+        })
+    }
+}
+
+/// Character of key index, skipping '"' and '\'
+pub fn encode_id(index: u32) -> char {
+    let mut code = index + 32;
+    if code >= 34 {
+        code += 1;
+    }
+    if code >= 92 {
+        code += 1;
+    }
+    char::from_u32(code).unwrap_or(' ')
+}
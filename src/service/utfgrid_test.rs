@@ -0,0 +1,125 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use service::utfgrid::{UtfGrid, encode_id};
+use core::feature::{FeatureStruct, FeatureAttr, FeatureAttrValType};
+use core::geom::{GeometryType, Point, LineString, Polygon};
+use core::grid::Extent;
+
+
+fn line(coords: &[(f64, f64)]) -> LineString {
+    LineString {
+        points: coords.iter().map(|&(x, y)| Point::new(x, y, None)).collect(),
+        srid: None,
+    }
+}
+
+fn feature(fid: Option<u64>, name: &str, geometry: GeometryType) -> FeatureStruct {
+    FeatureStruct {
+        fid: fid,
+        attributes: vec![FeatureAttr {
+                             key: "name".to_string(),
+                             value: FeatureAttrValType::String(name.to_string()),
+                         },
+                         FeatureAttr {
+                             key: "area".to_string(),
+                             value: FeatureAttrValType::Double(16.0),
+                         }],
+        geometry: geometry,
+    }
+}
+
+#[test]
+fn test_encode_id() {
+    assert_eq!(encode_id(0), ' ');
+    assert_eq!(encode_id(1), '!');
+    assert_eq!(encode_id(2), '#'); // '"' skipped
+    assert_eq!(encode_id(58), '[');
+    assert_eq!(encode_id(59), ']'); // '\' skipped
+}
+
+#[test]
+fn test_rasterize() {
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 8.0,
+        maxy: 8.0,
+    };
+    let mut grid = UtfGrid::new(8, &extent);
+    let fields = vec!["name".to_string()];
+    let square = Polygon {
+        rings: vec![line(&[(2.0, 2.0), (6.0, 2.0), (6.0, 6.0), (2.0, 6.0), (2.0, 2.0)])],
+        srid: None,
+    };
+    grid.add_feature(&feature(Some(1), "square", GeometryType::Polygon(square)),
+                     &fields);
+    grid.add_feature(&feature(Some(7),
+                              "corner",
+                              GeometryType::Point(Point::new(0.5, 7.5, None))),
+                     &fields);
+    grid.add_feature(&feature(None,
+                              "bottom",
+                              GeometryType::LineString(line(&[(0.0, 0.5), (8.0, 0.5)]))),
+                     &[]);
+
+    let json = grid.to_json();
+    assert_eq!(json["grid"],
+               json!(["##      ",
+                      "##      ",
+                      "  !!!!  ",
+                      "  !!!!  ",
+                      "  !!!!  ",
+                      "  !!!!  ",
+                      "        ",
+                      "$$$$$$$$"]));
+    assert_eq!(json["keys"], json!(["", "1", "7", "_3"]));
+    assert_eq!(json["data"],
+               json!({"1": {"name": "square"}, "7": {"name": "corner"}, "_3": {}}));
+}
+
+#[test]
+fn test_feature_keys() {
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 12.0,
+        maxy: 12.0,
+    };
+    let mut grid = UtfGrid::new(12, &extent);
+    let fields = vec!["name".to_string()];
+    let point = |x, y| GeometryType::Point(Point::new(x, y, None));
+    // Feature without fid doesn't share the key of a feature with fid 1
+    grid.add_feature(&feature(None, "anonymous", point(1.5, 10.5)), &fields);
+    grid.add_feature(&feature(Some(1), "first", point(4.5, 10.5)), &fields);
+    grid.add_feature(&feature(None, "other", point(7.5, 10.5)), &fields);
+    // Features with the same fid share their key
+    grid.add_feature(&feature(Some(1), "second", point(10.5, 10.5)), &fields);
+
+    let json = grid.to_json();
+    assert_eq!(json["grid"][0], json!("!!!###$$$###"));
+    assert_eq!(json["keys"], json!(["", "_1", "1", "_3"]));
+    assert_eq!(json["data"],
+               json!({"_1": {"name": "anonymous"}, "1": {"name": "second"}, "_3": {"name": "other"}}));
+}
+
+#[test]
+fn test_polygon_hole() {
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 4.0,
+        maxy: 4.0,
+    };
+    let mut grid = UtfGrid::new(4, &extent);
+    let polygon = Polygon {
+        rings: vec![line(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)]),
+                    line(&[(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0), (1.0, 1.0)])],
+        srid: None,
+    };
+    grid.add_feature(&feature(Some(1), "ring", GeometryType::Polygon(polygon)),
+                     &[]);
+    assert_eq!(grid.to_json()["grid"], json!(["!!!!", "!  !", "!  !", "!!!!"]));
+}
@@ -0,0 +1,350 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! OGC Web Map Tile Service (WMTS 1.0.0) with KVP encoding
+
+use core::grid::{Grid, Origin, Unit};
+use service::mvt::MvtService;
+use mvt::tile::MVT_CONTENT_TYPE;
+use std::f64::consts::PI;
+
+
+/// Size of the WMTS standardized rendering pixel in meters
+const STANDARDIZED_PIXEL_SIZE: f64 = 0.00028;
+
+#[derive(PartialEq, Debug)]
+pub enum WmtsRequest {
+    GetCapabilities,
+    /// Tile at `row` counted from the top of tile matrix `zoom`
+    GetTile {
+        layer: String,
+        zoom: u8,
+        row: u32,
+        col: u32,
+    },
+}
+
+/// OWS exception report
+#[derive(PartialEq, Debug)]
+pub struct WmtsException {
+    pub code: &'static str,
+    pub locator: String,
+    pub text: String,
+}
+
+impl WmtsException {
+    fn new(code: &'static str, locator: &str, text: String) -> WmtsException {
+        WmtsException {
+            code: code,
+            locator: locator.to_string(),
+            text: text,
+        }
+    }
+    fn missing(locator: &str) -> WmtsException {
+        WmtsException::new("MissingParameterValue",
+                           locator,
+                           format!("Missing parameter '{}'", locator))
+    }
+    fn invalid(locator: &str, value: &str) -> WmtsException {
+        WmtsException::new("InvalidParameterValue",
+                           locator,
+                           format!("Invalid value '{}' of parameter '{}'", value, locator))
+    }
+    pub fn http_status(&self) -> u16 {
+        match self.code {
+            "OperationNotSupported" => 501,
+            _ => 400,
+        }
+    }
+    pub fn to_xml(&self) -> String {
+        format!(r#"<?xml version="1.0" encoding="UTF-8"?>
+<ows:ExceptionReport xmlns:ows="http://www.opengis.net/ows/1.1" version="1.1.0" xml:lang="en">
+  <ows:Exception exceptionCode="{}" locator="{}">
+    <ows:ExceptionText>{}</ows:ExceptionText>
+  </ows:Exception>
+</ows:ExceptionReport>
+"#,
+                self.code,
+                xml_escape(&self.locator),
+                xml_escape(&self.text))
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = String::from_utf8_lossy(&bytes[i + 1..i + 3]).to_string();
+                match u8::from_str_radix(&hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Query string parameters with lowercase names (KVP parameter names are case insensitive)
+pub fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|kv| !kv.is_empty())
+        .map(|kv| {
+                 let mut parts = kv.splitn(2, '=');
+                 let key = percent_decode(parts.next().unwrap_or(""));
+                 let value = percent_decode(parts.next().unwrap_or(""));
+                 (key.to_lowercase(), value)
+             })
+        .collect()
+}
+
+fn param<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|&&(ref key, _)| key == name)
+        .map(|&(_, ref value)| value.as_str())
+}
+
+fn required_param<'a>(params: &'a [(String, String)],
+                      name: &str)
+                      -> Result<&'a str, WmtsException> {
+    param(params, name).ok_or(WmtsException::missing(name))
+}
+
+/// Parse and validate KVP request
+pub fn parse_request(params: &[(String, String)],
+                     service: &MvtService)
+                     -> Result<WmtsRequest, WmtsException> {
+    if let Some(svc) = param(params, "service") {
+        if !svc.eq_ignore_ascii_case("WMTS") {
+            return Err(WmtsException::invalid("service", svc));
+        }
+    }
+    let request = try!(required_param(params, "request"));
+    if request.eq_ignore_ascii_case("GetCapabilities") {
+        return Ok(WmtsRequest::GetCapabilities);
+    }
+    if !request.eq_ignore_ascii_case("GetTile") {
+        return Err(WmtsException::new("OperationNotSupported",
+                                      "request",
+                                      format!("Operation '{}' not supported", request)));
+    }
+    let layer = try!(required_param(params, "layer"));
+    if !service.tilesets.iter().any(|set| set.name == layer) {
+        return Err(WmtsException::invalid("layer", layer));
+    }
+    let matrix_set = try!(required_param(params, "tilematrixset"));
+    if matrix_set != tile_matrix_set_id(&service.grid) {
+        return Err(WmtsException::invalid("tilematrixset", matrix_set));
+    }
+    if let Some(format) = param(params, "format") {
+        if format != MVT_CONTENT_TYPE {
+            return Err(WmtsException::invalid("format", format));
+        }
+    }
+    let matrix = try!(required_param(params, "tilematrix"));
+    let zoom = match matrix.parse::<u8>() {
+        Ok(zoom) if zoom < service.grid.nlevels() => zoom,
+        _ => return Err(WmtsException::invalid("tilematrix", matrix)),
+    };
+    let (maxcol, maxrow) = service.grid.level_limit(zoom);
+    let row_param = try!(required_param(params, "tilerow"));
+    let row = try!(row_param
+                       .parse::<u32>()
+                       .map_err(|_| WmtsException::invalid("tilerow", row_param)));
+    let col_param = try!(required_param(params, "tilecol"));
+    let col = try!(col_param
+                       .parse::<u32>()
+                       .map_err(|_| WmtsException::invalid("tilecol", col_param)));
+    if row >= maxrow {
+        return Err(WmtsException::new("TileOutOfRange",
+                                      "tilerow",
+                                      format!("Tile row {} out of range", row)));
+    }
+    if col >= maxcol {
+        return Err(WmtsException::new("TileOutOfRange",
+                                      "tilecol",
+                                      format!("Tile column {} out of range", col)));
+    }
+    Ok(WmtsRequest::GetTile {
+           layer: layer.to_string(),
+           zoom: zoom,
+           row: row,
+           col: col,
+       })
+}
+
+/// Identifier of the tile matrix set derived from the grid
+pub fn tile_matrix_set_id(grid: &Grid) -> String {
+    format!("EPSG:{}", grid.srid)
+}
+
+fn meters_per_unit(units: &Unit) -> f64 {
+    match units {
+        &Unit::M => 1.0,
+        &Unit::DD => 6378137.0 * 2.0 * PI / 360.0,
+        &Unit::Ft => 0.3048,
+    }
+}
+
+/// Scale denominator of tile matrix `zoom` with 0.28mm pixels
+pub fn scale_denominator(grid: &Grid, zoom: u8) -> f64 {
+    grid.pixel_width(zoom) * meters_per_unit(&grid.units) / STANDARDIZED_PIXEL_SIZE
+}
+
+/// Upper left corner of tile matrix `zoom` in grid units
+pub fn top_left_corner(grid: &Grid, zoom: u8) -> (f64, f64) {
+    match grid.origin {
+        Origin::TopLeft => (grid.extent.minx, grid.extent.maxy),
+        Origin::BottomLeft => {
+            // Rows are counted from the top of the last (partial) row
+            let (_, maxrow) = grid.level_limit(zoom);
+            let height = grid.pixel_width(zoom) * grid.tile_height() as f64;
+            (grid.extent.minx, grid.extent.miny + maxrow as f64 * height)
+        }
+    }
+}
+
+fn tile_matrix_set(grid: &Grid) -> String {
+    let mut xml = format!(r#"    <TileMatrixSet>
+      <ows:Identifier>{}</ows:Identifier>
+      <ows:SupportedCRS>urn:ogc:def:crs:EPSG::{}</ows:SupportedCRS>
+"#,
+                          tile_matrix_set_id(grid),
+                          grid.srid);
+    for zoom in 0..grid.nlevels() {
+        let (x, y) = top_left_corner(grid, zoom);
+        // Geographic CRS have latitude/longitude axis order
+        let corner = if grid.units == Unit::DD {
+            format!("{} {}", y, x)
+        } else {
+            format!("{} {}", x, y)
+        };
+        let (width, height) = grid.level_limit(zoom);
+        xml.push_str(&format!(r#"      <TileMatrix>
+        <ows:Identifier>{}</ows:Identifier>
+        <ScaleDenominator>{}</ScaleDenominator>
+        <TopLeftCorner>{}</TopLeftCorner>
+        <TileWidth>{}</TileWidth>
+        <TileHeight>{}</TileHeight>
+        <MatrixWidth>{}</MatrixWidth>
+        <MatrixHeight>{}</MatrixHeight>
+      </TileMatrix>
+"#,
+                              zoom,
+                              scale_denominator(grid, zoom),
+                              corner,
+                              grid.tile_size(),
+                              grid.tile_height(),
+                              width,
+                              height));
+    }
+    xml.push_str("    </TileMatrixSet>\n");
+    xml
+}
+
+fn operation(name: &str, baseurl: &str) -> String {
+    format!(r#"    <ows:Operation name="{}">
+      <ows:DCP>
+        <ows:HTTP>
+          <ows:Get xlink:href="{}/wmts?">
+            <ows:Constraint name="GetEncoding">
+              <ows:AllowedValues>
+                <ows:Value>KVP</ows:Value>
+              </ows:AllowedValues>
+            </ows:Constraint>
+          </ows:Get>
+        </ows:HTTP>
+      </ows:DCP>
+    </ows:Operation>
+"#,
+            name,
+            xml_escape(baseurl))
+}
+
+/// GetCapabilities document with tilesets as layers
+pub fn capabilities(service: &MvtService, baseurl: &str) -> String {
+    let matrix_set_id = tile_matrix_set_id(&service.grid);
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>
+<Capabilities xmlns="http://www.opengis.net/wmts/1.0" xmlns:ows="http://www.opengis.net/ows/1.1" xmlns:xlink="http://www.w3.org/1999/xlink" version="1.0.0">
+  <ows:ServiceIdentification>
+    <ows:Title>t-rex</ows:Title>
+    <ows:ServiceType>OGC WMTS</ows:ServiceType>
+    <ows:ServiceTypeVersion>1.0.0</ows:ServiceTypeVersion>
+  </ows:ServiceIdentification>
+  <ows:OperationsMetadata>
+"#);
+    xml.push_str(&operation("GetCapabilities", baseurl));
+    xml.push_str(&operation("GetTile", baseurl));
+    xml.push_str("  </ows:OperationsMetadata>\n  <Contents>\n");
+
+    let mut tilesets: Vec<_> = service.tilesets.iter().collect();
+    tilesets.sort_by_key(|set| set.name.clone());
+    for set in tilesets {
+        let name = xml_escape(&set.name);
+        xml.push_str(&format!("    <Layer>\n      <ows:Title>{}</ows:Title>\n", name));
+        if let Some(ref description) = set.description {
+            xml.push_str(&format!("      <ows:Abstract>{}</ows:Abstract>\n",
+                                  xml_escape(description)));
+        }
+        if let Some(bbox) = set.extent.as_ref().or(service.grid.bounds.as_ref()) {
+            xml.push_str(&format!(r#"      <ows:WGS84BoundingBox>
+        <ows:LowerCorner>{} {}</ows:LowerCorner>
+        <ows:UpperCorner>{} {}</ows:UpperCorner>
+      </ows:WGS84BoundingBox>
+"#,
+                                  bbox.minx,
+                                  bbox.miny,
+                                  bbox.maxx,
+                                  bbox.maxy));
+        }
+        xml.push_str(&format!(r#"      <ows:Identifier>{}</ows:Identifier>
+      <Style isDefault="true">
+        <ows:Identifier>default</ows:Identifier>
+      </Style>
+      <Format>{}</Format>
+      <TileMatrixSetLink>
+        <TileMatrixSet>{}</TileMatrixSet>
+      </TileMatrixSetLink>
+"#,
+                              name,
+                              MVT_CONTENT_TYPE,
+                              matrix_set_id));
+        if !set.tms {
+            // Tile URLs with rows counted from the top
+            xml.push_str(&format!("      <ResourceURL format=\"{}\" resourceType=\"tile\" \
+                                   template=\"{}/{}/{{TileMatrix}}/{{TileCol}}/{{TileRow}}.pbf\"/>\n",
+                                  MVT_CONTENT_TYPE,
+                                  xml_escape(baseurl),
+                                  name));
+        }
+        xml.push_str("    </Layer>\n");
+    }
+    xml.push_str(&tile_matrix_set(&service.grid));
+    xml.push_str(&format!(r#"  </Contents>
+  <ServiceMetadataURL xlink:href="{}/wmts?service=WMTS&amp;request=GetCapabilities"/>
+</Capabilities>
+"#,
+                          xml_escape(baseurl)));
+    xml
+}
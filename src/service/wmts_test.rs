@@ -0,0 +1,82 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use service::wmts::*;
+use service::mvt_test::geojson_service;
+use core::grid::Grid;
+
+
+#[test]
+fn test_parse_query() {
+    assert_eq!(parse_query("SERVICE=WMTS&Request=GetTile&Format=application%2Fvnd.mapbox-vector-tile&x="),
+               vec![("service".to_string(), "WMTS".to_string()),
+                    ("request".to_string(), "GetTile".to_string()),
+                    ("format".to_string(), "application/vnd.mapbox-vector-tile".to_string()),
+                    ("x".to_string(), "".to_string())]);
+    assert_eq!(parse_query(""), vec![]);
+    assert_eq!(parse_query("a=b+c%"), vec![("a".to_string(), "b c%".to_string())]);
+}
+
+#[test]
+fn test_tile_matrix() {
+    let grid = Grid::web_mercator();
+    assert_eq!(tile_matrix_set_id(&grid), "EPSG:3857");
+    assert!((scale_denominator(&grid, 0) - 559082264.0287178).abs() < 1e-3);
+    for zoom in vec![0, 5] {
+        let (x, y) = top_left_corner(&grid, zoom);
+        assert_eq!(x, -20037508.3427892480);
+        assert!((y - 20037508.3427892480).abs() < 1e-6);
+    }
+
+    let grid = Grid::lv95();
+    assert_eq!(top_left_corner(&grid, 0), (2420000.0, 1350000.0));
+}
+
+#[test]
+fn test_parse_request() {
+    let service = geojson_service();
+    let request = |query: &str| parse_request(&parse_query(query), &service);
+
+    assert_eq!(request("service=WMTS&request=GetCapabilities"),
+               Ok(WmtsRequest::GetCapabilities));
+    assert_eq!(request("SERVICE=WMTS&REQUEST=GetTile&VERSION=1.0.0&LAYER=places&STYLE=default&\
+                        TILEMATRIXSET=EPSG:3857&TILEMATRIX=8&TILEROW=90&TILECOL=133&\
+                        FORMAT=application/vnd.mapbox-vector-tile"),
+               Ok(WmtsRequest::GetTile {
+                      layer: "places".to_string(),
+                      zoom: 8,
+                      row: 90,
+                      col: 133,
+                  }));
+
+    let err = request("service=WMTS").unwrap_err();
+    assert_eq!((err.code, err.http_status()), ("MissingParameterValue", 400));
+    let err = request("service=WMTS&request=GetFeatureInfo").unwrap_err();
+    assert_eq!((err.code, err.http_status()), ("OperationNotSupported", 501));
+    let err = request("request=GetTile&layer=roads&tilematrixset=EPSG:3857&tilematrix=0&tilerow=0&tilecol=0")
+        .unwrap_err();
+    assert_eq!((err.code, err.locator.as_str()), ("InvalidParameterValue", "layer"));
+    let err = request("request=GetTile&layer=places&tilematrixset=EPSG:3857&tilematrix=23&tilerow=0&tilecol=0")
+        .unwrap_err();
+    assert_eq!((err.code, err.locator.as_str()), ("InvalidParameterValue", "tilematrix"));
+    let err = request("request=GetTile&layer=places&tilematrixset=EPSG:3857&tilematrix=1&tilerow=2&tilecol=0")
+        .unwrap_err();
+    assert_eq!((err.code, err.locator.as_str()), ("TileOutOfRange", "tilerow"));
+    assert!(err.to_xml().contains(r#"<ows:Exception exceptionCode="TileOutOfRange" locator="tilerow">"#));
+}
+
+#[test]
+fn test_capabilities() {
+    let service = geojson_service();
+    let xml = capabilities(&service, "http://127.0.0.1:6767");
+    assert!(xml.contains("<ows:Identifier>places</ows:Identifier>"));
+    assert!(xml.contains("<Format>application/vnd.mapbox-vector-tile</Format>"));
+    assert!(xml.contains(r#"template="http://127.0.0.1:6767/places/{TileMatrix}/{TileCol}/{TileRow}.pbf""#));
+    assert!(xml.contains(r#"<ows:Get xlink:href="http://127.0.0.1:6767/wmts?">"#));
+    assert!(xml.contains("<ows:SupportedCRS>urn:ogc:def:crs:EPSG::3857</ows:SupportedCRS>"));
+    assert_eq!(xml.matches("<TileMatrix>").count(), 23);
+    assert!(xml.contains("<TopLeftCorner>-20037508.342789248 20037508.342789248</TopLeftCorner>"));
+    assert!(xml.contains("<MatrixWidth>256</MatrixWidth>"));
+}
@@ -0,0 +1,272 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use nickel::{Request, Response, Middleware, MiddlewareResult};
+use hyper::header::ContentLength;
+use toml;
+use time;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LogFormat {
+    /// Apache Combined Log Format with request duration in microseconds
+    Combined,
+    /// One JSON object per line
+    Json,
+}
+
+/// Request information written to the access log
+pub struct LogEntry {
+    pub remote_addr: String,
+    pub time: time::Tm,
+    pub method: String,
+    pub uri: String,
+    pub version: String,
+    pub status: u16,
+    /// Body size from Content-Length header
+    pub bytes: Option<u64>,
+    pub referer: Option<String>,
+    pub user_agent: Option<String>,
+    pub duration: Duration,
+}
+
+/// Escape quoted log fields like Apache: `\"`, `\\`, control characters
+/// and non-ASCII bytes as `\xhh`.
+fn escape(val: &str) -> String {
+    let mut escaped = String::with_capacity(val.len());
+    for byte in val.bytes() {
+        match byte {
+            b'"' => escaped.push_str("\\\""),
+            b'\\' => escaped.push_str("\\\\"),
+            b'\n' => escaped.push_str("\\n"),
+            b'\r' => escaped.push_str("\\r"),
+            b'\t' => escaped.push_str("\\t"),
+            0x20..=0x7e => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    escaped
+}
+
+impl LogEntry {
+    fn duration_us(&self) -> u64 {
+        self.duration.as_secs() * 1_000_000 + (self.duration.subsec_nanos() / 1_000) as u64
+    }
+    /// UTC offset like `+0200` (`%z` of the time crate formats UTC as `-0000`)
+    fn utc_offset(&self) -> String {
+        let sign = if self.time.tm_utcoff < 0 { '-' } else { '+' };
+        let minutes = self.time.tm_utcoff.abs() / 60;
+        format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+    }
+    pub fn format(&self, format: LogFormat) -> String {
+        match format {
+            LogFormat::Combined => {
+                format!("{} - - [{} {}] \"{} {} {}\" {} {} \"{}\" \"{}\" {}",
+                        self.remote_addr,
+                        time::strftime("%d/%b/%Y:%H:%M:%S", &self.time).unwrap(),
+                        self.utc_offset(),
+                        escape(&self.method),
+                        escape(&self.uri),
+                        self.version,
+                        self.status,
+                        self.bytes.map_or("-".to_string(), |bytes| bytes.to_string()),
+                        self.referer.as_ref().map_or("-".to_string(), |val| escape(val)),
+                        self.user_agent.as_ref().map_or("-".to_string(), |val| escape(val)),
+                        self.duration_us())
+            }
+            LogFormat::Json => {
+                json!({
+                    "time": time::strftime("%Y-%m-%dT%H:%M:%S", &self.time).unwrap() +
+                            &self.utc_offset(),
+                    "remote_addr": self.remote_addr,
+                    "method": self.method,
+                    "uri": self.uri,
+                    "status": self.status,
+                    "bytes": self.bytes,
+                    "referer": self.referer,
+                    "user_agent": self.user_agent,
+                    "duration_us": self.duration_us()
+                })
+                        .to_string()
+            }
+        }
+    }
+}
+
+fn open_log(path: &str) -> Result<File, String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Error opening access log '{}': {}", path, e))
+}
+
+/// Access log file middleware
+#[derive(Clone)]
+pub struct AccessLog {
+    path: String,
+    format: LogFormat,
+    file: Arc<Mutex<File>>,
+}
+
+impl AccessLog {
+    pub fn new(path: &str, format: LogFormat) -> Result<AccessLog, String> {
+        let file = try!(open_log(path));
+        Ok(AccessLog {
+               path: path.to_string(),
+               format: format,
+               file: Arc::new(Mutex::new(file)),
+           })
+    }
+    pub fn from_config(http_config: &toml::Value) -> Result<Option<AccessLog>, String> {
+        let path = match http_config.get("access_log").and_then(|val| val.as_str()) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        let format = match http_config
+                  .get("access_log_format")
+                  .and_then(|val| val.as_str()) {
+            None | Some("combined") => LogFormat::Combined,
+            Some("json") => LogFormat::Json,
+            Some(format) => {
+                return Err(format!("Invalid access_log_format '{}' (expected 'combined' or 'json')",
+                                   format))
+            }
+        };
+        AccessLog::new(path, format).map(|log| Some(log))
+    }
+    /// Reopen log file after rotation
+    pub fn reopen(&self) {
+        match open_log(&self.path) {
+            Ok(file) => *self.file.lock().unwrap() = file,
+            Err(err) => error!("{}", err),
+        }
+    }
+    pub fn write(&self, entry: &LogEntry) {
+        let mut file = self.file.lock().unwrap();
+        if let Err(err) = writeln!(file, "{}", entry.format(self.format)) {
+            error!("Error writing access log '{}': {}", self.path, err);
+        }
+    }
+}
+
+fn header_value<D>(req: &Request<D>, name: &str) -> Option<String> {
+    req.origin
+        .headers
+        .get_raw(name)
+        .and_then(|vals| vals.first())
+        .map(|val| String::from_utf8_lossy(val).into_owned())
+}
+
+impl<D> Middleware<D> for AccessLog {
+    fn invoke<'mw, 'conn>(&'mw self,
+                          req: &mut Request<'mw, 'conn, D>,
+                          mut res: Response<'mw, D>)
+                          -> MiddlewareResult<'mw, D> {
+        let start = Instant::now();
+        let mut entry = LogEntry {
+            remote_addr: req.origin.remote_addr.ip().to_string(),
+            time: time::now(),
+            method: req.origin.method.to_string(),
+            uri: req.origin.uri.to_string(),
+            version: req.origin.version.to_string(),
+            status: 0,
+            bytes: None,
+            referer: header_value(req, "Referer"),
+            user_agent: header_value(req, "User-Agent"),
+            duration: Duration::from_secs(0),
+        };
+        let log = self.clone();
+        // Called before sending headers, after the handler has built the response
+        res.on_send(move |res| {
+                        entry.status = res.status().to_u16();
+                        entry.bytes = res.headers().get::<ContentLength>().map(|len| len.0);
+                        entry.duration = start.elapsed();
+                        log.write(&entry);
+                    });
+        res.next_middleware()
+    }
+}
+
+
+#[cfg(test)]
+fn test_entry() -> LogEntry {
+    LogEntry {
+        remote_addr: "127.0.0.1".to_string(),
+        time: time::at_utc(time::Timespec::new(1500000000, 0)),
+        method: "GET".to_string(),
+        uri: "/osm/6/33/22.pbf".to_string(),
+        version: "HTTP/1.1".to_string(),
+        status: 200,
+        bytes: Some(2048),
+        referer: None,
+        user_agent: Some("curl/7.54.0".to_string()),
+        duration: Duration::from_millis(12),
+    }
+}
+
+#[test]
+fn test_log_format() {
+    let entry = test_entry();
+    assert_eq!(entry.format(LogFormat::Combined),
+               r#"127.0.0.1 - - [14/Jul/2017:02:40:00 +0000] "GET /osm/6/33/22.pbf HTTP/1.1" 200 2048 "-" "curl/7.54.0" 12000"#);
+    assert_eq!(entry.format(LogFormat::Json),
+               r#"{"bytes":2048,"duration_us":12000,"method":"GET","referer":null,"remote_addr":"127.0.0.1","status":200,"time":"2017-07-14T02:40:00+0000","uri":"/osm/6/33/22.pbf","user_agent":"curl/7.54.0"}"#);
+}
+
+#[test]
+fn test_log_format_escaping() {
+    let mut entry = test_entry();
+    entry.uri = "/osm/6/33/22.pbf?q=\"a\\b\"".to_string();
+    entry.referer = Some("http://example.com/\u{e4}".to_string());
+    entry.user_agent = Some("evil\" 200 0 \"-\"\n\x01".to_string());
+    assert_eq!(entry.format(LogFormat::Combined),
+               r#"127.0.0.1 - - [14/Jul/2017:02:40:00 +0000] "GET /osm/6/33/22.pbf?q=\"a\\b\" HTTP/1.1" 200 2048 "http://example.com/\xc3\xa4" "evil\" 200 0 \"-\"\n\x01" 12000"#);
+}
+
+#[test]
+fn test_access_log_file() {
+    use core::parse_config;
+    use std::env;
+    use std::fs;
+    use std::io::Read;
+
+    let config = parse_config("access_log = \"access.log\"\naccess_log_format = \"xml\""
+                                  .to_string(),
+                              "")
+            .unwrap();
+    assert_eq!(AccessLog::from_config(&config).err(),
+               Some("Invalid access_log_format 'xml' (expected 'combined' or 'json')".to_string()));
+    let config = parse_config("".to_string(), "").unwrap();
+    assert!(AccessLog::from_config(&config).unwrap().is_none());
+
+    let path = env::temp_dir().join("t_rex_access.log");
+    let rotated = env::temp_dir().join("t_rex_access.log.1");
+    let _ = fs::remove_file(&path);
+    let config = parse_config(format!("access_log = {:?}\naccess_log_format = \"json\"",
+                                      path.to_str().unwrap()),
+                              "")
+            .unwrap();
+    let log = AccessLog::from_config(&config).unwrap().unwrap();
+    assert_eq!(log.format, LogFormat::Json);
+    log.write(&test_entry());
+    // logrotate moves the file and sends SIGHUP
+    fs::rename(&path, &rotated).unwrap();
+    log.reopen();
+    log.write(&test_entry());
+
+    for file in &[&path, &rotated] {
+        let mut content = String::new();
+        File::open(file).unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content.lines().count(), 1);
+        assert!(content.starts_with(r#"{"bytes":2048,"#));
+    }
+    let _ = fs::remove_file(&path);
+    let _ = fs::remove_file(&rotated);
+}
@@ -0,0 +1,286 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use nickel::{Request, Response, Middleware, MiddlewareResult};
+use nickel::status::StatusCode;
+use hyper::header::{Authorization, Basic, Bearer, Headers};
+use hyper::method::Method;
+use webserver::rate_limit::is_tile_path;
+use toml;
+
+
+header! { (WwwAuthenticate, "WWW-Authenticate") => [String] }
+
+/// Routes which can be protected separately
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum AuthScope {
+    /// Tiles, TileJSON, styles, metadata and WMTS
+    Tiles,
+    /// Built-in viewer and static files
+    Viewer,
+    /// Seed endpoint and metrics
+    Admin,
+}
+
+/// HTTP Basic auth and bearer token protection
+pub struct Auth {
+    /// Basic auth username and password
+    credentials: Option<(String, String)>,
+    /// Bearer token
+    token: Option<String>,
+    /// Protected routes
+    scopes: Vec<AuthScope>,
+    realm: String,
+}
+
+impl AuthScope {
+    fn from_name(name: &str) -> Result<AuthScope, String> {
+        match name {
+            "tiles" => Ok(AuthScope::Tiles),
+            "viewer" => Ok(AuthScope::Viewer),
+            "admin" => Ok(AuthScope::Admin),
+            _ => Err(format!("Unknown auth scope '{}'", name)),
+        }
+    }
+}
+
+/// Scope of a request path. Health probes are never protected.
+pub fn auth_scope(path: &str) -> Option<AuthScope> {
+    let path = path.split('?').next().unwrap_or("");
+    let segments: Vec<&str> = path.trim_left_matches('/').split('/').collect();
+    let last = segments[segments.len() - 1];
+    if segments.len() == 1 {
+        match last {
+            "healthz" | "readyz" => return None,
+            "metrics" => return Some(AuthScope::Admin),
+            "fontstacks.json" => return Some(AuthScope::Viewer),
+            // Service metadata, TileJSON and styles
+            "wmts" => return Some(AuthScope::Tiles),
+            _ if last.ends_with(".json") => return Some(AuthScope::Tiles),
+            _ => {}
+        }
+    }
+    if segments.len() == 2 {
+        match last {
+            "seed" => return Some(AuthScope::Admin),
+            "metadata.json" => return Some(AuthScope::Tiles),
+            _ => {}
+        }
+    }
+    if is_tile_path(path) {
+        Some(AuthScope::Tiles)
+    } else {
+        Some(AuthScope::Viewer)
+    }
+}
+
+/// Compare secrets in constant time
+pub fn secure_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() &&
+    a.bytes()
+        .zip(b.bytes())
+        .fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+impl Auth {
+    pub fn from_config(http_config: &toml::Value) -> Result<Option<Auth>, String> {
+        let auth_config = match http_config.get("auth") {
+            Some(config) => config,
+            None => return Ok(None),
+        };
+        let setting = |name: &str| -> Result<Option<String>, String> {
+            match auth_config.get(name) {
+                Some(val) => {
+                    let val = try!(val.as_str()
+                                       .ok_or(format!("auth {} must be a string", name)));
+                    if val.is_empty() {
+                        return Err(format!("auth {} must not be empty", name));
+                    }
+                    Ok(Some(val.to_string()))
+                }
+                None => Ok(None),
+            }
+        };
+        let credentials = match (try!(setting("username")), try!(setting("password"))) {
+            (Some(username), Some(password)) => Some((username, password)),
+            (None, None) => None,
+            _ => return Err("auth requires both username and password".to_string()),
+        };
+        let token = try!(setting("token"));
+        if credentials.is_none() && token.is_none() {
+            return Err("auth requires username and password or token".to_string());
+        }
+        let scopes = match auth_config.get("scope") {
+            Some(val) => {
+                let names = try!(val.as_array()
+                                     .ok_or("auth scope must be a list".to_string()));
+                let mut scopes = Vec::new();
+                for name in names {
+                    let name = try!(name.as_str()
+                                        .ok_or("auth scope must be a list of strings".to_string()));
+                    scopes.push(try!(AuthScope::from_name(name)));
+                }
+                scopes
+            }
+            None => vec![AuthScope::Tiles, AuthScope::Viewer, AuthScope::Admin],
+        };
+        let realm = try!(setting("realm")).unwrap_or("t-rex".to_string());
+        Ok(Some(Auth {
+                    credentials: credentials,
+                    token: token,
+                    scopes: scopes,
+                    realm: realm,
+                }))
+    }
+    /// Check Basic auth credentials or bearer token of request headers
+    pub fn authorized(&self, headers: &Headers) -> bool {
+        if let Some((ref username, ref password)) = self.credentials {
+            if let Some(&Authorization(ref basic)) = headers.get::<Authorization<Basic>>() {
+                let given = basic.password.as_ref().map_or("", |pw| pw.as_str());
+                if secure_eq(&basic.username, username) & secure_eq(given, password) {
+                    return true;
+                }
+            }
+        }
+        if let Some(ref token) = self.token {
+            if let Some(&Authorization(ref bearer)) = headers.get::<Authorization<Bearer>>() {
+                if secure_eq(&bearer.token, token) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+    pub fn protects_scope(&self, scope: AuthScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+    pub fn protects(&self, path: &str) -> bool {
+        auth_scope(path).map_or(false, |scope| self.protects_scope(scope))
+    }
+    fn challenge(&self) -> String {
+        let scheme = if self.credentials.is_some() {
+            "Basic"
+        } else {
+            "Bearer"
+        };
+        format!("{} realm=\"{}\"", scheme, self.realm)
+    }
+}
+
+impl<D> Middleware<D> for Auth {
+    fn invoke<'mw, 'conn>(&'mw self,
+                          req: &mut Request<'mw, 'conn, D>,
+                          mut res: Response<'mw, D>)
+                          -> MiddlewareResult<'mw, D> {
+        // CORS preflight requests are sent without credentials
+        let protected = req.origin.method != Method::Options &&
+                        req.path_without_query().map_or(true, |path| self.protects(path));
+        if protected && !self.authorized(&req.origin.headers) {
+            debug!("Unauthorized request {}", req.origin.uri);
+            res.set(StatusCode::Unauthorized);
+            res.headers_mut().set(WwwAuthenticate(self.challenge()));
+            return res.send("Unauthorized");
+        }
+        res.next_middleware()
+    }
+}
+
+
+#[test]
+fn test_auth_scope() {
+    assert_eq!(auth_scope("/healthz"), None);
+    assert_eq!(auth_scope("/readyz"), None);
+    assert_eq!(auth_scope("/metrics"), Some(AuthScope::Admin));
+    assert_eq!(auth_scope("/osm/seed?bbox=0,0,1,1"), Some(AuthScope::Admin));
+    assert_eq!(auth_scope("/osm/6/33/22.pbf"), Some(AuthScope::Tiles));
+    assert_eq!(auth_scope("/osm.json"), Some(AuthScope::Tiles));
+    assert_eq!(auth_scope("/osm.style.json"), Some(AuthScope::Tiles));
+    assert_eq!(auth_scope("/osm/metadata.json"), Some(AuthScope::Tiles));
+    assert_eq!(auth_scope("/index.json"), Some(AuthScope::Tiles));
+    assert_eq!(auth_scope("/wmts?request=GetCapabilities"),
+               Some(AuthScope::Tiles));
+    assert_eq!(auth_scope("/"), Some(AuthScope::Viewer));
+    assert_eq!(auth_scope("/index.html"), Some(AuthScope::Viewer));
+    assert_eq!(auth_scope("/fontstacks.json"), Some(AuthScope::Viewer));
+    assert_eq!(auth_scope("/maputnik/vendor.js"), Some(AuthScope::Viewer));
+}
+
+#[test]
+fn test_auth_config() {
+    use core::parse_config;
+
+    let config = parse_config("".to_string(), "").unwrap();
+    assert!(Auth::from_config(&config).unwrap().is_none());
+
+    let config = parse_config(r#"
+        [auth]
+        username = "tiles"
+        password = "secret"
+        "#
+                                      .to_string(),
+                              "")
+            .unwrap();
+    let auth = Auth::from_config(&config).unwrap().unwrap();
+    assert_eq!(auth.scopes.len(), 3);
+    assert_eq!(auth.challenge(), "Basic realm=\"t-rex\"");
+
+    let config = parse_config(r#"
+        [auth]
+        token = "secret"
+        scope = ["admin", "viewer"]
+        "#
+                                      .to_string(),
+                              "")
+            .unwrap();
+    let auth = Auth::from_config(&config).unwrap().unwrap();
+    assert_eq!(auth.challenge(), "Bearer realm=\"t-rex\"");
+    assert!(auth.protects("/osm/seed"));
+    assert!(auth.protects("/index.html"));
+    assert!(!auth.protects("/osm/6/33/22.pbf"));
+    assert!(!auth.protects("/healthz"));
+
+    let config = parse_config("[auth]\nusername = \"tiles\"".to_string(), "").unwrap();
+    assert_eq!(Auth::from_config(&config).err(),
+               Some("auth requires both username and password".to_string()));
+    let config = parse_config("[auth]\nscope = [\"tiles\"]".to_string(), "").unwrap();
+    assert_eq!(Auth::from_config(&config).err(),
+               Some("auth requires username and password or token".to_string()));
+    let config = parse_config("[auth]\ntoken = \"\"".to_string(), "").unwrap();
+    assert_eq!(Auth::from_config(&config).err(),
+               Some("auth token must not be empty".to_string()));
+    let config = parse_config("[auth]\ntoken = \"secret\"\nscope = [\"wms\"]".to_string(), "")
+        .unwrap();
+    assert_eq!(Auth::from_config(&config).err(),
+               Some("Unknown auth scope 'wms'".to_string()));
+}
+
+#[test]
+fn test_authorized() {
+    let auth = Auth {
+        credentials: Some(("tiles".to_string(), "secret".to_string())),
+        token: Some("token123".to_string()),
+        scopes: vec![AuthScope::Tiles],
+        realm: "t-rex".to_string(),
+    };
+    // Request headers are parsed from raw values
+    let request_headers = |authorization: &str| {
+        let mut headers = Headers::new();
+        headers.set_raw("Authorization", vec![authorization.as_bytes().to_vec()]);
+        headers
+    };
+    assert!(!auth.authorized(&Headers::new()));
+    // tiles:secret
+    assert!(auth.authorized(&request_headers("Basic dGlsZXM6c2VjcmV0")));
+    // tiles:wrong
+    assert!(!auth.authorized(&request_headers("Basic dGlsZXM6d3Jvbmc=")));
+    // tiles
+    assert!(!auth.authorized(&request_headers("Basic dGlsZXM=")));
+    assert!(auth.authorized(&request_headers("Bearer token123")));
+    assert!(!auth.authorized(&request_headers("Bearer token")));
+
+    assert!(secure_eq("secret", "secret"));
+    assert!(!secure_eq("secret", "secreT"));
+    assert!(!secure_eq("secret", "secret1"));
+}
@@ -0,0 +1,187 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use nickel::{Request, Response, Middleware, MiddlewareResult};
+use nickel::status::StatusCode;
+use hyper::header::{AccessControlAllowOrigin, AccessControlAllowMethods,
+                    AccessControlAllowHeaders, AccessControlRequestHeaders, Headers, Vary};
+use hyper::method::Method;
+use unicase::UniCase;
+use toml;
+
+
+/// CORS middleware
+// see also https://github.com/nickel-org/nickel.rs/blob/master/examples/enable_cors.rs
+pub struct Cors {
+    /// Allowed origins. All origins are allowed if empty.
+    pub allowed_origins: Vec<String>,
+}
+
+impl Cors {
+    pub fn from_config(http_config: &toml::Value) -> Cors {
+        let allowed_origins = http_config
+            .get("cors_allowed_origins")
+            .and_then(|val| val.as_array())
+            .map(|origins| {
+                     origins
+                         .iter()
+                         .filter_map(|origin| origin.as_str().map(|s| s.to_string()))
+                         .collect()
+                 })
+            .unwrap_or(Vec::new());
+        Cors { allowed_origins: allowed_origins }
+    }
+    /// Access-Control-Allow-Origin header for request headers
+    pub fn allow_origin(&self, headers: &Headers) -> Option<AccessControlAllowOrigin> {
+        if self.allowed_origins.is_empty() {
+            return Some(AccessControlAllowOrigin::Any);
+        }
+        headers
+            .get_raw("Origin")
+            .and_then(|vals| vals.first())
+            .and_then(|val| String::from_utf8(val.clone()).ok())
+            .and_then(|origin| if self.allowed_origins.contains(&origin) {
+                          Some(AccessControlAllowOrigin::Value(origin))
+                      } else {
+                          None
+                      })
+    }
+}
+
+impl<D> Middleware<D> for Cors {
+    fn invoke<'mw, 'conn>(&'mw self,
+                          req: &mut Request<'mw, 'conn, D>,
+                          mut res: Response<'mw, D>)
+                          -> MiddlewareResult<'mw, D> {
+        if !self.allowed_origins.is_empty() {
+            // Responses depend on the Origin header, also for origins which are not allowed
+            add_vary(res.headers_mut(), "Origin");
+        }
+        if let Some(allow_origin) = self.allow_origin(&req.origin.headers) {
+            res.set(allow_origin);
+            res.set(AccessControlAllowMethods(vec![Method::Get, Method::Head, Method::Options]));
+        }
+        res.next_middleware()
+    }
+}
+
+/// Handler for CORS preflight requests (OPTIONS) of a route.
+/// OPTIONS requests of paths without route are not found.
+pub struct Preflight;
+
+impl<D> Middleware<D> for Preflight {
+    fn invoke<'mw, 'conn>(&'mw self,
+                          req: &mut Request<'mw, 'conn, D>,
+                          mut res: Response<'mw, D>)
+                          -> MiddlewareResult<'mw, D> {
+        let requested_headers = req.origin
+            .headers
+            .get::<AccessControlRequestHeaders>()
+            .map(|h| h.0.clone())
+            .unwrap_or(Vec::new());
+        res.set(AccessControlAllowHeaders(requested_headers));
+        res.set(StatusCode::NoContent);
+        res.send("")
+    }
+}
+
+/// Add `name` to the Vary header, keeping entries of previous middlewares like CORS
+pub fn add_vary(headers: &mut Headers, name: &str) {
+    let mut items = match headers.get::<Vary>() {
+        Some(&Vary::Items(ref items)) => items.clone(),
+        Some(&Vary::Any) => return,
+        None => Vec::new(),
+    };
+    if !items.iter().any(|item| item.eq_ignore_ascii_case(name)) {
+        items.push(UniCase(name.to_string()));
+    }
+    headers.set(Vary::Items(items));
+}
+
+
+#[test]
+fn test_allow_origin() {
+    let mut headers = Headers::new();
+    headers.set_raw("Origin", vec![b"https://example.com".to_vec()]);
+
+    let cors = Cors { allowed_origins: Vec::new() };
+    assert_eq!(cors.allow_origin(&headers), Some(AccessControlAllowOrigin::Any));
+
+    let cors = Cors { allowed_origins: vec!["https://example.com".to_string()] };
+    assert_eq!(cors.allow_origin(&headers),
+               Some(AccessControlAllowOrigin::Value("https://example.com".to_string())));
+
+    headers.set_raw("Origin", vec![b"https://evil.com".to_vec()]);
+    assert_eq!(cors.allow_origin(&headers), None);
+
+    assert_eq!(cors.allow_origin(&Headers::new()), None);
+}
+
+#[test]
+fn test_add_vary() {
+    let mut headers = Headers::new();
+    add_vary(&mut headers, "Accept-Encoding");
+    assert_eq!(headers.get::<Vary>(),
+               Some(&Vary::Items(vec![UniCase("Accept-Encoding".to_string())])));
+    headers.set(Vary::Items(vec![UniCase("Origin".to_string())]));
+    add_vary(&mut headers, "Accept-Encoding");
+    add_vary(&mut headers, "accept-encoding");
+    assert_eq!(headers.get::<Vary>(),
+               Some(&Vary::Items(vec![UniCase("Origin".to_string()),
+                                      UniCase("Accept-Encoding".to_string())])));
+}
+
+#[test]
+fn test_cors_middleware() {
+    use nickel::{Nickel, HttpRouter};
+    use hyper::Client;
+    use hyper::header::Origin;
+
+    let mut server = Nickel::new();
+    server.utilize(Cors { allowed_origins: vec!["https://example.com".to_string()] });
+    server.get("/index.json", middleware!("{}"));
+    server.options("/index.json", Preflight);
+    // Close connections after each response like the default server configuration
+    server.keep_alive_timeout(None);
+    let listening = server.listen("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listening.socket());
+    let url = |path: &str| format!("{}{}", base, path);
+    let client = Client::new();
+    let vary = Vary::Items(vec![UniCase("Origin".to_string())]);
+
+    let res = client
+        .get(&url("/index.json"))
+        .header(Origin::new("https", "example.com", None))
+        .send()
+        .unwrap();
+    assert_eq!(res.status, StatusCode::Ok);
+    assert_eq!(res.headers.get::<Vary>(), Some(&vary));
+    assert_eq!(res.headers.get::<AccessControlAllowOrigin>(),
+               Some(&AccessControlAllowOrigin::Value("https://example.com".to_string())));
+
+    // Vary is also set for origins which are not allowed
+    let res = client
+        .get(&url("/index.json"))
+        .header(Origin::new("https", "evil.com", None))
+        .send()
+        .unwrap();
+    assert_eq!(res.headers.get::<Vary>(), Some(&vary));
+    assert_eq!(res.headers.get::<AccessControlAllowOrigin>(), None);
+
+    let res = client
+        .request(Method::Options, &url("/index.json"))
+        .header(Origin::new("https", "example.com", None))
+        .send()
+        .unwrap();
+    assert_eq!(res.status, StatusCode::NoContent);
+    let res = client
+        .request(Method::Options, &url("/missing.json"))
+        .header(Origin::new("https", "example.com", None))
+        .send()
+        .unwrap();
+    assert_eq!(res.status, StatusCode::NotFound);
+
+    listening.detach();
+}
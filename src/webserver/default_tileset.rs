@@ -0,0 +1,70 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use nickel::{Request, Response, Middleware, MiddlewareResult};
+use hyper::uri::RequestUri;
+
+
+/// Middleware serving a default tileset at paths without tileset prefix
+pub struct DefaultTileset(pub String);
+
+impl DefaultTileset {
+    /// Path with tileset prefix for a prefix-less path, including query string
+    pub fn rewrite_path(&self, path: &str) -> Option<String> {
+        let (path, query) = match path.find('?') {
+            Some(pos) => path.split_at(pos),
+            None => (path, ""),
+        };
+        let rewritten = match path {
+            "/tile.json" => format!("/{}.json", self.0),
+            "/style.json" => format!("/{}.style.json", self.0),
+            _ => {
+                // Tile paths like /{z}/{x}/{y}.pbf
+                let segments: Vec<&str> = path.trim_left_matches('/').split('/').collect();
+                if segments.len() != 3 || segments[0].parse::<u8>().is_err() ||
+                   segments[1].parse::<u32>().is_err() {
+                    return None;
+                }
+                format!("/{}{}", self.0, path)
+            }
+        };
+        Some(format!("{}{}", rewritten, query))
+    }
+}
+
+impl<D> Middleware<D> for DefaultTileset {
+    fn invoke<'mw, 'conn>(&'mw self,
+                          req: &mut Request<'mw, 'conn, D>,
+                          res: Response<'mw, D>)
+                          -> MiddlewareResult<'mw, D> {
+        let rewritten = match req.origin.uri {
+            RequestUri::AbsolutePath(ref path) => self.rewrite_path(path),
+            _ => None,
+        };
+        if let Some(path) = rewritten {
+            debug!("Default tileset path {}", path);
+            req.origin.uri = RequestUri::AbsolutePath(path);
+        }
+        res.next_middleware()
+    }
+}
+
+
+#[test]
+fn test_rewrite_path() {
+    let default = DefaultTileset("osm".to_string());
+    assert_eq!(default.rewrite_path("/tile.json"), Some("/osm.json".to_string()));
+    assert_eq!(default.rewrite_path("/style.json?key=1"),
+               Some("/osm.style.json?key=1".to_string()));
+    assert_eq!(default.rewrite_path("/8/133/90.pbf"), Some("/osm/8/133/90.pbf".to_string()));
+    assert_eq!(default.rewrite_path("/8/133/90.grid.json?callback=cb"),
+               Some("/osm/8/133/90.grid.json?callback=cb".to_string()));
+    // Prefixed and other paths are unchanged
+    assert_eq!(default.rewrite_path("/osm/8/133/90.pbf"), None);
+    assert_eq!(default.rewrite_path("/osm.json"), None);
+    assert_eq!(default.rewrite_path("/tiles.json"), None);
+    assert_eq!(default.rewrite_path("/osm/metadata.json"), None);
+    assert_eq!(default.rewrite_path("/index.json"), None);
+}
@@ -4,3 +4,13 @@
 //
 
 pub mod server;
+pub mod cors;
+pub mod shutdown;
+pub mod reload;
+pub mod access_log;
+pub mod rate_limit;
+pub mod auth;
+pub mod default_tileset;
+pub mod seed;
+#[cfg(unix)]
+pub mod unix_socket;
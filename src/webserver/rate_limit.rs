@@ -0,0 +1,325 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use nickel::{Request, Response, Middleware, MiddlewareResult};
+use nickel::status::StatusCode;
+use service::wmts;
+use webserver::server::trust_forwarded_headers;
+use toml;
+use hyper::header::Headers;
+use std::collections::{BTreeMap, HashMap};
+use std::str;
+use std::sync::Mutex;
+use std::time::Instant;
+
+
+header! { (RetryAfter, "Retry-After") => [u64] }
+
+/// Number of clients kept before buckets of least recently seen clients are removed
+const MAX_CLIENTS: usize = 10000;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    /// Sequence number of last request
+    seen: u64,
+}
+
+/// Buckets of clients with LRU eviction
+struct Buckets {
+    buckets: HashMap<String, Bucket>,
+    /// Clients by sequence number of their last request
+    lru: BTreeMap<u64, String>,
+    seq: u64,
+}
+
+/// Token bucket rate limiter for tile requests per client IP
+pub struct RateLimiter {
+    /// Requests per second
+    rate: f64,
+    /// Maximal number of requests without delay
+    burst: f64,
+    /// Identify clients by X-Forwarded-For header
+    trust_forwarded: bool,
+    buckets: Mutex<Buckets>,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant, rate: f64, burst: f64) {
+        if now > self.updated {
+            let elapsed = now.duration_since(self.updated);
+            let secs = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 / 1_000_000_000.0;
+            self.tokens = (self.tokens + secs * rate).min(burst);
+            self.updated = now;
+        }
+    }
+}
+
+impl Buckets {
+    fn new() -> Buckets {
+        Buckets {
+            buckets: HashMap::new(),
+            lru: BTreeMap::new(),
+            seq: 0,
+        }
+    }
+    /// Bucket of `client`, created with `burst` tokens. Removes the least recently seen
+    /// client if `max_clients` is reached.
+    fn get(&mut self, client: &str, now: Instant, burst: f64, max_clients: usize) -> &mut Bucket {
+        self.seq += 1;
+        let seq = self.seq;
+        let previous = self.buckets.get(client).map(|bucket| bucket.seen);
+        match previous {
+            Some(seen) => {
+                self.lru.remove(&seen);
+            }
+            None => {
+                if self.buckets.len() >= max_clients {
+                    let oldest = self.lru.keys().next().cloned();
+                    if let Some(oldest) = oldest {
+                        if let Some(evicted) = self.lru.remove(&oldest) {
+                            self.buckets.remove(&evicted);
+                        }
+                    }
+                }
+            }
+        }
+        self.lru.insert(seq, client.to_string());
+        let bucket = self.buckets
+            .entry(client.to_string())
+            .or_insert(Bucket {
+                           tokens: burst,
+                           updated: now,
+                           seen: seq,
+                       });
+        bucket.seen = seq;
+        bucket
+    }
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
+impl RateLimiter {
+    pub fn new(rate: f64, burst: u32, trust_forwarded: bool) -> RateLimiter {
+        RateLimiter {
+            rate: rate,
+            burst: burst as f64,
+            trust_forwarded: trust_forwarded,
+            buckets: Mutex::new(Buckets::new()),
+        }
+    }
+    pub fn from_config(http_config: &toml::Value) -> Result<Option<RateLimiter>, String> {
+        let rate = match http_config.get("rate_limit_per_sec") {
+            Some(val) => {
+                try!(val.as_float()
+                         .or(val.as_integer().map(|v| v as f64))
+                         .ok_or("rate_limit_per_sec must be a number".to_string()))
+            }
+            None => return Ok(None),
+        };
+        if rate <= 0.0 {
+            return Err("rate_limit_per_sec must be positive".to_string());
+        }
+        let burst = http_config
+            .get("burst")
+            .and_then(|val| val.as_integer())
+            .unwrap_or(rate.ceil() as i64);
+        if burst < 1 || burst > u32::max_value() as i64 {
+            return Err("burst must be at least 1".to_string());
+        }
+        let burst = burst as u32;
+        let trust_forwarded = trust_forwarded_headers(http_config);
+        Ok(Some(RateLimiter::new(rate, burst, trust_forwarded)))
+    }
+    /// Take a token of `client`. Returns the seconds until the next request is allowed
+    /// if no token is available.
+    pub fn acquire(&self, client: &str, now: Instant) -> Result<(), u64> {
+        let (rate, burst) = (self.rate, self.burst);
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.get(client, now, burst, MAX_CLIENTS);
+        bucket.refill(now, rate, burst);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / rate).ceil() as u64)
+        }
+    }
+    fn client<D>(&self, req: &Request<D>) -> String {
+        if self.trust_forwarded {
+            if let Some(client) = forwarded_client(&req.origin.headers) {
+                return client;
+            }
+        }
+        req.origin.remote_addr.ip().to_string()
+    }
+}
+
+/// Client address added by the trusted proxy. Earlier `X-Forwarded-For` entries are
+/// sent by the client and can't be trusted.
+fn forwarded_client(headers: &Headers) -> Option<String> {
+    headers
+        .get_raw("X-Forwarded-For")
+        .and_then(|vals| vals.last())
+        .and_then(|val| str::from_utf8(val).ok())
+        .and_then(|val| val.rsplit(',').next())
+        .map(|val| val.trim().to_string())
+        .and_then(|val| if val.is_empty() { None } else { Some(val) })
+}
+
+/// Tile requests like /osm/6/33/22.pbf. Metadata and viewer requests are not limited.
+pub fn is_tile_path(path: &str) -> bool {
+    let path = path.split('?').next().unwrap_or("");
+    path.split('/').count() == 5 && path.starts_with('/')
+}
+
+/// Tile paths and WMTS GetTile requests
+pub fn is_tile_request(uri: &str) -> bool {
+    let mut parts = uri.splitn(2, '?');
+    let path = parts.next().unwrap_or("");
+    if path == "/wmts" {
+        let params = wmts::parse_query(parts.next().unwrap_or(""));
+        params
+            .iter()
+            .any(|&(ref key, ref value)| key == "request" && value.eq_ignore_ascii_case("GetTile"))
+    } else {
+        is_tile_path(path)
+    }
+}
+
+impl<D> Middleware<D> for RateLimiter {
+    fn invoke<'mw, 'conn>(&'mw self,
+                          req: &mut Request<'mw, 'conn, D>,
+                          mut res: Response<'mw, D>)
+                          -> MiddlewareResult<'mw, D> {
+        let limited = is_tile_request(&req.origin.uri.to_string());
+        if limited {
+            let client = self.client(req);
+            if let Err(retry_after) = self.acquire(&client, Instant::now()) {
+                debug!("Rate limit exceeded for {}", client);
+                res.set(StatusCode::TooManyRequests);
+                res.headers_mut().set(RetryAfter(retry_after));
+                return res.send("Too many requests");
+            }
+        }
+        res.next_middleware()
+    }
+}
+
+
+#[test]
+fn test_rate_limit() {
+    use std::time::Duration;
+
+    let limiter = RateLimiter::new(10.0, 5, false);
+    let start = Instant::now();
+    for _ in 0..5 {
+        assert_eq!(limiter.acquire("10.0.0.1", start), Ok(()));
+    }
+    assert_eq!(limiter.acquire("10.0.0.1", start), Err(1));
+    assert_eq!(limiter.acquire("10.0.0.2", start), Ok(()));
+    // One token per 100ms
+    let later = start + Duration::from_millis(100);
+    assert_eq!(limiter.acquire("10.0.0.1", later), Ok(()));
+    assert_eq!(limiter.acquire("10.0.0.1", later), Err(1));
+    // Refill up to burst size
+    let later = start + Duration::from_secs(10);
+    for _ in 0..5 {
+        assert_eq!(limiter.acquire("10.0.0.1", later), Ok(()));
+    }
+    assert!(limiter.acquire("10.0.0.1", later).is_err());
+
+    let limiter = RateLimiter::new(0.1, 1, false);
+    assert_eq!(limiter.acquire("10.0.0.1", start), Ok(()));
+    assert_eq!(limiter.acquire("10.0.0.1", start), Err(10));
+}
+
+#[test]
+fn test_rate_limit_concurrent() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    let limiter = Arc::new(RateLimiter::new(1.0, 20, false));
+    let accepted = Arc::new(AtomicUsize::new(0));
+    let now = Instant::now();
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let limiter = limiter.clone();
+            let accepted = accepted.clone();
+            thread::spawn(move || for _ in 0..100 {
+                              if limiter.acquire("10.0.0.1", now).is_ok() {
+                                  accepted.fetch_add(1, Ordering::SeqCst);
+                              }
+                          })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(accepted.load(Ordering::SeqCst), 20);
+}
+
+#[test]
+fn test_rate_limit_config() {
+    use core::parse_config;
+
+    let config = parse_config("".to_string(), "").unwrap();
+    assert!(RateLimiter::from_config(&config).unwrap().is_none());
+    let config = parse_config("rate_limit_per_sec = 2.5".to_string(), "").unwrap();
+    let limiter = RateLimiter::from_config(&config).unwrap().unwrap();
+    assert_eq!(limiter.rate, 2.5);
+    assert_eq!(limiter.burst, 3.0);
+    let config = parse_config("rate_limit_per_sec = 50\nburst = 200".to_string(), "").unwrap();
+    let limiter = RateLimiter::from_config(&config).unwrap().unwrap();
+    assert_eq!(limiter.burst, 200.0);
+    let config = parse_config("rate_limit_per_sec = 0".to_string(), "").unwrap();
+    assert_eq!(RateLimiter::from_config(&config).err(),
+               Some("rate_limit_per_sec must be positive".to_string()));
+
+    assert!(is_tile_path("/osm/6/33/22.pbf"));
+    assert!(is_tile_path("/osm/6/33/22.grid.json"));
+    assert!(!is_tile_path("/osm.json"));
+    assert!(!is_tile_path("/osm/metadata.json"));
+    assert!(!is_tile_path("/fonts/Roboto-Regular.ttf"));
+    assert!(!is_tile_path("/index.json"));
+    assert!(is_tile_request("/osm/6/33/22.pbf?key=1"));
+    assert!(is_tile_request("/wmts?SERVICE=WMTS&REQUEST=GetTile&LAYER=osm"));
+    assert!(!is_tile_request("/wmts?service=WMTS&request=GetCapabilities"));
+    assert!(!is_tile_request("/index.json"));
+
+    let config = parse_config("rate_limit_per_sec = 5\nburst = -1".to_string(), "").unwrap();
+    assert_eq!(RateLimiter::from_config(&config).err(),
+               Some("burst must be at least 1".to_string()));
+}
+
+#[test]
+fn test_rate_limit_eviction() {
+    let mut buckets = Buckets::new();
+    let now = Instant::now();
+    buckets.get("10.0.0.1", now, 5.0, 2).tokens = 0.0;
+    buckets.get("10.0.0.2", now, 5.0, 2);
+    // Least recently seen client is removed
+    buckets.get("10.0.0.1", now, 5.0, 2);
+    buckets.get("10.0.0.3", now, 5.0, 2);
+    assert_eq!(buckets.len(), 2);
+    assert!(buckets.buckets.contains_key("10.0.0.1"));
+    assert!(!buckets.buckets.contains_key("10.0.0.2"));
+    assert_eq!(buckets.get("10.0.0.1", now, 5.0, 2).tokens, 0.0);
+    assert_eq!(buckets.lru.len(), 2);
+}
+
+#[test]
+fn test_forwarded_client() {
+    let mut headers = Headers::new();
+    assert_eq!(forwarded_client(&headers), None);
+    headers.set_raw("X-Forwarded-For", vec![b"1.2.3.4, 10.0.0.1".to_vec()]);
+    assert_eq!(forwarded_client(&headers), Some("10.0.0.1".to_string()));
+    headers.set_raw("X-Forwarded-For",
+                    vec![b"1.2.3.4".to_vec(), b"5.6.7.8, 10.0.0.2".to_vec()]);
+    assert_eq!(forwarded_client(&headers), Some("10.0.0.2".to_string()));
+}
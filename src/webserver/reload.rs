@@ -0,0 +1,115 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use service::mvt::MvtService;
+#[cfg(unix)]
+use libc;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, ATOMIC_BOOL_INIT, Ordering};
+use std::thread;
+use std::time::Duration;
+
+
+/// Service shared by all request handlers, which can be replaced while the server is running
+#[derive(Clone)]
+pub struct ServiceHandle {
+    service: Arc<RwLock<Arc<MvtService>>>,
+}
+
+impl ServiceHandle {
+    pub fn new(service: MvtService) -> ServiceHandle {
+        ServiceHandle { service: Arc::new(RwLock::new(Arc::new(service))) }
+    }
+    /// Current service. Running requests keep the instance they started with.
+    pub fn current(&self) -> Arc<MvtService> {
+        self.service.read().unwrap().clone()
+    }
+    pub fn replace(&self, service: MvtService) {
+        *self.service.write().unwrap() = Arc::new(service);
+    }
+}
+
+static RELOAD_REQUESTED: AtomicBool = ATOMIC_BOOL_INIT;
+
+#[cfg(unix)]
+extern "C" fn on_sighup(_signal: libc::c_int) {
+    RELOAD_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Request configuration reload on SIGHUP.
+/// Has to be installed after the termination handler, which catches SIGHUP as well.
+#[cfg(unix)]
+pub fn install_reload_handler() {
+    unsafe {
+        libc::signal(libc::SIGHUP,
+                     on_sighup as extern "C" fn(libc::c_int) as libc::sighandler_t);
+    }
+}
+
+#[cfg(not(unix))]
+pub fn install_reload_handler() {}
+
+/// Check and reset reload request
+pub fn reload_requested() -> bool {
+    RELOAD_REQUESTED.swap(false, Ordering::SeqCst)
+}
+
+/// Replace the service of `handle` with the result of `load` after each reload request.
+/// On errors or without new service the current service is kept.
+pub fn watch_reload<F>(handle: ServiceHandle, load: F)
+    where F: Fn() -> Result<Option<MvtService>, String> + Send + 'static
+{
+    thread::spawn(move || loop {
+                      thread::sleep(Duration::from_secs(1));
+                      if !reload_requested() {
+                          continue;
+                      }
+                      info!("SIGHUP received");
+                      match load() {
+                          Ok(Some(service)) => {
+                              handle.replace(service);
+                              info!("Configuration reloaded");
+                          }
+                          Ok(None) => {}
+                          Err(err) => {
+                              error!("Reloading configuration failed - keeping current configuration: {}",
+                                     err)
+                          }
+                      }
+                  });
+}
+
+
+#[test]
+fn test_replace_service() {
+    use core::{Config, parse_config};
+
+    let service_with_tileset = |name: &str| {
+        let toml = format!(r#"
+            [datasource]
+            type = "postgis"
+            url = "postgresql://pi@localhost/natural_earth_vectors"
+            [grid]
+            predefined = "web_mercator"
+            [[tileset]]
+            name = "{}"
+            [[tileset.layer]]
+            name = "points"
+            table_name = "ne_10m_populated_places"
+            "#,
+                           name);
+        MvtService::from_config(&parse_config(toml, "").unwrap()).unwrap()
+    };
+    let handle = ServiceHandle::new(service_with_tileset("before"));
+    let running = handle.current();
+    handle.clone().replace(service_with_tileset("after"));
+    assert_eq!(running.tilesets[0].name, "before");
+    assert_eq!(handle.current().tilesets[0].name, "after");
+
+    assert!(!reload_requested());
+    RELOAD_REQUESTED.store(true, Ordering::SeqCst);
+    assert!(reload_requested());
+    assert!(!reload_requested());
+}
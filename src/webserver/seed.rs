@@ -0,0 +1,240 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use core::grid::Extent;
+use webserver::reload::ServiceHandle;
+use webserver::shutdown::Shutdown;
+use serde_json;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::thread;
+
+
+/// Number of seed jobs waiting for the worker
+const SEED_QUEUE_SIZE: usize = 4;
+/// Number of finished seed jobs kept for status requests
+const SEED_JOBS_KEPT: usize = 100;
+/// Number of seeded tiles between progress updates
+const SEED_PROGRESS_INTERVAL: u64 = 100;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum SeedState {
+    Queued,
+    Running,
+    Done,
+    /// Stopped by server shutdown
+    Cancelled,
+    Failed(String),
+}
+
+/// Region of a tileset to seed into the cache
+#[derive(Clone, PartialEq, Debug)]
+pub struct SeedJob {
+    pub id: u64,
+    pub tileset: String,
+    pub extent: Extent,
+    pub minzoom: Option<u8>,
+    pub maxzoom: u8,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SeedStatus {
+    pub job: SeedJob,
+    pub state: SeedState,
+    pub zoom: Option<u8>,
+    pub tiles: u64,
+    pub total: u64,
+}
+
+impl SeedStatus {
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = json!({
+            "job": self.job.id,
+            "tileset": self.job.tileset,
+            "status": match self.state {
+                SeedState::Queued => "queued",
+                SeedState::Running => "running",
+                SeedState::Done => "done",
+                SeedState::Cancelled => "cancelled",
+                SeedState::Failed(_) => "error",
+            },
+            "zoom": self.zoom,
+            "tiles": self.tiles,
+            "total": self.total
+        });
+        if let SeedState::Failed(ref err) = self.state {
+            json["error"] = json!(err);
+        }
+        json
+    }
+}
+
+type SeedJobs = Arc<Mutex<BTreeMap<u64, SeedStatus>>>;
+
+/// Queue of seed jobs processed one after the other by a background worker
+pub struct Seeder {
+    queue: Mutex<SyncSender<SeedJob>>,
+    jobs: SeedJobs,
+    next_id: Mutex<u64>,
+}
+
+impl Seeder {
+    fn new(capacity: usize) -> (Seeder, Receiver<SeedJob>) {
+        let (sender, receiver) = sync_channel(capacity);
+        let seeder = Seeder {
+            queue: Mutex::new(sender),
+            jobs: Arc::new(Mutex::new(BTreeMap::new())),
+            next_id: Mutex::new(1),
+        };
+        (seeder, receiver)
+    }
+    /// Start the background worker. Seeding stops when the server is shutting down.
+    pub fn start(handle: ServiceHandle, shutdown: Arc<Shutdown>) -> Seeder {
+        let (seeder, receiver) = Seeder::new(SEED_QUEUE_SIZE);
+        let jobs = seeder.jobs.clone();
+        thread::spawn(move || for job in receiver.iter() {
+                          run_job(&handle, &shutdown, &jobs, job);
+                      });
+        seeder
+    }
+    /// Queue a seed job. Fails if too many jobs are waiting.
+    pub fn submit(&self,
+                  tileset: &str,
+                  extent: Extent,
+                  minzoom: Option<u8>,
+                  maxzoom: u8)
+                  -> Result<SeedStatus, String> {
+        let mut next_id = self.next_id.lock().unwrap();
+        let job = SeedJob {
+            id: *next_id,
+            tileset: tileset.to_string(),
+            extent: extent,
+            minzoom: minzoom,
+            maxzoom: maxzoom,
+        };
+        let status = SeedStatus {
+            job: job.clone(),
+            state: SeedState::Queued,
+            zoom: None,
+            tiles: 0,
+            total: 0,
+        };
+        // Register job before the worker can update it
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            jobs.insert(job.id, status.clone());
+            // Remove oldest finished jobs
+            while jobs.len() > SEED_JOBS_KEPT {
+                let finished = jobs.iter()
+                    .find(|&(_, status)| status.state != SeedState::Queued &&
+                                         status.state != SeedState::Running)
+                    .map(|(id, _)| *id);
+                match finished {
+                    Some(id) => jobs.remove(&id),
+                    None => break,
+                };
+            }
+        }
+        match self.queue.lock().unwrap().try_send(job.clone()) {
+            Ok(()) => {
+                *next_id += 1;
+                Ok(status)
+            }
+            Err(err) => {
+                self.jobs.lock().unwrap().remove(&job.id);
+                match err {
+                    TrySendError::Full(_) => Err("Too many seed jobs queued".to_string()),
+                    TrySendError::Disconnected(_) => Err("Seed worker not running".to_string()),
+                }
+            }
+        }
+    }
+    /// Status of queued, running and recently finished jobs of a tileset
+    pub fn status(&self, tileset: &str) -> Vec<SeedStatus> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|status| status.job.tileset == tileset)
+            .cloned()
+            .collect()
+    }
+}
+
+fn update_status<F>(jobs: &SeedJobs, id: u64, update: F)
+    where F: FnOnce(&mut SeedStatus)
+{
+    if let Some(status) = jobs.lock().unwrap().get_mut(&id) {
+        update(status);
+    }
+}
+
+fn run_job(handle: &ServiceHandle, shutdown: &Shutdown, jobs: &SeedJobs, job: SeedJob) {
+    update_status(jobs, job.id, |status| status.state = SeedState::Running);
+    let service = handle.current();
+    let mut cancelled = false;
+    let result = service.seed(&job.tileset,
+                              job.minzoom,
+                              Some(job.maxzoom),
+                              Some(job.extent.clone()),
+                              |zoom, tiles, total| {
+        if tiles % SEED_PROGRESS_INTERVAL == 0 || tiles == total {
+            update_status(jobs, job.id, |status| {
+                status.zoom = Some(zoom);
+                status.tiles = tiles;
+                status.total = total;
+            });
+        }
+        cancelled = shutdown.is_shutting_down();
+        !cancelled
+    });
+    update_status(jobs, job.id, |status| {
+        status.state = match result {
+            Ok(tiles) => {
+                status.tiles = tiles;
+                if cancelled {
+                    SeedState::Cancelled
+                } else {
+                    SeedState::Done
+                }
+            }
+            Err(err) => SeedState::Failed(err),
+        }
+    });
+}
+
+
+#[test]
+fn test_seed_queue() {
+    let (seeder, receiver) = Seeder::new(2);
+    let extent = Extent {
+        minx: 0.0,
+        miny: 0.0,
+        maxx: 10.0,
+        maxy: 10.0,
+    };
+    let status = seeder.submit("osm", extent.clone(), None, 12).unwrap();
+    assert_eq!(status.job.id, 1);
+    assert_eq!(status.to_json(),
+               json!({"job": 1, "tileset": "osm", "status": "queued", "zoom": null,
+                      "tiles": 0, "total": 0}));
+    assert_eq!(seeder.submit("osm", extent.clone(), Some(2), 14).unwrap().job.id, 2);
+    // Queue is full
+    assert_eq!(seeder.submit("osm", extent.clone(), None, 12).err(),
+               Some("Too many seed jobs queued".to_string()));
+    assert_eq!(seeder.status("osm").len(), 2);
+    assert!(seeder.status("other").is_empty());
+
+    let job = receiver.recv().unwrap();
+    assert_eq!(job.maxzoom, 12);
+    update_status(&seeder.jobs,
+                  job.id,
+                  |status| status.state = SeedState::Failed("Unknown tileset 'osm'".to_string()));
+    let json = seeder.status("osm")[0].to_json();
+    assert_eq!(json["status"], "error");
+    assert_eq!(json["error"], "Unknown tileset 'osm'");
+    assert_eq!(seeder.submit("osm", extent, None, 12).unwrap().job.id, 3);
+}
@@ -0,0 +1,115 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+use nickel::{Request, Response, Middleware, MiddlewareResult};
+use nickel::status::StatusCode;
+use hyper::header::Connection;
+use ctrlc;
+use webserver::reload::install_reload_handler;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+
+/// Shutdown state shared between signal handling and request handlers
+pub struct Shutdown {
+    shutting_down: AtomicBool,
+    active_requests: AtomicUsize,
+}
+
+/// Marks a request as in-flight until dropped
+pub struct RequestGuard<'a> {
+    shutdown: &'a Shutdown,
+}
+
+impl Shutdown {
+    pub fn new() -> Shutdown {
+        Shutdown {
+            shutting_down: AtomicBool::new(false),
+            active_requests: AtomicUsize::new(0),
+        }
+    }
+    /// Stop accepting new requests
+    pub fn begin(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+    pub fn active_requests(&self) -> usize {
+        self.active_requests.load(Ordering::SeqCst)
+    }
+    /// Register an in-flight request
+    pub fn request(&self) -> RequestGuard<'_> {
+        self.active_requests.fetch_add(1, Ordering::SeqCst);
+        RequestGuard { shutdown: self }
+    }
+    /// Wait until all in-flight requests are finished. Returns false on timeout.
+    pub fn wait_for_requests(&self, timeout: Duration) -> bool {
+        let start = Instant::now();
+        while self.active_requests() > 0 {
+            if start.elapsed() >= timeout {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        true
+    }
+}
+
+impl<'a> Drop for RequestGuard<'a> {
+    fn drop(&mut self) {
+        self.shutdown.active_requests.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Middleware rejecting requests during shutdown.
+// hyper doesn't support closing the listening socket, so new connections are answered with 503.
+pub struct RejectOnShutdown(pub Arc<Shutdown>);
+
+impl<D> Middleware<D> for RejectOnShutdown {
+    fn invoke<'mw, 'conn>(&'mw self,
+                          _req: &mut Request<'mw, 'conn, D>,
+                          mut res: Response<'mw, D>)
+                          -> MiddlewareResult<'mw, D> {
+        if self.0.is_shutting_down() {
+            res.set(StatusCode::ServiceUnavailable);
+            res.set(Connection::close());
+            return res.send("Server is shutting down");
+        }
+        res.next_middleware()
+    }
+}
+
+/// Block until SIGINT or SIGTERM is received
+pub fn wait_for_signal() -> Result<(), String> {
+    let (tx, rx) = mpsc::channel();
+    try!(ctrlc::set_handler(move || { let _ = tx.send(()); })
+             .map_err(|e| format!("Error installing signal handler: {}", e)));
+    // SIGHUP is caught by ctrlc as well
+    install_reload_handler();
+    rx.recv().map_err(|e| format!("Error waiting for signal: {}", e))
+}
+
+
+#[test]
+fn test_active_requests() {
+    let shutdown = Shutdown::new();
+    assert_eq!(shutdown.active_requests(), 0);
+    {
+        let _req1 = shutdown.request();
+        let _req2 = shutdown.request();
+        assert_eq!(shutdown.active_requests(), 2);
+        assert!(!shutdown.wait_for_requests(Duration::from_millis(10)));
+    }
+    assert_eq!(shutdown.active_requests(), 0);
+    assert!(shutdown.wait_for_requests(Duration::from_millis(10)));
+
+    assert!(!shutdown.is_shutting_down());
+    shutdown.begin();
+    assert!(shutdown.is_shutting_down());
+}
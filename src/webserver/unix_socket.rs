@@ -0,0 +1,230 @@
+//
+// Copyright (c) Pirmin Kalberer. All rights reserved.
+// Licensed under the MIT License. See LICENSE file in the project root for full license information.
+//
+
+//! Unix domain socket listener.
+//!
+//! The HTTP server (nickel 0.10 / hyper 0.10) only listens on TCP sockets, so the web
+//! server is bound to an ephemeral loopback port and connections accepted on the Unix
+//! domain socket are relayed to it.
+//! The loopback port is reachable by all local users, so the web server only answers
+//! requests of connections opened by the relay (see `RelayConnections`).
+
+use nickel::{Request, Response, Middleware, MiddlewareResult};
+use nickel::status::StatusCode;
+use hyper::header::Connection;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+
+/// Default permissions of the socket file (read/write for owner and group)
+pub const DEFAULT_SOCKET_MODE: u32 = 0o660;
+
+/// Parse octal file permissions like "660"
+pub fn parse_socket_mode(mode: &str) -> Result<u32, String> {
+    match u32::from_str_radix(mode, 8) {
+        Ok(mode) if mode <= 0o777 => Ok(mode),
+        _ => Err(format!("Invalid unix_socket_mode '{}' (expected octal permissions like \"660\")", mode)),
+    }
+}
+
+/// Local addresses of the open relay connections to the web server.
+/// As middleware, it rejects requests of all other connections.
+#[derive(Clone)]
+pub struct RelayConnections(Arc<Mutex<HashSet<SocketAddr>>>);
+
+impl RelayConnections {
+    pub fn new() -> RelayConnections {
+        RelayConnections(Arc::new(Mutex::new(HashSet::new())))
+    }
+    pub fn contains(&self, addr: &SocketAddr) -> bool {
+        self.0.lock().unwrap().contains(addr)
+    }
+    fn insert(&self, addr: SocketAddr) {
+        self.0.lock().unwrap().insert(addr);
+    }
+    fn remove(&self, addr: &SocketAddr) {
+        self.0.lock().unwrap().remove(addr);
+    }
+}
+
+impl<D> Middleware<D> for RelayConnections {
+    fn invoke<'mw, 'conn>(&'mw self,
+                          req: &mut Request<'mw, 'conn, D>,
+                          mut res: Response<'mw, D>)
+                          -> MiddlewareResult<'mw, D> {
+        if !self.contains(&req.origin.remote_addr) {
+            warn!("Rejected request from {} not relayed from Unix domain socket",
+                  req.origin.remote_addr);
+            res.set(StatusCode::Forbidden);
+            res.set(Connection::close());
+            return res.send("Forbidden");
+        }
+        res.next_middleware()
+    }
+}
+
+/// Unix domain socket relaying connections to a TCP address.
+/// The socket file is removed when dropped.
+pub struct UnixSocketRelay {
+    path: PathBuf,
+    listener: UnixListener,
+    connections: RelayConnections,
+}
+
+impl UnixSocketRelay {
+    /// Bind socket at `path` with permissions `mode`.
+    /// A stale socket file of a previous run is replaced, other files are left untouched.
+    pub fn bind(path: &str, mode: u32) -> io::Result<UnixSocketRelay> {
+        let path = PathBuf::from(path);
+        if let Ok(meta) = fs::symlink_metadata(&path) {
+            if !meta.file_type().is_socket() {
+                return Err(io::Error::new(io::ErrorKind::AlreadyExists,
+                                          format!("'{}' exists and is not a socket",
+                                                  path.display())));
+            }
+            try!(fs::remove_file(&path));
+        }
+        let listener = try!(UnixListener::bind(&path));
+        try!(fs::set_permissions(&path, fs::Permissions::from_mode(mode)));
+        Ok(UnixSocketRelay {
+               path: path,
+               listener: listener,
+               connections: RelayConnections::new(),
+           })
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Connections opened by the relay, to be checked by the web server
+    pub fn connections(&self) -> RelayConnections {
+        self.connections.clone()
+    }
+    /// Relay accepted connections to `target`
+    pub fn start(&self, target: SocketAddr) -> io::Result<()> {
+        let listener = try!(self.listener.try_clone());
+        let connections = self.connections.clone();
+        thread::spawn(move || for stream in listener.incoming() {
+                          match stream {
+                              Ok(stream) => {
+                                  let connections = connections.clone();
+                                  thread::spawn(move || if let Err(err) =
+                                      relay(stream, target, &connections) {
+                                                    debug!("Unix socket connection: {}", err);
+                                                });
+                              }
+                              Err(err) => warn!("Unix socket accept failed: {}", err),
+                          }
+                      });
+        Ok(())
+    }
+}
+
+impl Drop for UnixSocketRelay {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Copy data in both directions until both peers closed their side
+fn relay(client: UnixStream, target: SocketAddr, connections: &RelayConnections) -> io::Result<()> {
+    let server = try!(TcpStream::connect(target));
+    // Registered before any request data is sent and removed before the connection is closed
+    let local_addr = try!(server.local_addr());
+    connections.insert(local_addr);
+    let mut client_read = try!(client.try_clone());
+    let mut server_write = try!(server.try_clone());
+    let upstream = thread::spawn(move || {
+        let _ = io::copy(&mut client_read, &mut server_write);
+        let _ = server_write.shutdown(Shutdown::Write);
+    });
+    let mut server_read = server;
+    let mut client_write = client;
+    let result = io::copy(&mut server_read, &mut client_write);
+    let _ = client_write.shutdown(Shutdown::Write);
+    let _ = upstream.join();
+    connections.remove(&local_addr);
+    result.map(|_| ())
+}
+
+#[test]
+fn test_parse_socket_mode() {
+    assert_eq!(parse_socket_mode("660"), Ok(0o660));
+    assert_eq!(parse_socket_mode("0666"), Ok(0o666));
+    assert!(parse_socket_mode("990").is_err());
+    assert!(parse_socket_mode("7777").is_err());
+}
+
+#[test]
+fn test_unix_socket_relay() {
+    use std::env;
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    let tcp = TcpListener::bind("127.0.0.1:0").unwrap();
+    let target = tcp.local_addr().unwrap();
+    let (peer_tx, peer_rx) = mpsc::channel();
+    let (reply_tx, reply_rx) = mpsc::channel();
+    thread::spawn(move || for stream in tcp.incoming() {
+                      let mut stream = stream.unwrap();
+                      let mut request = String::new();
+                      stream.read_to_string(&mut request).unwrap();
+                      // Keep connection open until peer is checked
+                      peer_tx.send(stream.peer_addr().unwrap()).unwrap();
+                      reply_rx.recv().unwrap();
+                      stream.write_all(request.to_uppercase().as_bytes()).unwrap();
+                  });
+
+    let path = env::temp_dir().join(format!("t_rex_test_{}.sock", target.port()));
+    let path = path.to_str().unwrap();
+    let relay = UnixSocketRelay::bind(path, 0o600).unwrap();
+    relay.start(target).unwrap();
+    let mode = fs::metadata(path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    let connections = relay.connections();
+
+    let mut client = UnixStream::connect(path).unwrap();
+    client.write_all(b"get /").unwrap();
+    client.shutdown(Shutdown::Write).unwrap();
+    let peer = peer_rx.recv().unwrap();
+    // Connection of the relay is known while open
+    assert!(connections.contains(&peer));
+    reply_tx.send(()).unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).unwrap();
+    assert_eq!(response, "GET /");
+
+    // Direct connections to the TCP port are not relay connections
+    let mut direct = TcpStream::connect(target).unwrap();
+    direct.shutdown(Shutdown::Write).unwrap();
+    let peer = peer_rx.recv().unwrap();
+    assert!(!connections.contains(&peer));
+    reply_tx.send(()).unwrap();
+    let mut response = String::new();
+    direct.read_to_string(&mut response).unwrap();
+    assert_eq!(response, "");
+
+    // Socket file is removed on drop
+    drop(relay);
+    assert!(!Path::new(path).exists());
+
+    // Stale socket files are replaced, other files are not
+    let stale = UnixListener::bind(path).unwrap();
+    drop(stale);
+    assert!(Path::new(path).exists());
+    let relay = UnixSocketRelay::bind(path, 0o600).unwrap();
+    drop(relay);
+    File::create(path).unwrap();
+    assert!(UnixSocketRelay::bind(path, 0o600).is_err());
+    fs::remove_file(path).unwrap();
+}